@@ -0,0 +1,103 @@
+/*
+ *     Copyright 2026 The Dragonfly Authors
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use dragonfly_api::common::v2::ObjectStorage as ObjectStorageInfo;
+use dragonfly_client_backend::object_storage::{ObjectStorage, Scheme};
+use dragonfly_client_backend::{Backend, GetRequest};
+use dragonfly_client_config::dfdaemon::Config;
+use std::sync::Arc;
+use tokio::io::AsyncReadExt;
+use tokio::runtime::Runtime;
+use wiremock::{
+    matchers::{method, path},
+    Mock, MockServer, ResponseTemplate,
+};
+
+// Sizes of the objects to read in each benchmark.
+const OBJECT_SIZES: [usize; 2] = [4 * 1024 * 1024, 16 * 1024 * 1024];
+
+// Size of the read buffer of the reader path.
+const READ_BUFFER_SIZE: usize = 64 * 1024;
+
+fn get_request(server: &MockServer, size: usize) -> GetRequest {
+    GetRequest::builder()
+        .task_id("bench")
+        .piece_id("bench")
+        .url(format!("s3://bench-bucket/{}", size))
+        .object_storage(ObjectStorageInfo {
+            region: Some("bench-region".into()),
+            endpoint: Some(server.uri()),
+            access_key_id: Some("access-key-id".into()),
+            access_key_secret: Some("access-key-secret".into()),
+            ..Default::default()
+        })
+        .build()
+}
+
+async fn read_by_reader(object_storage: &ObjectStorage, request: GetRequest) -> usize {
+    let mut response = object_storage.get(request).await.unwrap();
+    let mut buffer = vec![0; READ_BUFFER_SIZE];
+    let mut read_size = 0;
+    loop {
+        let n = response.reader.read(&mut buffer).await.unwrap();
+        if n == 0 {
+            return read_size;
+        }
+
+        read_size += black_box(n);
+    }
+}
+
+async fn read_by_buffer(object_storage: &ObjectStorage, request: GetRequest) -> usize {
+    let buffer = object_storage.read_buffer(request).await.unwrap();
+    buffer.map(|chunk| black_box(chunk).len()).sum()
+}
+
+pub fn read_buffer(c: &mut Criterion) {
+    let rt: Runtime = Runtime::new().unwrap();
+    let server = rt.block_on(async {
+        let server = MockServer::start().await;
+        for size in OBJECT_SIZES {
+            Mock::given(method("GET"))
+                .and(path(format!("/bench-bucket/{}", size)))
+                .respond_with(ResponseTemplate::new(200).set_body_bytes(vec![0; size]))
+                .mount(&server)
+                .await;
+        }
+
+        server
+    });
+
+    let object_storage = ObjectStorage::new(Scheme::S3, Arc::new(Config::default())).unwrap();
+    let mut group = c.benchmark_group("Read Object");
+
+    for size in OBJECT_SIZES {
+        group.throughput(Throughput::Bytes(size as u64));
+        group.bench_with_input(BenchmarkId::new("Reader", size), &size, |b, size| {
+            b.iter(|| rt.block_on(read_by_reader(&object_storage, get_request(&server, *size))));
+        });
+
+        group.bench_with_input(BenchmarkId::new("Buffer", size), &size, |b, size| {
+            b.iter(|| rt.block_on(read_by_buffer(&object_storage, get_request(&server, *size))));
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, read_buffer);
+criterion_main!(benches);
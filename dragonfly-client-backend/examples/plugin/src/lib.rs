@@ -16,8 +16,8 @@
 
 use async_trait::async_trait;
 use dragonfly_client_backend::{
-    Backend, Body, ExistsRequest, GetRequest, GetResponse, PutRequest, PutResponse, StatRequest,
-    StatResponse,
+    Backend, Body, CopyRequest, ExistsRequest, GetRequest, GetResponse, PutRequest, PutResponse,
+    StatRequest, StatResponse,
 };
 use dragonfly_client_core::{Error, Result};
 
@@ -62,6 +62,15 @@ impl Backend for Hdfs {
         println!("HDFS exists url: {}", request.url);
         Err(Error::Unimplemented)
     }
+
+    /// copy copies the object in the backend.
+    async fn copy(&self, request: CopyRequest) -> Result<()> {
+        println!(
+            "HDFS copy url: {} -> {}",
+            request.source_url, request.dest_url
+        );
+        Err(Error::Unimplemented)
+    }
 }
 
 /// register_plugin is a function that returns a Box<dyn Backend + Send + Sync>.
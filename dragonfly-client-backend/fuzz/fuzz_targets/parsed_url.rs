@@ -0,0 +1,35 @@
+/*
+ *     Copyright 2026 The Dragonfly Authors
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#![no_main]
+
+use dragonfly_client_backend::object_storage::ParsedURL;
+use libfuzzer_sys::fuzz_target;
+
+// Run with `cargo +nightly fuzz run parsed_url` in the dragonfly-client-backend directory.
+fuzz_target!(|data: &[u8]| {
+    let Ok(url) = std::str::from_utf8(data) else {
+        return;
+    };
+
+    // The parsed URL is used to make the entry URLs when listing the directory, so the
+    // functions on the parsed URL must not panic either.
+    if let Ok(parsed_url) = ParsedURL::parse(url) {
+        if parsed_url.is_dir() {
+            parsed_url.make_url_by_entry_path("entry");
+        }
+    }
+});
@@ -0,0 +1,763 @@
+/*
+ *     Copyright 2026 The Dragonfly Authors
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Caching backend implementation for mirroring the reads to a local cache directory.
+//!
+//! The `CachingBackend` wraps any backend and serves the gets from the local cache directory,
+//! which avoids repeatedly pulling the same object from the remote storage. It is a composition
+//! over the Backend trait instead of a new scheme.
+//!
+//! # Cache Key
+//!
+//! The cached content is keyed by the URL, the ETag, the version and the range of the request.
+//! The ETag is validated by the stat before serving the cached content, so the changed object
+//! is pulled again. The requests of the backends without the ETag, the multiple ranges and the
+//! suffix ranges are passed through without caching.
+//!
+//! # Population
+//!
+//! The cache miss serves the content of the wrapped backend from the first byte, and the content
+//! is mirrored to the cache file as it is read. The cache file is indexed once the content is
+//! read completely, and abandoned if the response is dropped before the end.
+//!
+//! # Eviction
+//!
+//! The total size of the cached content is limited by the max size, and the least recently
+//! used content is evicted. The content larger than the max size is never cached.
+
+use crate::{
+    cancel_body, decompress_body, digest_body, limit_body, AppendRequest, Backend, Body,
+    CopyRequest, ExistsRequest, GetRequest, GetResponse, PutRequest, PutResponse, StatRequest,
+    StatResponse,
+};
+use async_trait::async_trait;
+use bytes::Bytes;
+use dragonfly_api::common::v2::ObjectStorage;
+use dragonfly_client_core::Result as ClientResult;
+use lru::LruCache;
+use parking_lot::Mutex;
+use sha2::{Digest, Sha256};
+use std::future::Future;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{ready, Context, Poll};
+use tokio::fs;
+use tokio::io::{AsyncRead, AsyncWriteExt, ReadBuf};
+use tokio::sync::{mpsc, oneshot};
+use tokio_util::sync::PollSender;
+use tracing::{debug, error, instrument, warn};
+
+/// CacheIndex is the index of the cached content in the cache directory.
+struct CacheIndex {
+    /// Entries are the sizes of the cached content keyed by the cache key, which are ordered by
+    /// the recency of the gets.
+    entries: LruCache<String, u64>,
+
+    /// Size is the total size of the cached content.
+    size: u64,
+}
+
+/// Cache is the local cache directory with the index of the cached content, which is shared
+/// by the backend and the writers populating the cache.
+struct Cache {
+    /// Dir is the local cache directory.
+    dir: PathBuf,
+
+    /// Max size is the maximum total size of the cached content in bytes.
+    max_size: u64,
+
+    /// Index is the index of the cached content.
+    index: Mutex<CacheIndex>,
+}
+
+/// Cache implements the index and the files of the cached content.
+impl Cache {
+    /// WRITE_BUFFER_CHUNKS is the number of the chunks buffered for the cache writer, the read
+    /// of the response waits for the cache writer once the buffer is full.
+    const WRITE_BUFFER_CHUNKS: usize = 16;
+
+    /// Touch marks the cached content as recently used, and returns whether it is cached.
+    fn touch(&self, key: &str) -> bool {
+        self.index.lock().entries.get(key).is_some()
+    }
+
+    /// Insert indexes the cached content, and returns the keys of the evicted content.
+    fn insert(&self, key: String, size: u64) -> Vec<String> {
+        {
+            let mut index = self.index.lock();
+            if let Some(old_size) = index.entries.put(key, size) {
+                index.size -= old_size;
+            }
+
+            index.size += size;
+        }
+
+        self.evict()
+    }
+
+    /// Remove removes the cached content from the index.
+    fn remove(&self, key: &str) {
+        let mut index = self.index.lock();
+        if let Some(size) = index.entries.pop(key) {
+            index.size -= size;
+        }
+    }
+
+    /// Evict pops the least recently used content until the total size does not exceed the max
+    /// size, and returns the keys of the evicted content.
+    fn evict(&self) -> Vec<String> {
+        let mut index = self.index.lock();
+        let mut evicted = Vec::new();
+        while index.size > self.max_size {
+            let Some((key, size)) = index.entries.pop_lru() else {
+                break;
+            };
+
+            index.size -= size;
+            evicted.push(key);
+        }
+
+        evicted
+    }
+
+    /// Remove files removes the files of the evicted content.
+    async fn remove_files(&self, keys: Vec<String>) {
+        for key in keys {
+            if let Err(err) = fs::remove_file(self.dir.join(&key)).await {
+                warn!("remove cached file failed {}: {}", key, err);
+            }
+        }
+    }
+
+    /// Tee returns the reader mirroring the content of the reader to the cache file of the key,
+    /// the cache file is written by the spawned writer as the content is read.
+    fn tee(self: &Arc<Self>, key: String, reader: Body) -> Body {
+        let (sender, receiver) = mpsc::channel(Self::WRITE_BUFFER_CHUNKS);
+        let (completion_sender, completion) = oneshot::channel();
+        let cache = self.clone();
+        tokio::spawn(async move {
+            cache.write(key, receiver).await;
+            let _ = completion_sender.send(());
+        });
+
+        Box::new(TeeReader {
+            reader,
+            sender: Some(PollSender::new(sender)),
+            completion: Some(completion),
+        })
+    }
+
+    /// Write writes the content received from the tee reader to the cache file, and indexes
+    /// it once the content is read completely. The content is written to the temporary file
+    /// and renamed to the cache file, so the partial content is never served. The temporary
+    /// file is removed if the write fails, or the reader is dropped before the end.
+    async fn write(&self, key: String, mut receiver: mpsc::Receiver<Option<Bytes>>) {
+        let path = self.dir.join(&key);
+        let temp_path = path.with_extension(format!("{}.tmp", fastrand::u64(..)));
+        let result: ClientResult<Option<u64>> = async {
+            let mut file = fs::File::create(&temp_path).await?;
+            let mut size = 0;
+            while let Some(content) = receiver.recv().await {
+                let Some(content) = content else {
+                    file.flush().await?;
+                    fs::rename(&temp_path, &path).await?;
+                    return Ok(Some(size));
+                };
+
+                file.write_all(&content).await?;
+                size += content.len() as u64;
+            }
+
+            Ok(None)
+        }
+        .await;
+
+        match result {
+            Ok(Some(size)) => {
+                debug!("populated cache {}: {} bytes", key, size);
+                let evicted = self.insert(key, size);
+                self.remove_files(evicted).await;
+            }
+            Ok(None) => {
+                debug!("abandoned cache {} before the end of the content", key);
+                let _ = fs::remove_file(&temp_path).await;
+            }
+            Err(err) => {
+                error!("write cached file failed {}: {}", key, err);
+                let _ = fs::remove_file(&temp_path).await;
+            }
+        }
+    }
+}
+
+/// TeeReader wraps the reader of the response body, and mirrors the content read by the caller
+/// to the cache writer, so the caller is served from the first byte while the cache is
+/// populated. The content is still served if the cache writer fails.
+struct TeeReader {
+    /// Reader is the reader of the response body.
+    reader: Body,
+
+    /// Sender sends the content to the cache writer, and None marks the end of the content.
+    /// It is None once the end is sent or the cache writer fails.
+    sender: Option<PollSender<Option<Bytes>>>,
+
+    /// Completion is resolved once the cache writer finishes, the end of the content is
+    /// returned after it, so the content is cached once the body is read.
+    completion: Option<oneshot::Receiver<()>>,
+}
+
+/// TeeReader implements the AsyncRead trait.
+impl AsyncRead for TeeReader {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        let this = self.get_mut();
+
+        // Reserve the slot of the cache writer before reading, so the content read is never
+        // dropped by the full buffer.
+        if let Some(sender) = this.sender.as_mut() {
+            if ready!(sender.poll_reserve(cx)).is_err() {
+                this.sender = None;
+            }
+        }
+
+        // The end of the content is returned once the cache writer finishes.
+        if this.sender.is_none() {
+            if let Some(completion) = this.completion.as_mut() {
+                let _ = ready!(Pin::new(completion).poll(cx));
+                this.completion = None;
+            }
+        }
+
+        let filled = buf.filled().len();
+        let has_remaining = buf.remaining() > 0;
+        if let Err(err) = ready!(Pin::new(&mut this.reader).poll_read(cx, buf)) {
+            // Dropping the sender without the end abandons the cache file.
+            this.sender = None;
+            this.completion = None;
+            return Poll::Ready(Err(err));
+        }
+
+        let Some(sender) = this.sender.as_mut() else {
+            return Poll::Ready(Ok(()));
+        };
+
+        let content = &buf.filled()[filled..];
+        if !content.is_empty() {
+            if sender
+                .send_item(Some(Bytes::copy_from_slice(content)))
+                .is_err()
+            {
+                this.sender = None;
+                this.completion = None;
+            }
+
+            return Poll::Ready(Ok(()));
+        }
+
+        if !has_remaining {
+            return Poll::Ready(Ok(()));
+        }
+
+        // Send the end of the content, and wait for the cache writer before returning the end.
+        let _ = sender.send_item(None);
+        this.sender = None;
+        match this.completion.as_mut() {
+            Some(completion) => {
+                let _ = ready!(Pin::new(completion).poll(cx));
+                this.completion = None;
+                Poll::Ready(Ok(()))
+            }
+            None => Poll::Ready(Ok(())),
+        }
+    }
+}
+
+/// CachingBackend is the backend decorator mirroring the reads to a local cache directory.
+pub struct CachingBackend {
+    /// Backend is the wrapped backend.
+    backend: Box<dyn Backend + Send + Sync>,
+
+    /// Cache is the local cache directory.
+    cache: Arc<Cache>,
+}
+
+/// CachingBackend implements the caching of the gets.
+impl CachingBackend {
+    /// Create a new CachingBackend wrapping the backend. The cached content left in the cache
+    /// directory is indexed, so the cache survives the restart of the process.
+    pub fn new(
+        backend: Box<dyn Backend + Send + Sync>,
+        dir: PathBuf,
+        max_size: u64,
+    ) -> ClientResult<Self> {
+        std::fs::create_dir_all(&dir)?;
+
+        let mut index = CacheIndex {
+            entries: LruCache::unbounded(),
+            size: 0,
+        };
+
+        for entry in std::fs::read_dir(&dir)? {
+            let entry = entry?;
+            let metadata = entry.metadata()?;
+            let Some(key) = entry.file_name().to_str().map(str::to_string) else {
+                continue;
+            };
+
+            // The temporary files of the interrupted writes are removed.
+            if !metadata.is_file() || !Self::is_cache_key(&key) {
+                if metadata.is_file() && key.ends_with(".tmp") {
+                    std::fs::remove_file(entry.path())?;
+                }
+
+                continue;
+            }
+
+            index.size += metadata.len();
+            index.entries.put(key, metadata.len());
+        }
+
+        let cache = Arc::new(Cache {
+            dir,
+            max_size,
+            index: Mutex::new(index),
+        });
+
+        // Evict the content exceeding the max size, such as the max size is decreased.
+        for key in cache.evict() {
+            std::fs::remove_file(cache.dir.join(key))?;
+        }
+
+        Ok(Self { backend, cache })
+    }
+
+    /// Cache key returns the hex encoded sha256 of the canonical key of the request and the
+    /// ETag, the canonical key covers the URL, the version and the range of the request.
+    fn cache_key(request: &GetRequest, etag: &str) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(request.cache_key().as_bytes());
+        hasher.update(b"\0");
+        hasher.update(etag.as_bytes());
+        hex::encode(hasher.finalize())
+    }
+
+    /// Is cache key returns whether the file name is the cache key.
+    fn is_cache_key(name: &str) -> bool {
+        name.len() == 64 && name.bytes().all(|b| b.is_ascii_hexdigit())
+    }
+
+    /// Cached response returns the response reading the raw content, which is the cached file
+    /// or the content being cached. The content is decompressed, limited and digested only here,
+    /// as the cache holds the raw content of the wrapped backend.
+    fn cached_response(
+        reader: Body,
+        content_length: Option<u64>,
+        request: GetRequest,
+    ) -> GetResponse<Body> {
+        let (reader, piece_digests) = digest_body(
+            limit_body(
+                decompress_body(reader, request.auto_decompress),
+                request.max_body_size,
+            ),
+            request.piece_length,
+        );
+
+        GetResponse {
+            success: true,
+            http_header: None,
+            http_status_code: Some(reqwest::StatusCode::OK),
+            reader,
+            error_message: None,
+            piece_digests,
+            range_readers: Vec::new(),
+            suggested_filename: None,
+            content_length: content_length.filter(|_| !request.auto_decompress),
+            total_length: None,
+        }
+    }
+}
+
+/// Implement the Backend trait for CachingBackend.
+#[async_trait]
+impl Backend for CachingBackend {
+    /// Scheme returns the scheme of the wrapped backend.
+    fn scheme(&self) -> String {
+        self.backend.scheme()
+    }
+
+    /// Stat the metadata from the wrapped backend.
+    async fn stat(&self, request: StatRequest) -> ClientResult<StatResponse> {
+        self.backend.stat(request).await
+    }
+
+    /// Get the content from the cache directory if the cached content is fresh, otherwise get
+    /// the content from the wrapped backend, and populate the cache as the content is read.
+    #[instrument(skip_all)]
+    async fn get(&self, request: GetRequest) -> ClientResult<GetResponse<Body>> {
+        if !request.ranges.is_empty() || request.suffix_length.is_some() {
+            return self.backend.get(request).await;
+        }
+
+        // Validate the freshness of the cached content by the ETag of the object.
+        let stat_response = self
+            .backend
+            .stat(StatRequest {
+                task_id: request.task_id.clone(),
+                url: request.url.clone(),
+                http_header: request.http_header.clone(),
+                timeout: request.timeout,
+                client_cert: request.client_cert.clone(),
+                object_storage: request.object_storage.clone(),
+                hdfs: request.hdfs.clone(),
+                hugging_face: request.hugging_face.clone(),
+                model_scope: request.model_scope.clone(),
+                page_size: None,
+                continuation_token: None,
+                cancel_token: request.cancel_token.clone(),
+                sniff_content_type: false,
+                retry_budget: request.retry_budget.clone(),
+                version_id: request.version_id.clone(),
+                list_metakeys: Vec::new(),
+                filter: None,
+                max_depth: None,
+                endpoint_override: None,
+                expected_bucket_owner: None,
+                head_via_get_fallback: false,
+                start_after: None,
+                stat_timeout: None,
+                trace_context: None,
+            })
+            .await?;
+
+        let Some(etag) = stat_response.etag.filter(|_| stat_response.success) else {
+            debug!(
+                "get request is not cached without etag {} {}",
+                request.piece_id, request.url
+            );
+
+            return self.backend.get(request).await;
+        };
+
+        let key = Self::cache_key(&request, &etag);
+        let path = self.cache.dir.join(&key);
+        if self.cache.touch(&key) {
+            match fs::File::open(&path).await {
+                Ok(file) => {
+                    debug!("get request hit cache {} {}", request.piece_id, request.url);
+                    let content_length = file.metadata().await.ok().map(|metadata| metadata.len());
+                    let reader = cancel_body(Box::new(file), request.cancel_token.clone());
+                    return Ok(Self::cached_response(reader, content_length, request));
+                }
+                Err(err) => {
+                    warn!("open cached file failed {}: {}", path.display(), err);
+                    self.cache.remove(&key);
+                }
+            }
+        }
+
+        // The content larger than the max size is never cached.
+        let content_length = match &request.range {
+            Some(range) => Some(range.length),
+            None => stat_response.content_length,
+        };
+
+        if content_length.is_none_or(|content_length| content_length > self.cache.max_size) {
+            debug!(
+                "get request is not cached with content length {:?} {} {}",
+                content_length, request.piece_id, request.url
+            );
+
+            return self.backend.get(request).await;
+        }
+
+        // The raw content is got from the wrapped backend, as the cached content is decompressed,
+        // limited and digested by the request reading it.
+        let response = self
+            .backend
+            .get(GetRequest {
+                auto_decompress: false,
+                max_body_size: None,
+                piece_length: None,
+                ..request.clone()
+            })
+            .await?;
+        if !response.success {
+            return Ok(response);
+        }
+
+        debug!(
+            "get request populates cache {} {}",
+            request.piece_id, request.url
+        );
+
+        let reader = self.cache.tee(key, response.reader);
+        let mut cached_response = Self::cached_response(reader, response.content_length, request);
+        cached_response.http_header = response.http_header;
+        cached_response.http_status_code = response.http_status_code;
+        cached_response.suggested_filename = response.suggested_filename;
+        Ok(cached_response)
+    }
+
+    /// Put the content to the wrapped backend.
+    async fn put(&self, request: PutRequest) -> ClientResult<PutResponse> {
+        self.backend.put(request).await
+    }
+
+    /// Put reader puts the content of the reader to the wrapped backend.
+    async fn put_reader(
+        &self,
+        request: PutRequest,
+        reader: Body,
+        content_length: Option<u64>,
+    ) -> ClientResult<PutResponse> {
+        self.backend
+            .put_reader(request, reader, content_length)
+            .await
+    }
+
+    /// Exists checks whether the object exists in the wrapped backend.
+    async fn exists(&self, request: ExistsRequest) -> ClientResult<bool> {
+        self.backend.exists(request).await
+    }
+
+    /// Copy copies the object in the wrapped backend.
+    async fn copy(&self, request: CopyRequest) -> ClientResult<()> {
+        self.backend.copy(request).await
+    }
+
+    /// Shutdown shuts down the wrapped backend.
+    async fn shutdown(&self) -> ClientResult<()> {
+        self.backend.shutdown().await
+    }
+
+    /// Warm up warms up the connections of the wrapped backend.
+    async fn warm_up(&self, url: &str, object_storage: Option<ObjectStorage>) -> ClientResult<()> {
+        self.backend.warm_up(url, object_storage).await
+    }
+
+    /// Append appends the content to the object in the wrapped backend.
+    async fn append(&self, request: AppendRequest) -> ClientResult<()> {
+        self.backend.append(request).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tempfile::tempdir;
+    use tokio::io::AsyncReadExt;
+
+    /// MockBackend serves the content with the ETag, and counts the gets.
+    struct MockBackend {
+        content: Arc<Mutex<(String, &'static str)>>,
+        gets: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl Backend for MockBackend {
+        fn scheme(&self) -> String {
+            "mock".to_string()
+        }
+
+        async fn stat(&self, _request: StatRequest) -> ClientResult<StatResponse> {
+            let (etag, content) = self.content.lock().clone();
+            Ok(StatResponse {
+                success: true,
+                content_length: Some(content.len() as u64),
+                http_header: None,
+                http_status_code: None,
+                error_message: None,
+                entries: Vec::new(),
+                user_metadata: Default::default(),
+                next_continuation_token: None,
+                storage_class: None,
+                content_type: None,
+                accept_ranges: true,
+                suggested_filename: None,
+                truncated: false,
+                is_dir: false,
+                failure_class: None,
+                etag: Some(etag),
+            })
+        }
+
+        async fn get(&self, _request: GetRequest) -> ClientResult<GetResponse<Body>> {
+            self.gets.fetch_add(1, Ordering::SeqCst);
+            let (_, content) = self.content.lock().clone();
+            Ok(GetResponse {
+                success: true,
+                http_header: None,
+                http_status_code: Some(reqwest::StatusCode::OK),
+                reader: Box::new(content.as_bytes()),
+                error_message: None,
+                piece_digests: None,
+                range_readers: Vec::new(),
+                suggested_filename: None,
+                content_length: None,
+                total_length: None,
+            })
+        }
+
+        async fn put(&self, _request: PutRequest) -> ClientResult<PutResponse> {
+            unimplemented!()
+        }
+
+        async fn exists(&self, _request: ExistsRequest) -> ClientResult<bool> {
+            unimplemented!()
+        }
+    }
+
+    fn caching_backend(
+        dir: &Path,
+        max_size: u64,
+    ) -> (
+        CachingBackend,
+        Arc<Mutex<(String, &'static str)>>,
+        Arc<AtomicUsize>,
+    ) {
+        let content = Arc::new(Mutex::new(("v1".to_string(), "content")));
+        let gets = Arc::new(AtomicUsize::new(0));
+        let backend = CachingBackend::new(
+            Box::new(MockBackend {
+                content: content.clone(),
+                gets: gets.clone(),
+            }),
+            dir.to_path_buf(),
+            max_size,
+        )
+        .unwrap();
+
+        (backend, content, gets)
+    }
+
+    fn get_request(url: &str) -> GetRequest {
+        GetRequest::builder()
+            .task_id("test")
+            .piece_id("test")
+            .url(url)
+            .build()
+    }
+
+    async fn get_text(backend: &CachingBackend, url: &str) -> String {
+        let mut response = backend.get(get_request(url)).await.unwrap();
+        response.text().await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn should_serve_cache_hit_without_remote_get() {
+        let dir = tempdir().unwrap();
+        let (backend, _, gets) = caching_backend(dir.path(), 1024);
+
+        // The cache miss gets the content from the remote and populates the cache.
+        let mut response = backend
+            .get(get_request("mock://bucket/file"))
+            .await
+            .unwrap();
+        assert_eq!(response.text().await.unwrap(), "content");
+        assert_eq!(gets.load(Ordering::SeqCst), 1);
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+
+        // The cache hit serves the content from the disk.
+        let mut response = backend
+            .get(get_request("mock://bucket/file"))
+            .await
+            .unwrap();
+        assert_eq!(response.content_length, Some(7));
+        assert_eq!(response.text().await.unwrap(), "content");
+        assert_eq!(gets.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn should_get_again_when_etag_changed() {
+        let dir = tempdir().unwrap();
+        let (backend, content, gets) = caching_backend(dir.path(), 1024);
+
+        let mut response = backend
+            .get(get_request("mock://bucket/file"))
+            .await
+            .unwrap();
+        assert_eq!(response.text().await.unwrap(), "content");
+
+        *content.lock() = ("v2".to_string(), "changed");
+        let mut response = backend
+            .get(get_request("mock://bucket/file"))
+            .await
+            .unwrap();
+        assert_eq!(response.text().await.unwrap(), "changed");
+        assert_eq!(gets.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn should_evict_least_recently_used_content() {
+        let dir = tempdir().unwrap();
+
+        // The max size holds the content of two objects.
+        let (backend, _, gets) = caching_backend(dir.path(), 14);
+        for url in ["mock://bucket/a", "mock://bucket/b", "mock://bucket/a"] {
+            get_text(&backend, url).await;
+        }
+        assert_eq!(gets.load(Ordering::SeqCst), 2);
+
+        // The least recently used content of b is evicted.
+        get_text(&backend, "mock://bucket/c").await;
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 2);
+        get_text(&backend, "mock://bucket/a").await;
+        assert_eq!(gets.load(Ordering::SeqCst), 3);
+        get_text(&backend, "mock://bucket/b").await;
+        assert_eq!(gets.load(Ordering::SeqCst), 4);
+    }
+
+    #[tokio::test]
+    async fn should_index_cached_content_after_restart() {
+        let dir = tempdir().unwrap();
+        let (backend, _, _) = caching_backend(dir.path(), 1024);
+        get_text(&backend, "mock://bucket/file").await;
+
+        let (backend, _, gets) = caching_backend(dir.path(), 1024);
+        let mut response = backend
+            .get(get_request("mock://bucket/file"))
+            .await
+            .unwrap();
+        assert_eq!(response.text().await.unwrap(), "content");
+        assert_eq!(gets.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn should_not_cache_unfinished_content() {
+        let dir = tempdir().unwrap();
+        let (backend, _, gets) = caching_backend(dir.path(), 1024);
+
+        // The response dropped before the end abandons the cache.
+        let mut response = backend
+            .get(get_request("mock://bucket/file"))
+            .await
+            .unwrap();
+        let mut buf = [0; 3];
+        response.reader.read_exact(&mut buf).await.unwrap();
+        drop(response);
+
+        assert_eq!(get_text(&backend, "mock://bucket/file").await, "content");
+        assert_eq!(gets.load(Ordering::SeqCst), 2);
+        assert_eq!(get_text(&backend, "mock://bucket/file").await, "content");
+        assert_eq!(gets.load(Ordering::SeqCst), 2);
+    }
+}
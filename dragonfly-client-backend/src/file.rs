@@ -0,0 +1,637 @@
+/*
+ *     Copyright 2026 The Dragonfly Authors
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! File backend implementation for reading files from the local filesystem.
+//!
+//! This module provides support for the `file://` URL scheme to access files from
+//! the local filesystem or the shared mounts, e.g. NFS. It is useful for local testing
+//! and for seeding the files from the shared mounts.
+//!
+//! # URL Format
+//!
+//! The URL format is: `file://<path>`
+//!
+//! Examples:
+//! - `file:///data/` - List entire directory
+//! - `file:///data/model.bin` - Access specific file
+//!
+//! # Root Directory
+//!
+//! The file backend is only registered if the `backend.fileRootDir` is configured, and it only
+//! accesses the files under the root directory, the paths escaping the root directory are
+//! rejected. Otherwise the `file://` scheme is unsupported, which prevents the peers from
+//! reading any file readable by dfdaemon.
+//!
+//! # Memory Map
+//!
+//! With the `mmap` feature and the `backend.enableFileMmap`, the content not smaller than the
+//! `backend.fileMmapThreshold` is read by the memory map instead of the buffered reads, which
+//! benefits the large sequential reads. The memory map is unsafe with the concurrent writers:
+//! the file truncated by another process while it is mapped crashes dfdaemon with SIGBUS, so
+//! it is disabled by default and must be enabled only for the files never modified while they
+//! are read.
+
+use crate::{
+    cancel_body, decompress_body, digest_body, limit_body, Backend, Body, DirEntry, ExistsRequest,
+    GetRequest, GetResponse, PutRequest, PutResponse, StatRequest, StatResponse,
+};
+use async_trait::async_trait;
+use dragonfly_client_core::error::BackendError;
+use dragonfly_client_core::{Error as ClientError, Result as ClientResult};
+use std::collections::HashMap;
+use std::io::{ErrorKind, SeekFrom};
+use std::path::{Path, PathBuf};
+#[cfg(feature = "mmap")]
+use std::pin::Pin;
+#[cfg(feature = "mmap")]
+use std::task::{Context, Poll};
+use tokio::fs;
+#[cfg(feature = "mmap")]
+use tokio::io::{AsyncRead, ReadBuf};
+use tokio::io::{AsyncReadExt, AsyncSeekExt, BufReader};
+use tracing::{debug, error, instrument};
+use url::Url;
+
+/// SCHEME is the scheme of the file.
+pub const SCHEME: &str = "file";
+
+/// File is a struct that implements the Backend trait.
+pub struct File {
+    /// Scheme is the scheme of the file.
+    scheme: String,
+
+    /// Root dir is the directory that the file backend is restricted to.
+    root_dir: PathBuf,
+
+    /// Mmap threshold is the minimum size of the content read by the memory map, the memory
+    /// map is disabled if it is None.
+    #[cfg_attr(not(feature = "mmap"), allow(dead_code))]
+    mmap_threshold: Option<u64>,
+}
+
+/// MMAP_READ_CHUNK_SIZE is the maximum size copied from the memory map by each read, which
+/// bounds the pages accessed after the length of the file is checked.
+#[cfg(feature = "mmap")]
+const MMAP_READ_CHUNK_SIZE: usize = 4 * 1024 * 1024;
+
+/// MmapReader reads the content of the file by the memory map. Accessing the pages beyond the
+/// end of the truncated file raises SIGBUS, which can not be prevented: the length of the file
+/// is checked before each copy to return the UnexpectedEof error for the file truncated before
+/// the copy, but the file truncated during the copy still crashes the process. The copy faults
+/// in the pages from the disk, which blocks the runtime thread like the buffered reads of the
+/// standard library, so the copy is bounded by the chunk size.
+#[cfg(feature = "mmap")]
+struct MmapReader {
+    /// File is the mapped file, which is used to check the length of the file.
+    file: std::fs::File,
+
+    /// Mmap is the memory map of the content.
+    mmap: memmap2::Mmap,
+
+    /// Offset is the offset of the content in the file.
+    offset: u64,
+
+    /// Position is the position of the content has been read.
+    position: usize,
+}
+
+/// MmapReader implements the AsyncRead trait.
+#[cfg(feature = "mmap")]
+impl AsyncRead for MmapReader {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        let this = self.get_mut();
+        let remaining = &this.mmap[this.position..];
+        let n = remaining
+            .len()
+            .min(buf.remaining())
+            .min(MMAP_READ_CHUNK_SIZE);
+        if n == 0 {
+            return Poll::Ready(Ok(()));
+        }
+
+        let end = this.offset + (this.position + n) as u64;
+        if this.file.metadata()?.len() < end {
+            return Poll::Ready(Err(std::io::Error::new(
+                ErrorKind::UnexpectedEof,
+                "file is truncated during read",
+            )));
+        }
+
+        buf.put_slice(&remaining[..n]);
+        this.position += n;
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// File implements the Backend trait.
+impl File {
+    /// Create a new File instance.
+    pub fn new(root_dir: PathBuf, mmap_threshold: Option<u64>) -> Self {
+        Self {
+            scheme: SCHEME.to_string(),
+            root_dir,
+            mmap_threshold,
+        }
+    }
+
+    /// Mmap reader returns the reader of the content of the file by the memory map if the
+    /// memory map is enabled and the content is not smaller than the mmap threshold, and
+    /// returns None otherwise or if the memory map fails, which falls back to the buffered
+    /// reads.
+    #[cfg(feature = "mmap")]
+    async fn mmap_reader(&self, file: &fs::File, offset: u64, length: u64) -> Option<Body> {
+        let mmap_threshold = self.mmap_threshold?;
+        if length == 0 || length < mmap_threshold {
+            return None;
+        }
+
+        let file = file.try_clone().await.ok()?.into_std().await;
+        let length = usize::try_from(length).ok()?;
+
+        // SAFETY: The memory map is only sound if the file is not modified or truncated while
+        // it is mapped, which is the requirement of enabling the file mmap. The length check of
+        // the MmapReader only detects the truncation before each copy, the truncation during
+        // the copy raises SIGBUS.
+        let mmap = unsafe {
+            memmap2::MmapOptions::new()
+                .offset(offset)
+                .len(length)
+                .map(&file)
+        }
+        .inspect_err(|err| debug!("mmap failed, fall back to buffered reads: {}", err))
+        .ok()?;
+
+        #[cfg(unix)]
+        let _ = mmap.advise(memmap2::Advice::Sequential);
+
+        Some(Box::new(MmapReader {
+            file,
+            mmap,
+            offset,
+            position: 0,
+        }))
+    }
+
+    /// Mmap reader returns None without the mmap feature, which always uses the buffered reads.
+    #[cfg(not(feature = "mmap"))]
+    async fn mmap_reader(&self, _file: &fs::File, _offset: u64, _length: u64) -> Option<Body> {
+        None
+    }
+
+    /// Path returns the canonical path of the URL, and checks whether the path escapes the
+    /// root directory.
+    async fn path(&self, url: &str) -> ClientResult<PathBuf> {
+        let path = Url::parse(url)
+            .ok()
+            .and_then(|url| url.to_file_path().ok())
+            .ok_or_else(|| ClientError::InvalidURI(url.to_string()))?;
+
+        // Canonicalize the path to resolve the `..` components and the symbolic links.
+        let path = fs::canonicalize(&path).await?;
+        let root_dir = fs::canonicalize(&self.root_dir).await?;
+        if !path.starts_with(&root_dir) {
+            error!(
+                "path {} escapes the root directory {}",
+                path.display(),
+                root_dir.display()
+            );
+
+            return Err(ClientError::BackendError(Box::new(BackendError {
+                message: format!(
+                    "{} escapes the root directory {}",
+                    path.display(),
+                    root_dir.display()
+                ),
+                status_code: None,
+                header: None,
+            })));
+        }
+
+        Ok(path)
+    }
+
+    /// List entries lists the entries of the directory recursively.
+    async fn list_entries(dir: &Path) -> ClientResult<Vec<DirEntry>> {
+        let mut entries = Vec::new();
+        let mut dirs = vec![dir.to_path_buf()];
+        while let Some(dir) = dirs.pop() {
+            let mut read_dir = fs::read_dir(&dir).await?;
+            while let Some(entry) = read_dir.next_entry().await? {
+                let path = entry.path();
+                let metadata = entry.metadata().await?;
+                let url = if metadata.is_dir() {
+                    dirs.push(path.clone());
+                    Url::from_directory_path(&path)
+                } else {
+                    Url::from_file_path(&path)
+                }
+                .map_err(|_| ClientError::InvalidURI(path.display().to_string()))?;
+
+                entries.push(DirEntry {
+                    url: url.to_string(),
+                    content_length: if metadata.is_dir() {
+                        0
+                    } else {
+                        metadata.len() as usize
+                    },
+                    is_dir: metadata.is_dir(),
+                    etag: None,
+                    last_modified: None,
+                    content_type: None,
+                });
+            }
+        }
+
+        Ok(entries)
+    }
+}
+
+/// Implement the Backend trait for File.
+#[async_trait]
+impl Backend for File {
+    /// Scheme returns the scheme of the file backend.
+    fn scheme(&self) -> String {
+        self.scheme.clone()
+    }
+
+    /// Stat the metadata from the backend.
+    #[instrument(skip_all)]
+    async fn stat(&self, request: StatRequest) -> ClientResult<StatResponse> {
+        debug!("stat request {} {}", request.task_id, request.url);
+
+        let path = self.path(&request.url).await.inspect_err(|err| {
+            error!(
+                "stat request failed {} {}: {}",
+                request.task_id, request.url, err
+            );
+        })?;
+        let metadata = fs::metadata(&path).await?;
+
+        // Get the entries if url point to a directory.
+        let entries = if metadata.is_dir() && request.url.ends_with('/') {
+            Self::list_entries(&path).await.inspect_err(|err| {
+                error!(
+                    "list request failed {} {}: {}",
+                    request.task_id, request.url, err
+                );
+            })?
+        } else {
+            Vec::new()
+        };
+
+        let content_length = if metadata.is_dir() { 0 } else { metadata.len() };
+        debug!(
+            "stat response {} {}: {}",
+            request.task_id, request.url, content_length
+        );
+
+        Ok(StatResponse {
+            success: true,
+            content_length: Some(content_length),
+            http_header: None,
+            http_status_code: None,
+            error_message: None,
+            entries,
+            user_metadata: HashMap::new(),
+            next_continuation_token: None,
+            storage_class: None,
+            content_type: None,
+            accept_ranges: true,
+            suggested_filename: None,
+            truncated: false,
+            is_dir: metadata.is_dir(),
+            failure_class: None,
+            etag: None,
+        })
+    }
+
+    /// Get the content from the backend.
+    #[instrument(skip_all)]
+    async fn get(&self, request: GetRequest) -> ClientResult<GetResponse<Body>> {
+        debug!(
+            "get request {} {}: {:?}",
+            request.piece_id, request.url, request.range
+        );
+
+        // The multiple ranges and the suffix range are only supported by the HTTP and object
+        // storage backends.
+        if !request.ranges.is_empty() || request.suffix_length.is_some() {
+            return Err(ClientError::Unsupported(format!(
+                "{} multiple ranges and suffix range",
+                self.scheme
+            )));
+        }
+
+        let path = self.path(&request.url).await.inspect_err(|err| {
+            error!(
+                "get request failed {} {}: {}",
+                request.piece_id, request.url, err
+            );
+        })?;
+
+        let mut file = fs::File::open(&path).await?;
+        let file_length = file.metadata().await?.len();
+        let content_length = match request.range {
+            Some(range) => range.length.min(file_length.saturating_sub(range.start)),
+            None => file_length,
+        };
+
+        let offset = request.range.map_or(0, |range| range.start);
+        let reader: Body = match self.mmap_reader(&file, offset, content_length).await {
+            Some(reader) => reader,
+            None => match request.range {
+                Some(range) => {
+                    file.seek(SeekFrom::Start(range.start)).await?;
+                    match request.read_buffer_size {
+                        Some(read_buffer_size) => Box::new(
+                            BufReader::with_capacity(read_buffer_size, file).take(range.length),
+                        ),
+                        None => Box::new(file.take(range.length)),
+                    }
+                }
+                None => match request.read_buffer_size {
+                    Some(read_buffer_size) => {
+                        Box::new(BufReader::with_capacity(read_buffer_size, file))
+                    }
+                    None => Box::new(file),
+                },
+            },
+        };
+
+        let (reader, piece_digests) = digest_body(
+            limit_body(
+                decompress_body(
+                    cancel_body(reader, request.cancel_token),
+                    request.auto_decompress,
+                ),
+                request.max_body_size,
+            ),
+            request.piece_length,
+        );
+
+        Ok(GetResponse {
+            success: true,
+            http_header: None,
+            http_status_code: Some(reqwest::StatusCode::OK),
+            reader,
+            error_message: None,
+            piece_digests,
+            range_readers: Vec::new(),
+            suggested_filename: None,
+            content_length: Some(content_length).filter(|_| !request.auto_decompress),
+            total_length: None,
+        })
+    }
+
+    /// Put the content to the backend.
+    #[instrument(skip_all)]
+    async fn put(&self, _request: PutRequest) -> ClientResult<PutResponse> {
+        Err(ClientError::Unsupported(format!("{} put", self.scheme)))
+    }
+
+    /// Exists checks whether the file exists in the backend.
+    #[instrument(skip_all)]
+    async fn exists(&self, request: ExistsRequest) -> ClientResult<bool> {
+        debug!("exist request {} {}", request.task_id, request.url);
+
+        match self.path(&request.url).await {
+            Ok(_) => Ok(true),
+            Err(ClientError::IO(err)) if err.kind() == ErrorKind::NotFound => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dragonfly_api::common::v2::Range;
+    use std::time::Duration;
+    use tempfile::tempdir;
+
+    fn get_request(url: String, range: Option<Range>) -> GetRequest {
+        GetRequest {
+            task_id: "test".to_string(),
+            piece_id: "test".to_string(),
+            url,
+            range,
+            http_header: None,
+            timeout: Duration::from_secs(5),
+            client_cert: None,
+            object_storage: None,
+            hdfs: None,
+            hugging_face: None,
+            model_scope: None,
+            read_buffer_size: None,
+            max_body_size: None,
+            piece_length: None,
+            ranges: Vec::new(),
+            cancel_token: None,
+            retry_budget: None,
+            suffix_length: None,
+            version_id: None,
+            adaptive_chunking: false,
+            endpoint_override: None,
+            expected_bucket_owner: None,
+            auto_decompress: false,
+            trace_context: None,
+            prefetch_chunks: 0,
+            min_throughput: None,
+            min_throughput_window: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn should_get_file_with_range() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("file");
+        fs::write(&path, b"0123456789").await.unwrap();
+        let url = Url::from_file_path(&path).unwrap().to_string();
+
+        let file = File::new(dir.path().to_path_buf(), None);
+        let mut response = file.get(get_request(url.clone(), None)).await.unwrap();
+        assert!(response.success);
+        assert_eq!(response.text().await.unwrap(), "0123456789");
+
+        let mut response = file
+            .get(get_request(
+                url.clone(),
+                Some(Range {
+                    start: 2,
+                    length: 5,
+                }),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.text().await.unwrap(), "23456");
+    }
+
+    #[tokio::test]
+    async fn should_stat_directory_with_entries() {
+        let dir = tempdir().unwrap();
+        fs::create_dir(dir.path().join("sub")).await.unwrap();
+        fs::write(dir.path().join("a"), b"a").await.unwrap();
+        fs::write(dir.path().join("sub").join("b"), b"bb")
+            .await
+            .unwrap();
+
+        let response = File::new(dir.path().to_path_buf(), None)
+            .stat(StatRequest {
+                task_id: "test".to_string(),
+                url: Url::from_directory_path(dir.path()).unwrap().to_string(),
+                http_header: None,
+                timeout: Duration::from_secs(5),
+                client_cert: None,
+                object_storage: None,
+                hdfs: None,
+                hugging_face: None,
+                model_scope: None,
+                page_size: None,
+                continuation_token: None,
+                cancel_token: None,
+                sniff_content_type: false,
+                retry_budget: None,
+                version_id: None,
+                list_metakeys: Vec::new(),
+                filter: None,
+                max_depth: None,
+                endpoint_override: None,
+                expected_bucket_owner: None,
+                head_via_get_fallback: false,
+                start_after: None,
+                stat_timeout: None,
+                trace_context: None,
+            })
+            .await
+            .unwrap();
+
+        let mut entries: Vec<(bool, usize)> = response
+            .entries
+            .iter()
+            .map(|entry| (entry.is_dir, entry.content_length))
+            .collect();
+        entries.sort();
+        assert_eq!(entries, vec![(false, 1), (false, 2), (true, 0)]);
+    }
+
+    #[tokio::test]
+    async fn should_reject_path_escaping_root_dir() {
+        let root_dir = tempdir().unwrap();
+        let outside_dir = tempdir().unwrap();
+        let path = outside_dir.path().join("file");
+        fs::write(&path, b"secret").await.unwrap();
+
+        let file = File::new(root_dir.path().to_path_buf(), None);
+        let url = format!(
+            "{}../{}/file",
+            Url::from_directory_path(root_dir.path()).unwrap(),
+            outside_dir.path().file_name().unwrap().to_string_lossy()
+        );
+        assert!(file.get(get_request(url, None)).await.is_err());
+
+        let url = Url::from_file_path(&path).unwrap().to_string();
+        assert!(file.get(get_request(url, None)).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn should_return_false_when_file_not_exists() {
+        let dir = tempdir().unwrap();
+        let exists = File::new(dir.path().to_path_buf(), None)
+            .exists(ExistsRequest {
+                task_id: "test".to_string(),
+                url: Url::from_file_path(dir.path().join("not-exists"))
+                    .unwrap()
+                    .to_string(),
+                http_header: None,
+                timeout: Duration::from_secs(5),
+                client_cert: None,
+                object_storage: None,
+                hdfs: None,
+                hugging_face: None,
+                model_scope: None,
+            })
+            .await
+            .unwrap();
+        assert!(!exists);
+    }
+
+    #[cfg(feature = "mmap")]
+    #[tokio::test]
+    async fn should_read_large_file_by_mmap() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("large");
+        let content: Vec<u8> = (0..10 * 1024 * 1024 + 7).map(|i| (i % 251) as u8).collect();
+        fs::write(&path, &content).await.unwrap();
+        let url = Url::from_file_path(&path).unwrap().to_string();
+
+        // The content read by the memory map is the same as the content of the buffered reads.
+        for range in [
+            None,
+            Some(Range {
+                start: 4097,
+                length: 5 * 1024 * 1024,
+            }),
+        ] {
+            let mut mmap_content = Vec::new();
+            File::new(dir.path().to_path_buf(), Some(1024))
+                .get(get_request(url.clone(), range))
+                .await
+                .unwrap()
+                .reader
+                .read_to_end(&mut mmap_content)
+                .await
+                .unwrap();
+
+            let mut buffered_content = Vec::new();
+            File::new(dir.path().to_path_buf(), None)
+                .get(get_request(url.clone(), range))
+                .await
+                .unwrap()
+                .reader
+                .read_to_end(&mut buffered_content)
+                .await
+                .unwrap();
+
+            assert_eq!(mmap_content, buffered_content);
+        }
+
+        // The truncation of the file during the read fails the read instead of the SIGBUS.
+        let mut response = File::new(dir.path().to_path_buf(), Some(1024))
+            .get(get_request(url, None))
+            .await
+            .unwrap();
+        let mut buffer = vec![0; 1024];
+        response.reader.read_exact(&mut buffer).await.unwrap();
+        assert_eq!(buffer, content[..1024]);
+
+        std::fs::OpenOptions::new()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_len(0)
+            .unwrap();
+        let err = response
+            .reader
+            .read_to_end(&mut Vec::new())
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    }
+}
@@ -35,8 +35,8 @@
 //! a delegation token for authentication.
 
 use crate::{
-    Backend, Body, DirEntry, ExistsRequest, GetRequest, GetResponse, PutRequest, PutResponse,
-    StatRequest, StatResponse,
+    cancel_body, decompress_body, digest_body, limit_body, Backend, Body, DirEntry,
+    ExistsRequest, GetRequest, GetResponse, PutRequest, PutResponse, StatRequest, StatResponse,
 };
 use async_trait::async_trait;
 use dragonfly_api::common;
@@ -44,6 +44,7 @@ use dragonfly_client_core::error::BackendError;
 use dragonfly_client_core::{Error as ClientError, Result as ClientResult};
 use opendal::{layers::TimeoutLayer, Operator};
 use percent_encoding::percent_decode_str;
+use std::collections::HashMap;
 use std::time::Duration;
 use tokio_util::io::StreamReader;
 use tracing::{debug, error, instrument};
@@ -143,11 +144,7 @@ impl Backend for Hdfs {
                         request.task_id, request.url, err
                     );
 
-                    ClientError::BackendError(Box::new(BackendError {
-                        message: err.to_string(),
-                        status_code: None,
-                        header: None,
-                    }))
+                    ClientError::BackendError(Box::new(BackendError::from_opendal(&err)))
                 })?
                 .into_iter()
                 .map(|entry| {
@@ -158,6 +155,9 @@ impl Backend for Hdfs {
                         url: url.to_string(),
                         content_length: metadata.content_length() as usize,
                         is_dir: metadata.is_dir(),
+                        etag: None,
+                        last_modified: None,
+                        content_type: None,
                     }
                 })
                 .collect()
@@ -172,11 +172,7 @@ impl Backend for Hdfs {
                 request.task_id, request.url, err
             );
 
-            ClientError::BackendError(Box::new(BackendError {
-                message: err.to_string(),
-                status_code: None,
-                header: None,
-            }))
+            ClientError::BackendError(Box::new(BackendError::from_opendal(&err)))
         })?;
 
         debug!(
@@ -193,6 +189,16 @@ impl Backend for Hdfs {
             http_status_code: None,
             error_message: None,
             entries,
+            user_metadata: HashMap::new(),
+            next_continuation_token: None,
+            storage_class: None,
+            content_type: None,
+            accept_ranges: true,
+            suggested_filename: None,
+            truncated: false,
+            is_dir: response.mode().is_dir(),
+            failure_class: None,
+            etag: None,
         })
     }
 
@@ -204,6 +210,15 @@ impl Backend for Hdfs {
             request.piece_id, request.url, request.http_header
         );
 
+        // The multiple ranges and the suffix range are only supported by the HTTP and object
+        // storage backends.
+        if !request.ranges.is_empty() || request.suffix_length.is_some() {
+            return Err(ClientError::Unsupported(format!(
+                "{} multiple ranges and suffix range",
+                self.scheme
+            )));
+        }
+
         // Parse the URL.
         let url = Url::parse(request.url.as_ref())
             .map_err(|_| ClientError::InvalidURI(request.url.clone()))?;
@@ -222,11 +237,7 @@ impl Backend for Hdfs {
                     request.piece_id, request.url, err
                 );
 
-                ClientError::BackendError(Box::new(BackendError {
-                    message: err.to_string(),
-                    status_code: None,
-                    header: None,
-                }))
+                ClientError::BackendError(Box::new(BackendError::from_opendal(&err)))
             })?;
 
         let stream = match request.range {
@@ -239,11 +250,7 @@ impl Backend for Hdfs {
                         request.piece_id, request.url, err
                     );
 
-                    ClientError::BackendError(Box::new(BackendError {
-                        message: err.to_string(),
-                        status_code: None,
-                        header: None,
-                    }))
+                    ClientError::BackendError(Box::new(BackendError::from_opendal(&err)))
                 })?,
             None => operator_reader.into_bytes_stream(..).await.map_err(|err| {
                 error!(
@@ -251,20 +258,35 @@ impl Backend for Hdfs {
                     request.piece_id, request.url, err
                 );
 
-                ClientError::BackendError(Box::new(BackendError {
-                    message: err.to_string(),
-                    status_code: None,
-                    header: None,
-                }))
+                ClientError::BackendError(Box::new(BackendError::from_opendal(&err)))
             })?,
         };
 
+        let (reader, piece_digests) = digest_body(
+            limit_body(
+                decompress_body(
+                    cancel_body(Box::new(StreamReader::new(stream)), request.cancel_token),
+                    request.auto_decompress,
+                ),
+                request.max_body_size,
+            ),
+            request.piece_length,
+        );
+
         Ok(crate::GetResponse {
             success: true,
             http_header: None,
             http_status_code: Some(reqwest::StatusCode::OK),
-            reader: Box::new(StreamReader::new(stream)),
+            reader,
             error_message: None,
+            piece_digests,
+            range_readers: Vec::new(),
+            suggested_filename: None,
+            content_length: request
+                .range
+                .map(|range| range.length)
+                .filter(|_| !request.auto_decompress),
+            total_length: None,
         })
     }
 
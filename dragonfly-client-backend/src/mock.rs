@@ -0,0 +1,600 @@
+/*
+ *     Copyright 2026 The Dragonfly Authors
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Mock backend implementation for testing the code paths depending on the backends.
+//!
+//! The `MockBackend` serves the programmed responses without the network, so the downstream
+//! crates can test their retry and fallback logic. It is exported with the `test-util`
+//! feature.
+//!
+//! # Programming Responses
+//!
+//! The responses are programmed per URL by the `MockBackendBuilder`. The scripted results of
+//! a URL are returned in order, one per call, and the canned response of the URL is returned
+//! after the script is exhausted. The call of the URL without any programmed response fails
+//! with the unknown error, except the put, copy and append which succeed by default.
+//!
+//! # Call Counts
+//!
+//! Every call is counted by the method and the URL, the copy is counted by the source URL.
+
+use crate::{
+    AppendRequest, Backend, Body, CopyRequest, ExistsRequest, GetRequest, GetResponse, PutRequest,
+    PutResponse, StatRequest, StatResponse,
+};
+use async_trait::async_trait;
+use bytes::Bytes;
+use dragonfly_client_core::{Error as ClientError, Result as ClientResult};
+use reqwest::header::HeaderMap;
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::io::Cursor;
+use std::sync::Mutex;
+
+/// MockMethod is the method of the Backend called on the mock backend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MockMethod {
+    /// Stat is the stat method.
+    Stat,
+
+    /// Get is the get method.
+    Get,
+
+    /// Put is the put method.
+    Put,
+
+    /// Exists is the exists method.
+    Exists,
+
+    /// Copy is the copy method.
+    Copy,
+
+    /// Append is the append method.
+    Append,
+}
+
+/// MockMethod implements the Display.
+impl fmt::Display for MockMethod {
+    /// Fmt formats the value using the given formatter.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MockMethod::Stat => write!(f, "stat"),
+            MockMethod::Get => write!(f, "get"),
+            MockMethod::Put => write!(f, "put"),
+            MockMethod::Exists => write!(f, "exists"),
+            MockMethod::Copy => write!(f, "copy"),
+            MockMethod::Append => write!(f, "append"),
+        }
+    }
+}
+
+/// MockGetResponse is the canned get response, the reader of the GetResponse is made from the
+/// content for every call.
+#[derive(Debug, Clone)]
+pub struct MockGetResponse {
+    /// HTTP header is the headers of the response.
+    pub http_header: Option<HeaderMap>,
+
+    /// HTTP status code is the status code of the response.
+    pub http_status_code: reqwest::StatusCode,
+
+    /// Content is the body of the response.
+    pub content: Bytes,
+}
+
+/// MockGetResponse implements the MockGetResponse.
+impl MockGetResponse {
+    /// New returns the successful get response with the content.
+    pub fn new(content: impl Into<Bytes>) -> Self {
+        Self {
+            http_header: None,
+            http_status_code: reqwest::StatusCode::OK,
+            content: content.into(),
+        }
+    }
+
+    /// Into get response makes the GetResponse reading the content.
+    fn into_get_response(self) -> GetResponse<Body> {
+        let content_length = self.content.len() as u64;
+        GetResponse {
+            success: self.http_status_code.is_success(),
+            http_header: self.http_header,
+            http_status_code: Some(self.http_status_code),
+            reader: Box::new(Cursor::new(self.content)),
+            error_message: None,
+            piece_digests: None,
+            range_readers: Vec::new(),
+            suggested_filename: None,
+            content_length: Some(content_length),
+            total_length: None,
+        }
+    }
+}
+
+/// Script is the programmed results of a method of a URL.
+struct Script<T> {
+    /// Sequence is the scripted results returned in order, one per call.
+    sequence: VecDeque<ClientResult<T>>,
+
+    /// Canned is the result returned after the sequence is exhausted.
+    canned: Option<T>,
+}
+
+/// Script implements the Default.
+impl<T> Default for Script<T> {
+    /// Default returns the script without any programmed result.
+    fn default() -> Self {
+        Self {
+            sequence: VecDeque::new(),
+            canned: None,
+        }
+    }
+}
+
+/// Script implements the result of the next call.
+impl<T: Clone> Script<T> {
+    /// Next returns the next scripted result, or the canned result if the sequence is
+    /// exhausted. It returns None if there is no programmed result.
+    fn next(&mut self) -> Option<ClientResult<T>> {
+        self.sequence
+            .pop_front()
+            .or_else(|| self.canned.clone().map(Ok))
+    }
+}
+
+/// Scripts is the programmed results of all the methods keyed by the URL.
+#[derive(Default)]
+struct Scripts {
+    /// Stat is the programmed stat responses.
+    stat: HashMap<String, Script<StatResponse>>,
+
+    /// Get is the programmed get responses.
+    get: HashMap<String, Script<MockGetResponse>>,
+
+    /// Exists is the programmed exists results.
+    exists: HashMap<String, Script<bool>>,
+
+    /// Put is the programmed put errors, the put succeeds if the errors are exhausted.
+    put: HashMap<String, Script<()>>,
+
+    /// Copy is the programmed copy errors keyed by the source URL, the copy succeeds if the
+    /// errors are exhausted.
+    copy: HashMap<String, Script<()>>,
+
+    /// Append is the programmed append errors, the append succeeds if the errors are
+    /// exhausted.
+    append: HashMap<String, Script<()>>,
+}
+
+/// MockBackendBuilder is the builder of the MockBackend programming the responses per URL.
+pub struct MockBackendBuilder {
+    /// Scheme is the scheme of the mock backend.
+    scheme: String,
+
+    /// Scripts is the programmed results.
+    scripts: Scripts,
+}
+
+/// MockBackendBuilder implements the programming of the responses.
+impl MockBackendBuilder {
+    /// Scheme sets the scheme of the mock backend, the default is `mock`.
+    pub fn scheme(mut self, scheme: impl Into<String>) -> Self {
+        self.scheme = scheme.into();
+        self
+    }
+
+    /// Stat sets the canned stat response of the URL.
+    pub fn stat(mut self, url: impl Into<String>, response: StatResponse) -> Self {
+        self.scripts.stat.entry(url.into()).or_default().canned = Some(response);
+        self
+    }
+
+    /// Stat once appends the scripted stat result of the URL.
+    pub fn stat_once(mut self, url: impl Into<String>, result: ClientResult<StatResponse>) -> Self {
+        self.scripts
+            .stat
+            .entry(url.into())
+            .or_default()
+            .sequence
+            .push_back(result);
+        self
+    }
+
+    /// Get sets the canned get response of the URL.
+    pub fn get(mut self, url: impl Into<String>, response: MockGetResponse) -> Self {
+        self.scripts.get.entry(url.into()).or_default().canned = Some(response);
+        self
+    }
+
+    /// Get once appends the scripted get result of the URL.
+    pub fn get_once(
+        mut self,
+        url: impl Into<String>,
+        result: ClientResult<MockGetResponse>,
+    ) -> Self {
+        self.scripts
+            .get
+            .entry(url.into())
+            .or_default()
+            .sequence
+            .push_back(result);
+        self
+    }
+
+    /// Exists sets the canned exists result of the URL.
+    pub fn exists(mut self, url: impl Into<String>, exists: bool) -> Self {
+        self.scripts.exists.entry(url.into()).or_default().canned = Some(exists);
+        self
+    }
+
+    /// Error appends the scripted error of the method of the URL, the copy is keyed by the
+    /// source URL.
+    pub fn error(mut self, method: MockMethod, url: impl Into<String>, err: ClientError) -> Self {
+        let url = url.into();
+        match method {
+            MockMethod::Stat => self
+                .scripts
+                .stat
+                .entry(url)
+                .or_default()
+                .sequence
+                .push_back(Err(err)),
+            MockMethod::Get => self
+                .scripts
+                .get
+                .entry(url)
+                .or_default()
+                .sequence
+                .push_back(Err(err)),
+            MockMethod::Exists => self
+                .scripts
+                .exists
+                .entry(url)
+                .or_default()
+                .sequence
+                .push_back(Err(err)),
+            MockMethod::Put => self
+                .scripts
+                .put
+                .entry(url)
+                .or_default()
+                .sequence
+                .push_back(Err(err)),
+            MockMethod::Copy => self
+                .scripts
+                .copy
+                .entry(url)
+                .or_default()
+                .sequence
+                .push_back(Err(err)),
+            MockMethod::Append => self
+                .scripts
+                .append
+                .entry(url)
+                .or_default()
+                .sequence
+                .push_back(Err(err)),
+        }
+
+        self
+    }
+
+    /// Build returns the MockBackend serving the programmed responses.
+    pub fn build(self) -> MockBackend {
+        MockBackend {
+            scheme: self.scheme,
+            scripts: Mutex::new(self.scripts),
+            calls: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+/// MockBackend is the backend serving the programmed responses without the network.
+pub struct MockBackend {
+    /// Scheme is the scheme of the mock backend.
+    scheme: String,
+
+    /// Scripts is the programmed results.
+    scripts: Mutex<Scripts>,
+
+    /// Calls is the count of the calls keyed by the method and the URL.
+    calls: Mutex<HashMap<(MockMethod, String), usize>>,
+}
+
+/// MockBackend implements the programming and the introspection of the calls.
+impl MockBackend {
+    /// Builder returns the builder of the MockBackend.
+    pub fn builder() -> MockBackendBuilder {
+        MockBackendBuilder {
+            scheme: "mock".to_string(),
+            scripts: Scripts::default(),
+        }
+    }
+
+    /// Calls returns the count of the calls of the method of the URL.
+    pub fn calls(&self, method: MockMethod, url: &str) -> usize {
+        self.calls
+            .lock()
+            .unwrap()
+            .get(&(method, url.to_string()))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Total calls returns the count of the calls of the method of all the URLs.
+    pub fn total_calls(&self, method: MockMethod) -> usize {
+        self.calls
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|((called_method, _), _)| *called_method == method)
+            .map(|(_, count)| count)
+            .sum()
+    }
+
+    /// Record counts the call of the method of the URL.
+    fn record(&self, method: MockMethod, url: &str) {
+        *self
+            .calls
+            .lock()
+            .unwrap()
+            .entry((method, url.to_string()))
+            .or_default() += 1;
+    }
+
+    /// Unprogrammed returns the error of the call without any programmed response.
+    fn unprogrammed(method: MockMethod, url: &str) -> ClientError {
+        ClientError::Unknown(format!(
+            "mock backend has no {} response for {}",
+            method, url
+        ))
+    }
+}
+
+/// MockBackend implements the Backend trait.
+#[async_trait]
+impl Backend for MockBackend {
+    /// Scheme returns the scheme of the mock backend.
+    fn scheme(&self) -> String {
+        self.scheme.clone()
+    }
+
+    /// Stat returns the next programmed stat response of the URL.
+    async fn stat(&self, request: StatRequest) -> ClientResult<StatResponse> {
+        self.record(MockMethod::Stat, &request.url);
+        let result = self
+            .scripts
+            .lock()
+            .unwrap()
+            .stat
+            .get_mut(&request.url)
+            .and_then(Script::next);
+
+        result.unwrap_or_else(|| Err(Self::unprogrammed(MockMethod::Stat, &request.url)))
+    }
+
+    /// Get returns the next programmed get response of the URL.
+    async fn get(&self, request: GetRequest) -> ClientResult<GetResponse<Body>> {
+        self.record(MockMethod::Get, &request.url);
+        let result = self
+            .scripts
+            .lock()
+            .unwrap()
+            .get
+            .get_mut(&request.url)
+            .and_then(Script::next);
+
+        result
+            .unwrap_or_else(|| Err(Self::unprogrammed(MockMethod::Get, &request.url)))
+            .map(MockGetResponse::into_get_response)
+    }
+
+    /// Put succeeds unless the error of the URL is programmed.
+    async fn put(&self, request: PutRequest) -> ClientResult<PutResponse> {
+        self.record(MockMethod::Put, &request.url);
+        let result = self
+            .scripts
+            .lock()
+            .unwrap()
+            .put
+            .get_mut(&request.url)
+            .and_then(Script::next);
+
+        result.unwrap_or(Ok(()))?;
+        Ok(PutResponse {
+            success: true,
+            content_length: None,
+            http_header: None,
+            http_status_code: None,
+            error_message: None,
+        })
+    }
+
+    /// Exists returns the next programmed exists result of the URL.
+    async fn exists(&self, request: ExistsRequest) -> ClientResult<bool> {
+        self.record(MockMethod::Exists, &request.url);
+        let result = self
+            .scripts
+            .lock()
+            .unwrap()
+            .exists
+            .get_mut(&request.url)
+            .and_then(Script::next);
+
+        result.unwrap_or_else(|| Err(Self::unprogrammed(MockMethod::Exists, &request.url)))
+    }
+
+    /// Copy succeeds unless the error of the source URL is programmed.
+    async fn copy(&self, request: CopyRequest) -> ClientResult<()> {
+        self.record(MockMethod::Copy, &request.source_url);
+        let result = self
+            .scripts
+            .lock()
+            .unwrap()
+            .copy
+            .get_mut(&request.source_url)
+            .and_then(Script::next);
+
+        result.unwrap_or(Ok(()))
+    }
+
+    /// Append succeeds unless the error of the URL is programmed.
+    async fn append(&self, request: AppendRequest) -> ClientResult<()> {
+        self.record(MockMethod::Append, &request.url);
+        let result = self
+            .scripts
+            .lock()
+            .unwrap()
+            .append
+            .get_mut(&request.url)
+            .and_then(Script::next);
+
+        result.unwrap_or(Ok(()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn get_request(url: &str) -> GetRequest {
+        GetRequest::builder()
+            .task_id("test")
+            .piece_id("test")
+            .url(url)
+            .build()
+    }
+
+    /// Get with retry retries the get on the retryable error, which is the logic under test
+    /// of the downstream crates.
+    async fn get_with_retry(
+        backend: &dyn Backend,
+        url: &str,
+        max_attempts: usize,
+    ) -> ClientResult<String> {
+        let mut attempts = 0;
+        loop {
+            attempts += 1;
+            match backend.get(get_request(url)).await {
+                Ok(mut response) => return response.text().await,
+                Err(err) if err.is_retryable() && attempts < max_attempts => continue,
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn should_fail_then_succeed_get() {
+        let url = "mock://bucket/file";
+        let backend = MockBackend::builder()
+            .error(MockMethod::Get, url, ClientError::SendTimeout)
+            .error(
+                MockMethod::Get,
+                url,
+                ClientError::IO(std::io::ErrorKind::ConnectionReset.into()),
+            )
+            .get(url, MockGetResponse::new("content"))
+            .build();
+
+        assert_eq!(get_with_retry(&backend, url, 3).await.unwrap(), "content");
+        assert_eq!(backend.calls(MockMethod::Get, url), 3);
+        assert_eq!(backend.total_calls(MockMethod::Get), 3);
+
+        // The canned response is returned after the script is exhausted.
+        let response = backend.get(get_request(url)).await.unwrap();
+        assert_eq!(response.content_length, Some(7));
+        assert_eq!(backend.calls(MockMethod::Get, url), 4);
+    }
+
+    #[tokio::test]
+    async fn should_give_up_when_errors_exceed_attempts() {
+        let url = "mock://bucket/file";
+        let backend = MockBackend::builder()
+            .error(MockMethod::Get, url, ClientError::SendTimeout)
+            .error(MockMethod::Get, url, ClientError::SendTimeout)
+            .get(url, MockGetResponse::new("content"))
+            .build();
+
+        assert!(get_with_retry(&backend, url, 2).await.is_err());
+        assert_eq!(backend.calls(MockMethod::Get, url), 2);
+    }
+
+    #[tokio::test]
+    async fn should_serve_programmed_responses_per_url() {
+        let backend = MockBackend::builder()
+            .scheme("s3")
+            .exists("s3://bucket/present", true)
+            .exists("s3://bucket/absent", false)
+            .error(
+                MockMethod::Put,
+                "s3://bucket/file",
+                ClientError::Unauthorized,
+            )
+            .build();
+        assert_eq!(backend.scheme(), "s3");
+
+        let exists_request = |url: &str| ExistsRequest {
+            task_id: "test".to_string(),
+            url: url.to_string(),
+            http_header: None,
+            timeout: Duration::from_secs(1),
+            client_cert: None,
+            object_storage: None,
+            hdfs: None,
+            hugging_face: None,
+            model_scope: None,
+        };
+        assert!(backend
+            .exists(exists_request("s3://bucket/present"))
+            .await
+            .unwrap());
+        assert!(!backend
+            .exists(exists_request("s3://bucket/absent"))
+            .await
+            .unwrap());
+
+        // The URL without the programmed response fails.
+        assert!(backend
+            .exists(exists_request("s3://bucket/other"))
+            .await
+            .is_err());
+        assert!(backend.get(get_request("s3://bucket/other")).await.is_err());
+        assert_eq!(backend.total_calls(MockMethod::Exists), 3);
+
+        // The put fails with the programmed error once, then succeeds.
+        let put_request = PutRequest {
+            task_id: "test".to_string(),
+            url: "s3://bucket/file".to_string(),
+            path: "/tmp/file".into(),
+            http_header: None,
+            timeout: Duration::from_secs(1),
+            client_cert: None,
+            object_storage: None,
+            hdfs: None,
+            hugging_face: None,
+            model_scope: None,
+            if_not_exists: false,
+            acl: None,
+        };
+        assert!(matches!(
+            backend.put(put_request.clone()).await,
+            Err(ClientError::Unauthorized)
+        ));
+        assert!(backend.put(put_request).await.unwrap().success);
+        assert_eq!(backend.calls(MockMethod::Put, "s3://bucket/file"), 2);
+    }
+}
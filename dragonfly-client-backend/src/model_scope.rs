@@ -32,9 +32,12 @@
 //!
 //! For private repositories or to increase rate limits, use the `--ms-token` flag.
 
+use crate::http::accept_ranges;
 use crate::{
-    Backend, Body, DirEntry, ExistsRequest, GetRequest, GetResponse, PutRequest, PutResponse,
-    StatRequest, StatResponse, DEFAULT_USER_AGENT, KEEP_ALIVE_INTERVAL, POOL_MAX_IDLE_PER_HOST,
+    cancel_body, content_length, decompress_body, digest_body, limit_body, tls_client_builder,
+    Backend, Body, DirEntry, ExistsRequest, FailureClass, GetRequest, GetResponse, PutRequest,
+    PutResponse, StatRequest, StatResponse, DEFAULT_USER_AGENT, KEEP_ALIVE_INTERVAL,
+    POOL_MAX_IDLE_PER_HOST,
 };
 use async_trait::async_trait;
 use dragonfly_api::common::v2::Range;
@@ -43,11 +46,11 @@ use dragonfly_client_core::{
     error::{BackendError, ErrorType, OrErr},
     Error, Result,
 };
-use dragonfly_client_util::tls::NoVerifier;
 use futures::TryStreamExt;
 use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_LENGTH, RANGE, USER_AGENT};
 use reqwest::Client;
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::io::{Error as IOError, ErrorKind};
 use std::sync::Arc;
 use tokio_util::io::StreamReader;
@@ -214,19 +217,19 @@ impl ModelScope {
     /// Create a new ModelScope backend.
     pub fn new(config: Arc<Config>) -> Result<Self> {
         // Default TLS client config with no validation.
-        let client_config_builder = rustls::ClientConfig::builder()
-            .dangerous()
-            .with_custom_certificate_verifier(NoVerifier::new())
-            .with_no_client_auth();
-
-        let client = reqwest::Client::builder()
+        let client = tls_client_builder(None, config.backend.min_tls_version)
             .no_gzip()
             .no_brotli()
             .no_zstd()
             .no_deflate()
             .hickory_dns(config.backend.enable_hickory_dns)
-            .use_preconfigured_tls(client_config_builder)
-            .pool_max_idle_per_host(POOL_MAX_IDLE_PER_HOST)
+            .pool_idle_timeout(config.backend.pool_idle_timeout)
+            .pool_max_idle_per_host(
+                config
+                    .backend
+                    .pool_max_idle_per_host
+                    .unwrap_or(POOL_MAX_IDLE_PER_HOST),
+            )
             .tcp_keepalive(KEEP_ALIVE_INTERVAL)
             .tcp_nodelay(true)
             .build()?;
@@ -368,12 +371,23 @@ impl Backend for ModelScope {
                             http_status_code: None,
                             entries: Vec::new(),
                             error_message: Some(err.to_string()),
+                            user_metadata: HashMap::new(),
+                            next_continuation_token: None,
+                            storage_class: None,
+                            content_type: None,
+                            accept_ranges: false,
+                            suggested_filename: None,
+                            truncated: false,
+                            is_dir: false,
+                            failure_class: Some(FailureClass::from_error(&err)),
+                            etag: None,
                         });
                     }
                 };
 
                 let response_status_code = response.status();
                 let response_header = response.headers().clone();
+                let accept_ranges = accept_ranges(&response_header);
                 let content_length = match response_header.get(CONTENT_LENGTH) {
                     Some(content_length) => content_length.to_str()?.parse::<u64>().ok(),
                     None => response.content_length(),
@@ -396,6 +410,16 @@ impl Backend for ModelScope {
                     http_status_code: Some(response_status_code),
                     error_message: Some(response_status_code.to_string()),
                     entries: Vec::new(),
+                    user_metadata: HashMap::new(),
+                    next_continuation_token: None,
+                    storage_class: None,
+                    content_type: None,
+                    accept_ranges,
+                    suggested_filename: None,
+                    truncated: false,
+                    is_dir: false,
+                    failure_class: FailureClass::from_status_code(response_status_code),
+                    etag: None,
                 })
             }
             None => {
@@ -422,6 +446,16 @@ impl Backend for ModelScope {
                             http_status_code: None,
                             entries: Vec::new(),
                             error_message: Some(err.to_string()),
+                            user_metadata: HashMap::new(),
+                            next_continuation_token: None,
+                            storage_class: None,
+                            content_type: None,
+                            accept_ranges: false,
+                            suggested_filename: None,
+                            truncated: false,
+                            is_dir: false,
+                            failure_class: Some(FailureClass::from_error(&err)),
+                            etag: None,
                         });
                     }
                 };
@@ -441,6 +475,16 @@ impl Backend for ModelScope {
                         http_status_code: response_status_code.into(),
                         error_message: Some(response_status_code.to_string()),
                         entries: Vec::new(),
+                        user_metadata: HashMap::new(),
+                        next_continuation_token: None,
+                        storage_class: None,
+                        content_type: None,
+                        accept_ranges: false,
+                        suggested_filename: None,
+                        truncated: false,
+                        is_dir: false,
+                        failure_class: FailureClass::from_status_code(response_status_code),
+                        etag: None,
                     });
                 }
 
@@ -501,6 +545,9 @@ impl Backend for ModelScope {
                             url: ms_url,
                             content_length: content_length as usize,
                             is_dir: false,
+                            etag: None,
+                            last_modified: None,
+                            content_type: None,
                         })
                     })
                     .collect();
@@ -521,6 +568,16 @@ impl Backend for ModelScope {
                     http_status_code: Some(response_status_code),
                     error_message: Some(response_status_code.to_string()),
                     entries,
+                    user_metadata: HashMap::new(),
+                    next_continuation_token: None,
+                    storage_class: None,
+                    content_type: None,
+                    accept_ranges: true,
+                    suggested_filename: None,
+                    truncated: false,
+                    is_dir: true,
+                    failure_class: FailureClass::from_status_code(response_status_code),
+                    etag: None,
                 })
             }
         }
@@ -532,6 +589,15 @@ impl Backend for ModelScope {
             request.task_id, request.piece_id, request.url, request.http_header
         );
 
+        // The multiple ranges and the suffix range are only supported by the HTTP and object
+        // storage backends.
+        if !request.ranges.is_empty() || request.suffix_length.is_some() {
+            return Err(Error::Unsupported(format!(
+                "{} multiple ranges and suffix range",
+                self.scheme
+            )));
+        }
+
         // Build request headers, including authentication if provided ModelScope token.
         let request_header = Self::build_request_headers(
             request.model_scope.as_ref().and_then(|ms| ms.token.clone()),
@@ -582,6 +648,11 @@ impl Backend for ModelScope {
                     http_status_code: None,
                     reader: Box::new(tokio::io::empty()),
                     error_message: Some(err.to_string()),
+                    piece_digests: None,
+                    range_readers: Vec::new(),
+                    suggested_filename: None,
+                    content_length: None,
+                    total_length: None,
                 });
             }
         };
@@ -599,12 +670,29 @@ impl Backend for ModelScope {
             request.task_id, request.piece_id, response_status_code, response_header,
         );
 
+        let (reader, piece_digests) = digest_body(
+            limit_body(
+                decompress_body(
+                    cancel_body(response_reader, request.cancel_token),
+                    request.auto_decompress,
+                ),
+                request.max_body_size,
+            ),
+            request.piece_length,
+        );
+
+        let content_length = content_length(&response_header).filter(|_| !request.auto_decompress);
         Ok(GetResponse {
             success: response_status_code.is_success(),
             http_header: Some(response_header),
             http_status_code: Some(response_status_code),
-            reader: response_reader,
+            reader,
             error_message: Some(response_status_code.to_string()),
+            piece_digests,
+            range_readers: Vec::new(),
+            suggested_filename: None,
+            content_length,
+            total_length: None,
         })
     }
 
@@ -24,31 +24,56 @@
 //! # Supported Schemes
 //!
 //! - `s3://` - Amazon Simple Storage Service (S3)
-//! - `gs://` - Google Cloud Storage (GCS)
+//! - `gs://` - Google Cloud Storage (GCS), `gcs://` is accepted as an alias
 //! - `abs://` - Azure Blob Storage (ABS)
 //! - `oss://` - Aliyun Object Storage Service (OSS)
 //! - `obs://` - Huawei Cloud Object Storage Service (OBS)
 //! - `cos://` - Tencent Cloud Object Storage Service (COS)
+//! - `oci://` - Oracle Cloud Infrastructure Object Storage (OCI)
+//! - `s3c://` - Generic S3-compatible object storage, such as Wasabi and DigitalOcean Spaces
 //!
 //! # URL Format
 //!
 //! The URL format is: `<scheme>://<bucket>/<key>`
 //!
+//! For S3, the virtual-hosted-style host `<bucket>.s3.<region>.amazonaws.com` and the path-style
+//! host `s3.<region>.amazonaws.com/<bucket>` are also recognized, and the region in the host is
+//! used when the region is not configured.
+//!
+//! For OCI, the URL format `oci://<bucket>@<namespace>/<key>` is also supported, and the
+//! namespace is used to derive the S3-compatible endpoint when the endpoint is not configured.
+//!
+//! For the generic S3-compatible object storage, the endpoint is required and the path-style
+//! addressing `<endpoint>/<bucket>/<key>` is always used, so any S3-compatible provider can be
+//! addressed without the per-vendor code.
+//!
 //! Examples:
 //! - `s3://my-bucket/models/` - List entire directory in S3
 //! - `s3://my-bucket/models/weights.bin` - Access specific file in S3
 //! - `gs://my-bucket/data/train.csv` - Access specific file in GCS
 //! - `oss://my-bucket/path/to/file` - Access specific file in OSS
 //!
+//! The URL without the trailing slash, such as `s3://my-bucket/models`, is also listed as a
+//! directory if no object exists at the key but any object exists under the prefix.
+//!
 //! # Authentication
 //!
 //! Each object storage provider requires different credentials:
-//! - **S3**: `access_key_id`, `access_key_secret`, and `region` (optionally `endpoint`, `session_token`)
+//! - **S3**: `access_key_id`, `access_key_secret`, and `region` (optionally `endpoint`, `session_token`, `predefined_acl`)
 //! - **GCS**: `credential_path` for service account credentials (optionally `endpoint`, `predefined_acl`)
 //! - **ABS**: `access_key_id` (account name), `access_key_secret` (account key), and `endpoint`
-//! - **OSS**: `access_key_id`, `access_key_secret`, and `endpoint` (optionally `security_token`)
+//! - **OSS**: `access_key_id`, `access_key_secret`, and `endpoint` (optionally `security_token`, `predefined_acl`)
 //! - **OBS**: `access_key_id`, `access_key_secret`, and `endpoint`
 //! - **COS**: `access_key_id` (secret id), `access_key_secret` (secret key), and `endpoint`
+//! - **OCI**: `access_key_id`, `access_key_secret`, `region`, and `endpoint` or the namespace in URL
+//! - **S3C**: `access_key_id`, `access_key_secret`, and `endpoint` (optionally `region`, `session_token`)
+//!
+//! # Endpoint Failover
+//!
+//! The endpoint can be the comma-separated list of the endpoints of the same bucket, such as
+//! `http://gateway-1:9000,http://gateway-2:9000`. The endpoints are tried in order, and the
+//! request fails over to the next endpoint on the connection-level failures. The failed
+//! endpoint is tried last until it recovers or the unhealthy duration elapses.
 //!
 //! # TLS Configuration
 //!
@@ -57,26 +82,46 @@
 //! object storage configuration.
 
 use crate::{
-    Body, DirEntry, ExistsRequest, GetRequest, GetResponse, PutRequest, PutResponse, StatRequest,
-    StatResponse, HTTP2_CONNECTION_WINDOW_SIZE, HTTP2_KEEP_ALIVE_INTERVAL,
-    HTTP2_KEEP_ALIVE_TIMEOUT, HTTP2_STREAM_WINDOW_SIZE, KEEP_ALIVE_INTERVAL,
-    POOL_MAX_IDLE_PER_HOST,
+    cancel_body, decompress_body, digest_body, guard_body, limit_body, redact_url,
+    run_until_cancelled, set_trace_parent, tls_client_builder, AppendRequest, Body, CopyRequest,
+    DirEntry, ExistsRequest, FailureClass, GetRequest, GetResponse, ListMetakey, PutRequest,
+    PutResponse, RetryBudget, StatRequest, StatResponse, HTTP2_CONNECTION_WINDOW_SIZE,
+    HTTP2_KEEP_ALIVE_INTERVAL, HTTP2_KEEP_ALIVE_TIMEOUT, HTTP2_STREAM_WINDOW_SIZE,
+    KEEP_ALIVE_INTERVAL, POOL_MAX_IDLE_PER_HOST,
 };
 use async_trait::async_trait;
+use base64::prelude::{Engine, BASE64_STANDARD};
+use bytes::Bytes;
 use dragonfly_api::common;
 use dragonfly_client_config::dfdaemon::Config;
-use dragonfly_client_core::error::BackendError;
+use dragonfly_client_core::error::{BackendError, ErrorType, OrErr};
 use dragonfly_client_core::{Error as ClientError, Result as ClientResult};
-use dragonfly_client_util::tls::NoVerifier;
-use opendal::{layers::HttpClientLayer, layers::TimeoutLayer, raw::HttpClient, Operator};
-use percent_encoding::percent_decode_str;
+use futures::future::{BoxFuture, Shared};
+use futures::{future, FutureExt, Stream, TryStreamExt};
+use lru::LruCache;
+use opendal::{
+    layers::HttpClientLayer,
+    layers::TimeoutLayer,
+    raw::{HttpBody, HttpClient, HttpFetch},
+    Buffer, EntryMode, Metadata, Operator,
+};
+use parking_lot::{Mutex, RwLock};
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, CONTROLS};
+use sha2::Digest as Sha2Digest;
+use std::borrow::Borrow;
+use std::collections::HashMap;
 use std::fmt;
+use std::num::NonZeroUsize;
+use std::path::Path;
+use std::pin::Pin;
 use std::result::Result;
 use std::str::FromStr;
 use std::sync::Arc;
-use std::time::Duration;
+use std::task::{ready, Context, Poll};
+use std::time::{Duration, Instant, SystemTime};
+use tokio::io::{AsyncRead, AsyncReadExt, ReadBuf};
 use tokio_util::io::StreamReader;
-use tracing::{debug, error, instrument};
+use tracing::{debug, error, instrument, warn};
 use url::Url;
 
 /// Scheme is the scheme of the object storage.
@@ -99,13 +144,85 @@ pub enum Scheme {
 
     /// COS is the Tencent Cloud Object Storage Service.
     COS,
+
+    /// OCI is the Oracle Cloud Infrastructure Object Storage Service.
+    OCI,
+
+    /// S3Compatible is the generic S3-compatible object storage service, such as Wasabi,
+    /// DigitalOcean Spaces and Linode Object Storage.
+    S3Compatible,
 }
 
 /// Scheme implements the Scheme trait.
 impl Scheme {
-    /// Returns true if the given string is a supported scheme.
+    /// Returns true if the given string is a supported scheme, including the compound scheme
+    /// such as `s3+https`.
     pub fn is_supported(scheme: &str) -> bool {
-        scheme.parse::<Scheme>().is_ok()
+        Scheme::parse_compound(scheme).is_ok()
+    }
+
+    /// From url parses the scheme of the URL, and returns the transport if the scheme is the
+    /// compound scheme, such as `s3+https://bucket/key`.
+    pub fn from_url(url: &Url) -> Result<(Scheme, Option<Transport>), String> {
+        Scheme::parse_compound(url.scheme())
+    }
+
+    /// Parse compound parses the scheme in the form of `<scheme>` or `<scheme>+<transport>`,
+    /// such as `s3+https` and `oss+http`, the transport is None for the plain scheme.
+    pub fn parse_compound(s: &str) -> Result<(Scheme, Option<Transport>), String> {
+        match s.split_once('+') {
+            Some((scheme, transport)) => Ok((scheme.parse()?, Some(transport.parse()?))),
+            None => Ok((s.parse()?, None)),
+        }
+    }
+}
+
+/// Transport is the transport of the compound scheme, which drives the TLS of the endpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transport {
+    /// Http is the plain HTTP transport.
+    Http,
+
+    /// Https is the HTTP transport over TLS.
+    Https,
+}
+
+/// Transport implements the Transport.
+impl Transport {
+    /// Apply returns the endpoint with the scheme of the transport, the scheme of the endpoint
+    /// is replaced if it is present.
+    pub fn apply(&self, endpoint: &str) -> String {
+        let endpoint = endpoint
+            .strip_prefix("http://")
+            .or_else(|| endpoint.strip_prefix("https://"))
+            .unwrap_or(endpoint);
+
+        format!("{}://{}", self, endpoint)
+    }
+}
+
+/// Transport implements the Display.
+impl fmt::Display for Transport {
+    /// Fmt formats the value using the given formatter.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Transport::Http => write!(f, "http"),
+            Transport::Https => write!(f, "https"),
+        }
+    }
+}
+
+/// Transport implements the FromStr.
+impl FromStr for Transport {
+    type Err = String;
+
+    /// FromStr parses a transport string.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "http" => Ok(Transport::Http),
+            "https" => Ok(Transport::Https),
+            _ => Err(format!("invalid transport: {}", s)),
+        }
     }
 }
 
@@ -120,6 +237,8 @@ impl fmt::Display for Scheme {
             Scheme::OSS => write!(f, "oss"),
             Scheme::OBS => write!(f, "obs"),
             Scheme::COS => write!(f, "cos"),
+            Scheme::OCI => write!(f, "oci"),
+            Scheme::S3Compatible => write!(f, "s3c"),
         }
     }
 }
@@ -128,22 +247,396 @@ impl fmt::Display for Scheme {
 impl FromStr for Scheme {
     type Err = String;
 
-    /// FromStr parses a scheme string.
+    /// FromStr parses a scheme string, the alias of the scheme such as `gcs` is parsed to the
+    /// same scheme, and the scheme is always displayed as the canonical one.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "s3" => Ok(Scheme::S3),
-            "gs" => Ok(Scheme::GCS),
+            "gs" | "gcs" => Ok(Scheme::GCS),
             "abs" => Ok(Scheme::ABS),
             "oss" => Ok(Scheme::OSS),
             "obs" => Ok(Scheme::OBS),
             "cos" => Ok(Scheme::COS),
+            "oci" => Ok(Scheme::OCI),
+            "s3c" => Ok(Scheme::S3Compatible),
             _ => Err(format!("invalid scheme: {}", s)),
         }
     }
 }
 
-/// ParsedURL is a struct that contains the parsed URL, bucket, and path.
+/// ObjectStorageConfig builds the object storage information of the requests, which sets only
+/// the required fields instead of constructing the full proto.
+pub struct ObjectStorageConfig;
+
+/// ObjectStorageConfig implements the ObjectStorageConfig.
+impl ObjectStorageConfig {
+    /// Builder returns a new ObjectStorageConfigBuilder.
+    pub fn builder() -> ObjectStorageConfigBuilder {
+        ObjectStorageConfigBuilder::default()
+    }
+}
+
+/// ObjectStorageConfigBuilder is the builder for the object storage information.
+#[derive(Default)]
+pub struct ObjectStorageConfigBuilder {
+    /// Object storage is the object storage information being built.
+    object_storage: common::v2::ObjectStorage,
+}
+
+/// ObjectStorageConfigBuilder implements the builder methods.
+impl ObjectStorageConfigBuilder {
+    /// Sets the access key id.
+    pub fn access_key_id(mut self, access_key_id: impl Into<String>) -> Self {
+        self.object_storage.access_key_id = Some(access_key_id.into());
+        self
+    }
+
+    /// Sets the access key secret.
+    pub fn access_key_secret(mut self, access_key_secret: impl Into<String>) -> Self {
+        self.object_storage.access_key_secret = Some(access_key_secret.into());
+        self
+    }
+
+    /// Sets the security token.
+    pub fn security_token(mut self, security_token: impl Into<String>) -> Self {
+        self.object_storage.security_token = Some(security_token.into());
+        self
+    }
+
+    /// Sets the session token.
+    pub fn session_token(mut self, session_token: impl Into<String>) -> Self {
+        self.object_storage.session_token = Some(session_token.into());
+        self
+    }
+
+    /// Sets the region.
+    pub fn region(mut self, region: impl Into<String>) -> Self {
+        self.object_storage.region = Some(region.into());
+        self
+    }
+
+    /// Sets the endpoint.
+    pub fn endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.object_storage.endpoint = Some(endpoint.into());
+        self
+    }
+
+    /// Sets the credential path.
+    pub fn credential_path(mut self, credential_path: impl Into<String>) -> Self {
+        self.object_storage.credential_path = Some(credential_path.into());
+        self
+    }
+
+    /// Sets the predefined acl.
+    pub fn predefined_acl(mut self, predefined_acl: impl Into<String>) -> Self {
+        self.object_storage.predefined_acl = Some(predefined_acl.into());
+        self
+    }
+
+    /// Sets the insecure skip verify.
+    pub fn insecure_skip_verify(mut self, insecure_skip_verify: bool) -> Self {
+        self.object_storage.insecure_skip_verify = Some(insecure_skip_verify);
+        self
+    }
+
+    /// Builds and returns the object storage information. It returns an error if the options
+    /// conflict, the credential path of the service account and the access key are mutually
+    /// exclusive, and the access key id and secret must be set together.
+    pub fn build(self) -> ClientResult<common::v2::ObjectStorage> {
+        let object_storage = self.object_storage;
+        let has_access_key =
+            object_storage.access_key_id.is_some() || object_storage.access_key_secret.is_some();
+
+        if object_storage.credential_path.is_some() && has_access_key {
+            return Err(ClientError::ValidationError(
+                "credential_path and access key are mutually exclusive".to_string(),
+            ));
+        }
+
+        if has_access_key
+            && (object_storage.access_key_id.is_none()
+                || object_storage.access_key_secret.is_none())
+        {
+            return Err(ClientError::ValidationError(
+                "access_key_id and access_key_secret must be set together".to_string(),
+            ));
+        }
+
+        if (object_storage.session_token.is_some() || object_storage.security_token.is_some())
+            && !has_access_key
+        {
+            return Err(ClientError::ValidationError(
+                "session_token and security_token require access key".to_string(),
+            ));
+        }
+
+        Ok(object_storage)
+    }
+}
+
+/// KEY_ENCODE_SET is the set of the characters percent-encoded in the object key of the URL
+/// path, which includes `%` so the literal `%` of the key is not decoded as an escape.
+const KEY_ENCODE_SET: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'%')
+    .add(b'<')
+    .add(b'>')
+    .add(b'?')
+    .add(b'`')
+    .add(b'{')
+    .add(b'}');
+
+/// OPERATOR_ROOT is the root of all the object storage operators. The key of the ParsedURL is
+/// relative to the root, so the same key addresses the same object for every scheme.
+const OPERATOR_ROOT: &str = "/";
+
+/// X_AMZ_EXPECTED_BUCKET_OWNER is the header of the account id of the expected owner of the S3
+/// bucket.
+const X_AMZ_EXPECTED_BUCKET_OWNER: &str = "x-amz-expected-bucket-owner";
+
+/// X_AMZ_CHECKSUM_MODE is the header of the S3 request enabling the checksums of the object
+/// in the response.
+const X_AMZ_CHECKSUM_MODE: &str = "x-amz-checksum-mode";
+
+/// X_AMZ_DATE is the header of the signing time of the S3 request.
+const X_AMZ_DATE: &str = "x-amz-date";
+
+/// HeaderSigner is the signer of the requests of the SignedHeadersFetcher. The operator signs
+/// the requests before they reach the HTTP client, so the headers added by the fetcher are
+/// covered by the signature only after the request is signed again.
+enum HeaderSigner {
+    /// S3 signs the requests with the AWS signature version 4.
+    S3(reqsign::AwsV4Signer, reqsign::AwsCredential),
+
+    /// OSS signs the requests with the OSS signature.
+    OSS(reqsign::AliyunOssSigner, reqsign::AliyunCredential),
+}
+
+/// HeaderSigner implements the HeaderSigner.
+impl HeaderSigner {
+    /// Sign removes the signature of the operator and signs the request again, so the
+    /// signature covers all the headers of the request.
+    fn sign(&self, request: &mut http::Request<Buffer>) -> opendal::Result<()> {
+        request.headers_mut().remove(http::header::AUTHORIZATION);
+        let result = match self {
+            HeaderSigner::S3(signer, credential) => {
+                request.headers_mut().remove(X_AMZ_DATE);
+                signer.sign(request, credential)
+            }
+            HeaderSigner::OSS(signer, credential) => {
+                request.headers_mut().remove(http::header::DATE);
+                signer.sign(request, credential)
+            }
+        };
+
+        result.map_err(|err| {
+            opendal::Error::new(opendal::ErrorKind::Unexpected, "failed to sign the request")
+                .set_source(err)
+        })
+    }
+}
+
+/// SignedHeadersFetcher is the HTTP client of the operator setting the headers not supported
+/// by the options of opendal, such as the expected bucket owner of S3 and the canned ACL of
+/// the object. The request is signed again after the headers are set, otherwise the service
+/// rejects the request with the signature mismatch.
+struct SignedHeadersFetcher {
+    /// Client is the HTTP client sending the requests.
+    client: HttpClient,
+
+    /// Signer signs the requests after the headers are set.
+    signer: HeaderSigner,
+
+    /// Headers are set on every request, including the stat, the list and the reads.
+    headers: http::HeaderMap,
+
+    /// Object write headers are set on the writes of the object, which are the upload of the
+    /// object and the initiation of the multipart upload. The other requests, such as the
+    /// upload of the parts, do not accept them.
+    object_write_headers: http::HeaderMap,
+}
+
+/// SignedHeadersFetcher implements the SignedHeadersFetcher.
+impl SignedHeadersFetcher {
+    /// Is object write returns whether the request writes the object, which is the PUT without
+    /// the part number or the POST initiating the multipart upload.
+    fn is_object_write(request: &http::Request<Buffer>) -> bool {
+        let mut query = request
+            .uri()
+            .query()
+            .unwrap_or_default()
+            .split('&')
+            .map(|pair| pair.split('=').next().unwrap_or_default());
+
+        match *request.method() {
+            http::Method::PUT => !query.any(|key| key == "partNumber"),
+            http::Method::POST => query.any(|key| key == "uploads"),
+            _ => false,
+        }
+    }
+}
+
+/// SignedHeadersFetcher implements the HttpFetch of opendal.
+impl HttpFetch for SignedHeadersFetcher {
+    /// Fetch sets the headers, signs the request again and sends the request.
+    async fn fetch(
+        &self,
+        mut request: http::Request<Buffer>,
+    ) -> opendal::Result<http::Response<HttpBody>> {
+        let mut headers = self.headers.clone();
+        if Self::is_object_write(&request) {
+            headers.extend(self.object_write_headers.clone());
+        }
+
+        if !headers.is_empty() {
+            request.headers_mut().extend(headers);
+            self.signer.sign(&mut request)?;
+        }
+
+        self.client.fetch(request).await
+    }
+}
+
+/// HostHeaderFetcher is the HTTP client of the S3 operator for the object storage behind a
+/// reverse proxy. The operator signs the requests with the overridden host, and the fetcher
+/// sends the requests to the endpoint with the signed host as the `Host` header.
+struct HostHeaderFetcher {
+    /// Client is the HTTP client sending the requests.
+    client: HttpClient,
+
+    /// Endpoint authority is the authority of the endpoint connected by the requests.
+    endpoint_authority: http::uri::Authority,
+}
+
+/// HostHeaderFetcher implements the HttpFetch of opendal.
+impl HttpFetch for HostHeaderFetcher {
+    /// Fetch sets the `Host` header to the signed host, and sends the request to the endpoint.
+    async fn fetch(
+        &self,
+        mut request: http::Request<Buffer>,
+    ) -> opendal::Result<http::Response<HttpBody>> {
+        let mut parts = request.uri().clone().into_parts();
+        if let Some(host) = parts.authority.replace(self.endpoint_authority.clone()) {
+            let host = http::HeaderValue::from_str(host.as_str()).map_err(|err| {
+                opendal::Error::new(opendal::ErrorKind::Unexpected, "invalid host").set_source(err)
+            })?;
+            request.headers_mut().insert(http::header::HOST, host);
+        }
+
+        *request.uri_mut() = http::Uri::from_parts(parts).map_err(|err| {
+            opendal::Error::new(opendal::ErrorKind::Unexpected, "invalid uri").set_source(err)
+        })?;
+        self.client.fetch(request).await
+    }
+}
+
+/// MAX_CLOCK_SKEW is the maximum difference between the clock of the host and the clock of the
+/// object storage accepted by the signature of the requests, which is 15 minutes for S3.
+const MAX_CLOCK_SKEW: Duration = Duration::from_secs(15 * 60);
+
+/// ResponseError is the error of the response classified by the status code and the headers,
+/// which is the source of the opendal error, as opendal does not expose the response headers.
 #[derive(Debug)]
+enum ResponseError {
+    /// Throttled is the 429 or 503 response, with the delay of the Retry-After header.
+    Throttled { retry_after: Option<Duration> },
+
+    /// ClockSkew is the 403 response whose Date header differs from the clock of the host more
+    /// than the MAX_CLOCK_SKEW, such as the `RequestTimeTooSkewed` of S3.
+    ClockSkew {
+        server_time: chrono::DateTime<chrono::Utc>,
+    },
+}
+
+/// ResponseError implements the ResponseError.
+impl ResponseError {
+    /// From response classifies the response, and returns None if the response is not
+    /// classified, which is converted to the error by opendal.
+    fn from_response(response: &http::Response<HttpBody>) -> Option<Self> {
+        match response.status() {
+            http::StatusCode::TOO_MANY_REQUESTS | http::StatusCode::SERVICE_UNAVAILABLE => {
+                Some(Self::Throttled {
+                    retry_after: crate::http::parse_retry_after(response.headers()),
+                })
+            }
+            http::StatusCode::FORBIDDEN => {
+                let server_time = response
+                    .headers()
+                    .get(http::header::DATE)
+                    .and_then(|date| date.to_str().ok())
+                    .and_then(|date| chrono::DateTime::parse_from_rfc2822(date).ok())
+                    .map(|date| date.with_timezone(&chrono::Utc))?;
+
+                let skew = (chrono::Utc::now() - server_time).abs().to_std().ok()?;
+                (skew > MAX_CLOCK_SKEW).then_some(Self::ClockSkew { server_time })
+            }
+            _ => None,
+        }
+    }
+
+    /// Into opendal error converts the ResponseError to the opendal error with the source of
+    /// itself, the throttled error is temporary.
+    fn into_opendal_error(self) -> opendal::Error {
+        match self {
+            Self::Throttled { .. } => {
+                opendal::Error::new(opendal::ErrorKind::RateLimited, self.to_string())
+                    .set_temporary()
+                    .set_source(self)
+            }
+            Self::ClockSkew { .. } => {
+                opendal::Error::new(opendal::ErrorKind::PermissionDenied, self.to_string())
+                    .set_source(self)
+            }
+        }
+    }
+}
+
+/// ResponseError implements the Display.
+impl fmt::Display for ResponseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Throttled { retry_after } => {
+                write!(f, "request is throttled, retry after {:?}", retry_after)
+            }
+            Self::ClockSkew { server_time } => write!(
+                f,
+                "request time is too skewed from the server time {}",
+                server_time
+            ),
+        }
+    }
+}
+
+/// ResponseError implements the Error.
+impl std::error::Error for ResponseError {}
+
+/// ResponseErrorFetcher is the HTTP client of the operators converting the responses classified
+/// by the ResponseError to the opendal errors, so the status code and the headers of the
+/// response are kept as the source of the error.
+struct ResponseErrorFetcher {
+    /// Client is the HTTP client sending the requests.
+    client: HttpClient,
+}
+
+/// ResponseErrorFetcher implements the HttpFetch of opendal.
+impl HttpFetch for ResponseErrorFetcher {
+    /// Fetch sends the request, and converts the classified response to the error.
+    async fn fetch(
+        &self,
+        request: http::Request<Buffer>,
+    ) -> opendal::Result<http::Response<HttpBody>> {
+        let response = self.client.fetch(request).await?;
+        match ResponseError::from_response(&response) {
+            Some(err) => Err(err.into_opendal_error()),
+            None => Ok(response),
+        }
+    }
+}
+
+/// ParsedURL is a struct that contains the parsed URL, bucket, and path.
+#[derive(Debug, Clone)]
 pub struct ParsedURL {
     /// URL is the requested URL of the object storage.
     pub url: Url,
@@ -156,22 +649,227 @@ pub struct ParsedURL {
 
     /// Key is the key of the object storage.
     pub key: String,
+
+    /// Region is the region embedded in the URL host, such as `eu-west-1` in
+    /// `s3://bucket.s3.eu-west-1.amazonaws.com/key`.
+    pub region: Option<String>,
+
+    /// Namespace is the namespace of the OCI object storage, such as `namespace` in
+    /// `oci://bucket@namespace/key`.
+    pub namespace: Option<String>,
+
+    /// Transport is the transport of the compound scheme, such as `https` in
+    /// `s3+https://bucket/key`, which overrides the scheme of the endpoint.
+    pub transport: Option<Transport>,
 }
 
 /// ParsedURL implements the ParsedURL trait.
 impl ParsedURL {
+    /// Parse parses the string as the URL and converts it to the ParsedURL. It returns an error
+    /// instead of panicking for any input, which is guaranteed by the `parsed_url` fuzz target.
+    pub fn parse(s: &str) -> ClientResult<ParsedURL> {
+        let url = Url::parse(s).map_err(|_| ClientError::InvalidURI(s.to_string()))?;
+        url.try_into()
+    }
+
     /// Returns true if the URL path is a directory, which means it ends with a slash.
     pub fn is_dir(&self) -> bool {
         self.url.path().ends_with('/')
     }
 
+    /// To dir returns the ParsedURL of the directory with the trailing slash, which is used when
+    /// the URL without the trailing slash is resolved as a prefix.
+    pub fn to_dir(&self) -> ParsedURL {
+        if self.is_dir() {
+            return self.clone();
+        }
+
+        let mut parsed_url = self.clone();
+        parsed_url.url.set_path(&format!("{}/", self.url.path()));
+        parsed_url.key = format!("{}/", self.key);
+        parsed_url
+    }
+
+    /// To root returns the ParsedURL of the bucket root, which keeps the bucket, the namespace
+    /// and the transport of the URL.
+    pub fn to_root(&self) -> ParsedURL {
+        ParsedURL {
+            url: self.make_url_by_entry_path(""),
+            key: String::new(),
+            ..self.clone()
+        }
+    }
+
+    /// Matches filter returns whether the entry path relative to the directory matches the glob
+    /// pattern of the filter.
+    fn matches_filter(&self, filter: &glob::Pattern, entry_path: &str) -> bool {
+        let relative_path = entry_path.strip_prefix(&self.key).unwrap_or(entry_path);
+        filter.matches_with(
+            relative_path,
+            glob::MatchOptions {
+                case_sensitive: true,
+                require_literal_separator: true,
+                require_literal_leading_dot: false,
+            },
+        )
+    }
+
+    /// Depth boundary returns the ancestor directory of the entry at the max depth relative to
+    /// the directory, and returns None if the entry is not deeper than the max depth. The depth
+    /// is the count of the path segments, e.g. the boundary of `a/b/file` at the max depth of 2
+    /// is `a/b/`.
+    fn depth_boundary(&self, entry_path: &str, max_depth: usize) -> Option<String> {
+        let relative_path = entry_path.strip_prefix(&self.key).unwrap_or(entry_path);
+        let segments: Vec<&str> = relative_path.trim_end_matches('/').split('/').collect();
+        if segments.len() <= max_depth {
+            return None;
+        }
+
+        Some(format!("{}{}/", self.key, segments[..max_depth].join("/")))
+    }
+
     /// Make a URL by the entry path when the URL is a directory. The entry path is the path of the
-    /// entry in the directory.
+    /// entry relative to the bucket root, so it is joined with the bucket root instead of the
+    /// requested prefix, and the trailing slash of the directory entry is kept.
     pub fn make_url_by_entry_path(&self, entry_path: &str) -> Url {
+        // The entry path is the raw key, so it is encoded to be decoded back to the same key.
+        let entry_path = ParsedURL::encode_key(entry_path.trim_start_matches('/'));
         let mut url = self.url.clone();
-        url.set_path(entry_path);
+        if self.is_path_style() {
+            url.set_path(&format!("/{}/{}", self.bucket, entry_path));
+        } else {
+            url.set_path(&format!("/{}", entry_path));
+        }
+
+        // The query and the fragment belong to the requested URL, such as the version id, and
+        // do not address the entry.
+        url.set_query(None);
+        url.set_fragment(None);
         url
     }
+
+    /// Encode key percent-encodes the raw object key for the URL path, the `/` separators are
+    /// kept. It is the inverse of the decode key.
+    pub fn encode_key(key: &str) -> String {
+        utf8_percent_encode(key, KEY_ENCODE_SET).to_string()
+    }
+
+    /// Decode key decodes the percent-encoded object key of the URL path to the raw key, which
+    /// is the form expected by the object storage operator because the operator encodes the
+    /// key of the request internally.
+    pub fn decode_key(key: &str) -> String {
+        percent_decode_str(key).decode_utf8_lossy().to_string()
+    }
+
+    /// Normalize key strips the single leading slash of the key, so the key is relative to the
+    /// root of the operator, e.g. `/a/b/c` is normalized to `a/b/c`. The repeated slashes are
+    /// kept, because `a//b` and `a/b` are the different objects in the object storage.
+    pub fn normalize_key(key: &str) -> String {
+        key.strip_prefix('/').unwrap_or(key).to_string()
+    }
+
+    /// Raw key returns the key of the URL path verbatim without decoding.
+    pub fn raw_key(&self) -> &str {
+        let key = self.url.path().strip_prefix('/').unwrap_or_default();
+        if self.is_path_style() {
+            return key.split_once('/').map(|(_, key)| key).unwrap_or_default();
+        }
+
+        key
+    }
+
+    /// Returns true if the bucket is in the URL path instead of the URL host, such as
+    /// `s3://s3.eu-west-1.amazonaws.com/bucket/key`.
+    fn is_path_style(&self) -> bool {
+        self.scheme == Scheme::S3
+            && matches!(
+                self.url.host_str().and_then(ParsedURL::parse_s3_host),
+                Some((None, _))
+            )
+    }
+
+    /// Parse the S3 host, which returns the bucket and the region embedded in the host. The
+    /// bucket is None if the host is path-style, and returns None if the host is not an S3
+    /// host.
+    ///
+    /// The supported host patterns are:
+    /// - `<bucket>.s3.<region>.amazonaws.com`
+    /// - `<bucket>.s3-<region>.amazonaws.com`
+    /// - `<bucket>.s3.dualstack.<region>.amazonaws.com`
+    /// - `<bucket>.s3.amazonaws.com`
+    /// - `s3.<region>.amazonaws.com`
+    fn parse_s3_host(host: &str) -> Option<(Option<String>, Option<String>)> {
+        let host = host
+            .strip_suffix(".amazonaws.com")
+            .or_else(|| host.strip_suffix(".amazonaws.com.cn"))?;
+
+        // Find the last `s3` label, because the bucket name may contain dots.
+        let labels: Vec<&str> = host.split('.').collect();
+        let index = labels
+            .iter()
+            .rposition(|label| *label == "s3" || label.starts_with("s3-"))?;
+
+        let bucket = match labels[..index].join(".") {
+            bucket if bucket.is_empty() => None,
+            bucket => Some(bucket),
+        };
+
+        let region = match labels[index].strip_prefix("s3-") {
+            Some(region) => Some(region.to_string()),
+            None => labels[index + 1..]
+                .iter()
+                .find(|label| **label != "dualstack")
+                .map(|region| region.to_string()),
+        };
+
+        Some((bucket, region))
+    }
+
+    /// Make dir entries converts the listed entries to the dir entries when the URL is a directory.
+    /// Some services return the listed directory itself as an entry, so the entry whose path
+    /// equals the key is excluded to avoid the self-reference. The metadata requested by the list
+    /// metakeys is populated from the listed metadata.
+    fn make_dir_entries(
+        &self,
+        entries: Vec<(String, Metadata)>,
+        list_metakeys: &[ListMetakey],
+    ) -> Vec<DirEntry> {
+        let list_etag = list_metakeys.contains(&ListMetakey::Etag);
+        let list_last_modified = list_metakeys.contains(&ListMetakey::LastModified);
+        let list_content_type = list_metakeys.contains(&ListMetakey::ContentType);
+
+        entries
+            .into_iter()
+            .filter(|(path, _)| *path != self.key)
+            .map(|(path, metadata)| DirEntry {
+                url: self.make_url_by_entry_path(&path).to_string(),
+                content_length: metadata.content_length() as usize,
+                is_dir: ParsedURL::is_dir_entry(&path, &metadata),
+                etag: metadata
+                    .etag()
+                    .filter(|_| list_etag)
+                    .map(|etag| etag.to_string()),
+                last_modified: metadata
+                    .last_modified()
+                    .filter(|_| list_last_modified)
+                    .map(|last_modified| last_modified.to_string()),
+                content_type: metadata
+                    .content_type()
+                    .filter(|_| list_content_type)
+                    .map(|content_type| content_type.to_string()),
+            })
+            .collect()
+    }
+
+    /// Returns true if the entry is a directory. Some services do not support the mode of the
+    /// metadata in the listing, and the mode is unknown, so the directory is inferred from the
+    /// trailing slash of the entry path.
+    fn is_dir_entry(path: &str, metadata: &Metadata) -> bool {
+        match metadata.mode() {
+            EntryMode::Unknown => path.ends_with('/'),
+            mode => mode.is_dir(),
+        }
+    }
 }
 
 /// ParsedURL implements the TryFrom trait for the URL.
@@ -182,1409 +880,8190 @@ impl TryFrom<Url> for ParsedURL {
 
     /// TryFrom parses the URL and returns a ParsedURL.
     fn try_from(url: Url) -> Result<Self, Self::Error> {
-        // Get the bucket from the URL host.
-        let bucket = url
+        // Get the host from the URL host, the empty host such as `s3:///key` is invalid.
+        let host = url
             .host_str()
-            .ok_or_else(|| ClientError::InvalidURI(url.to_string()))?
-            .to_string();
+            .filter(|host| !host.is_empty())
+            .ok_or_else(|| ClientError::InvalidURI(url.to_string()))?;
 
-        // Get the scheme from the URL scheme.
-        let scheme: Scheme = url.scheme().to_string().parse().map_err(|err| {
+        // Get the scheme and the transport from the URL scheme.
+        let (scheme, transport) = Scheme::from_url(&url).map_err(|err| {
             error!("parse scheme failed {}: {}", url, err);
             ClientError::InvalidURI(url.to_string())
         })?;
 
         // Get the key from the URL path.
-        let key = url
+        let mut key = url
             .path()
             .strip_prefix('/')
             .ok_or_else(|| ClientError::InvalidURI(url.to_string()))?;
-        // Decode the key.
-        let decoded_key = percent_decode_str(key).decode_utf8_lossy().to_string();
+
+        // Get the bucket and the namespace from the URL userinfo and host for OCI.
+        let mut namespace = None;
+
+        // Get the bucket and the region from the URL host. If the S3 host is path-style, the
+        // bucket is the first segment of the URL path.
+        let (bucket, region) = match scheme {
+            Scheme::S3 => match ParsedURL::parse_s3_host(host) {
+                Some((Some(bucket), region)) => (bucket, region),
+                Some((None, region)) => {
+                    let (bucket, path_key) = key
+                        .split_once('/')
+                        .filter(|(bucket, _)| !bucket.is_empty())
+                        .ok_or_else(|| ClientError::InvalidURI(url.to_string()))?;
+
+                    let bucket = bucket.to_string();
+                    key = path_key;
+                    (bucket, region)
+                }
+                None => (host.to_string(), None),
+            },
+            Scheme::OCI if !url.username().is_empty() => {
+                namespace = Some(host.to_string());
+                (url.username().to_string(), None)
+            }
+            _ => (host.to_string(), None),
+        };
+
+        // Decode and normalize the key, so the key is relative to the root of the operator.
+        let decoded_key = ParsedURL::normalize_key(&ParsedURL::decode_key(key));
 
         Ok(Self {
             url,
             scheme,
             bucket,
             key: decoded_key,
+            region,
+            namespace,
+            transport,
         })
     }
 }
 
-/// Make a message for the need fields in the object storage. The fields are the required fields
-/// for the object storage, which are different for different object storages. The macro takes a
-/// variable and a list of fields, and returns a message that indicates which fields are needed.
-macro_rules! make_need_fields_message {
-    ($var:ident {$($field:ident),*}) => {{
-            let mut need_fields: Vec<&'static str> = vec![];
+/// Is connection error returns whether the error is the connection-level failure without the
+/// response, such as the refused connection and the timeout, which fails over to the next
+/// endpoint. The errors with the response, such as 5xx and throttling, are not failed over.
+fn is_connection_error(err: &ClientError) -> bool {
+    err.is_retryable()
+        && !matches!(
+            err,
+            ClientError::BackendError(_) | ClientError::Throttled { .. }
+        )
+}
 
-            $(
-                if $var.$field.is_none() {
-                    need_fields.push(stringify!($field));
-                }
-            )*
+/// Expand env replaces the `${VAR}` placeholders in the value with the values looked up by the
+/// name, and returns an error if the variable is not in the allowlist or is undefined.
+fn expand_env(
+    value: &str,
+    allowlist: &[String],
+    lookup: impl Fn(&str) -> Option<String>,
+) -> ClientResult<String> {
+    let mut expanded = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        expanded.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find('}') else {
+            return Err(ClientError::BackendError(Box::new(BackendError {
+                message: format!("unterminated environment variable placeholder in {}", value),
+                status_code: None,
+                header: None,
+            })));
+        };
 
-            format!("need {}", need_fields.join(", "))
-       }};
-}
+        let name = &rest[start + 2..start + end];
+        if !allowlist.iter().any(|allowed| allowed == name) {
+            error!("environment variable {} is not allowed in {}", name, value);
+            return Err(ClientError::BackendError(Box::new(BackendError {
+                message: format!("environment variable {} is not allowed in {}", name, value),
+                status_code: None,
+                header: None,
+            })));
+        }
 
-/// ObjectStorage is a struct that implements the backend trait.
-pub struct ObjectStorage {
-    /// Scheme is the scheme of the object storage.
-    scheme: Scheme,
+        let env = lookup(name).ok_or_else(|| {
+            error!("environment variable {} is undefined in {}", name, value);
+            ClientError::BackendError(Box::new(BackendError {
+                message: format!("environment variable {} is undefined in {}", name, value),
+                status_code: None,
+                header: None,
+            }))
+        })?;
 
-    /// Config is the configuration of the dfdaemon.
-    config: Arc<Config>,
+        expanded.push_str(&env);
+        rest = &rest[start + end + 1..];
+    }
 
-    /// Client is the reqwest client.
-    client: reqwest::Client,
+    expanded.push_str(rest);
+    Ok(expanded)
+}
 
-    // Danger client is the reqwest dangerous client, which skips certificate verification.
-    danger_client: reqwest::Client,
+/// ServerChecksum is the checksum of the whole object declared by the object storage.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ServerChecksum {
+    /// Crc32c is the CRC32C checksum of the object.
+    Crc32c(u32),
+
+    /// Sha256 is the SHA256 checksum of the object.
+    Sha256(Vec<u8>),
 }
 
-/// ObjectStorage implements the ObjectStorage trait.
-impl ObjectStorage {
-    /// Returns ObjectStorage that implements the Backend trait.
-    pub fn new(scheme: Scheme, config: Arc<Config>) -> ClientResult<ObjectStorage> {
-        // Initialize the reqwest client.
-        let client = reqwest::Client::builder()
-            .no_gzip()
-            .no_brotli()
-            .no_zstd()
-            .no_deflate()
-            .hickory_dns(config.backend.enable_hickory_dns)
-            .pool_max_idle_per_host(POOL_MAX_IDLE_PER_HOST)
-            .tcp_keepalive(KEEP_ALIVE_INTERVAL)
-            .tcp_nodelay(true)
-            .http2_adaptive_window(true)
-            .http2_initial_stream_window_size(Some(HTTP2_STREAM_WINDOW_SIZE))
-            .http2_initial_connection_window_size(Some(HTTP2_CONNECTION_WINDOW_SIZE))
-            .http2_keep_alive_timeout(HTTP2_KEEP_ALIVE_TIMEOUT)
-            .http2_keep_alive_interval(HTTP2_KEEP_ALIVE_INTERVAL)
-            .http2_keep_alive_while_idle(true)
-            .build()?;
+/// ServerChecksum implements the ServerChecksum.
+impl ServerChecksum {
+    /// From header parses the checksum of the whole object from the response headers of S3
+    /// (`x-amz-checksum-sha256` and `x-amz-checksum-crc32c`) and GCS (`x-goog-hash`), the
+    /// values are base64 encoded. The SHA256 checksum is preferred if both are declared. The
+    /// composite checksum of the multipart object is the checksum of the checksums of the
+    /// parts, which can not be verified by the content, so it is ignored.
+    fn from_header(header: &reqwest::header::HeaderMap) -> Option<ServerChecksum> {
+        let value = |name: &str| header.get(name).and_then(|value| value.to_str().ok());
+        if value("x-amz-checksum-type").is_some_and(|value| value.eq_ignore_ascii_case("COMPOSITE"))
+        {
+            return None;
+        }
 
-        // Initialize the reqwest dangerous client.
-        let client_config_builder = rustls::ClientConfig::builder()
-            .dangerous()
-            .with_custom_certificate_verifier(NoVerifier::new())
-            .with_no_client_auth();
+        // The composite checksum is suffixed by the number of the parts, such as `...-3`.
+        let decode = |value: &str| {
+            if value.contains('-') {
+                return None;
+            }
 
-        let danger_client = reqwest::Client::builder()
-            .no_gzip()
-            .no_brotli()
-            .no_zstd()
-            .no_deflate()
-            .hickory_dns(config.backend.enable_hickory_dns)
-            .use_preconfigured_tls(client_config_builder)
-            .pool_max_idle_per_host(POOL_MAX_IDLE_PER_HOST)
-            .tcp_keepalive(KEEP_ALIVE_INTERVAL)
-            .tcp_nodelay(true)
-            .http2_adaptive_window(true)
-            .http2_initial_stream_window_size(Some(HTTP2_STREAM_WINDOW_SIZE))
-            .http2_initial_connection_window_size(Some(HTTP2_CONNECTION_WINDOW_SIZE))
-            .http2_keep_alive_timeout(HTTP2_KEEP_ALIVE_TIMEOUT)
-            .http2_keep_alive_interval(HTTP2_KEEP_ALIVE_INTERVAL)
-            .http2_keep_alive_while_idle(true)
-            .build()?;
+            BASE64_STANDARD.decode(value.trim()).ok()
+        };
 
-        Ok(Self {
-            scheme,
-            config,
-            client,
-            danger_client,
-        })
-    }
+        if let Some(checksum) = value("x-amz-checksum-sha256").and_then(decode) {
+            return Some(ServerChecksum::Sha256(checksum));
+        }
 
-    /// Operator initializes the operator with the parsed URL and object storage.
-    pub fn operator(
-        &self,
-        parsed_url: &ParsedURL,
-        object_storage: Option<common::v2::ObjectStorage>,
-        timeout: Duration,
-    ) -> ClientResult<Operator> {
-        // If download backend is object storage, object_storage parameter is required.
-        let Some(object_storage) = object_storage else {
-            return Err(ClientError::BackendError(Box::new(BackendError {
-                message: format!("{} need object_storage parameter", self.scheme),
-                status_code: None,
-                header: None,
-            })));
-        };
+        // GCS declares the checksums in the single header, such as `crc32c=...,md5=...`.
+        let crc32c = value("x-amz-checksum-crc32c").or_else(|| {
+            header
+                .get_all("x-goog-hash")
+                .iter()
+                .filter_map(|value| value.to_str().ok())
+                .flat_map(|value| value.split(','))
+                .find_map(|value| value.trim().strip_prefix("crc32c="))
+        })?;
 
-        match self.scheme {
-            Scheme::S3 => self.s3_operator(parsed_url, object_storage, timeout),
-            Scheme::GCS => self.gcs_operator(parsed_url, object_storage, timeout),
-            Scheme::ABS => self.abs_operator(parsed_url, object_storage, timeout),
-            Scheme::OSS => self.oss_operator(parsed_url, object_storage, timeout),
-            Scheme::OBS => self.obs_operator(parsed_url, object_storage, timeout),
-            Scheme::COS => self.cos_operator(parsed_url, object_storage, timeout),
-        }
+        let checksum: [u8; 4] = decode(crc32c)?.try_into().ok()?;
+        Some(ServerChecksum::Crc32c(u32::from_be_bytes(checksum)))
     }
+}
 
-    /// S3 operator initializes the S3 operator with the parsed URL and object storage.
-    pub fn s3_operator(
-        &self,
-        parsed_url: &ParsedURL,
-        object_storage: common::v2::ObjectStorage,
-        timeout: Duration,
-    ) -> ClientResult<Operator> {
-        // S3 requires the access key id and the secret access key.
-        let (Some(access_key_id), Some(access_key_secret), Some(region)) = (
-            &object_storage.access_key_id,
-            &object_storage.access_key_secret,
-            &object_storage.region,
-        ) else {
-            return Err(ClientError::BackendError(Box::new(BackendError {
-                message: format!(
-                    "{} {}",
-                    self.scheme,
-                    make_need_fields_message!(object_storage {
-                        access_key_id,
-                        access_key_secret,
-                        region
-                    })
-                ),
-                status_code: None,
-                header: None,
-            })));
-        };
-
-        // Initialize the S3 operator with the object storage.
-        let mut builder = opendal::services::S3::default();
-        builder = builder
-            .access_key_id(access_key_id)
-            .secret_access_key(access_key_secret)
-            .bucket(&parsed_url.bucket)
-            .region(region);
+/// ChecksumHasher is the hasher of the algorithm of the server checksum.
+enum ChecksumHasher {
+    /// Crc32c is the running CRC32C checksum.
+    Crc32c(u32),
 
-        // Configure the endpoint if it is provided.
-        if let Some(endpoint) = object_storage.endpoint.as_deref() {
-            builder = builder.endpoint(endpoint);
-        }
+    /// Sha256 is the running SHA256 hasher.
+    Sha256(sha2::Sha256),
+}
 
-        // Configure the session token if it is provided.
-        if let Some(session_token) = object_storage.session_token.as_deref() {
-            builder = builder.session_token(session_token);
-        }
+/// ChecksumReader wraps the reader of the object, and verifies the content against the server
+/// checksum at EOF, which returns an error if the checksum does not match. It is distinct from
+/// the digest of the request, which is supplied by the caller.
+struct ChecksumReader<R> {
+    /// Reader is the reader of the object.
+    reader: R,
 
-        // Choose the http client using dangerous client or not by insecure_skip_verify.
-        let http_client = match object_storage.insecure_skip_verify {
-            Some(true) => self.danger_client.clone(),
-            _ => self.client.clone(),
-        };
+    /// Expected is the checksum declared by the object storage.
+    expected: ServerChecksum,
 
-        Ok(Operator::new(builder)?
-            .finish()
-            .layer(TimeoutLayer::new().with_timeout(timeout))
-            .layer(HttpClientLayer::new(HttpClient::with(http_client))))
-    }
+    /// Hasher is the hasher of the content read, it is None once the content is verified.
+    hasher: Option<ChecksumHasher>,
+}
 
-    /// GCS operator initializes the GCS operator with the parsed URL and object storage.
-    pub fn gcs_operator(
-        &self,
-        parsed_url: &ParsedURL,
-        object_storage: common::v2::ObjectStorage,
-        timeout: Duration,
-    ) -> ClientResult<Operator> {
-        // Initialize the GCS operator with the object storage.
-        let mut builder = opendal::services::Gcs::default();
-        builder = builder.bucket(&parsed_url.bucket);
+/// ChecksumReader implements the ChecksumReader.
+impl<R> ChecksumReader<R> {
+    /// New returns a new ChecksumReader.
+    fn new(reader: R, expected: ServerChecksum) -> Self {
+        let hasher = match expected {
+            ServerChecksum::Crc32c(_) => ChecksumHasher::Crc32c(0),
+            ServerChecksum::Sha256(_) => ChecksumHasher::Sha256(sha2::Sha256::new()),
+        };
 
-        // Configure the credentials using the local path to the credential file if provided.
-        // Otherwise, configure using the Application Default Credentials (ADC).
-        if let Some(credential_path) = object_storage.credential_path.as_deref() {
-            builder = builder.credential_path(credential_path);
+        Self {
+            reader,
+            expected,
+            hasher: Some(hasher),
         }
+    }
+}
 
-        // Configure the endpoint if it is provided.
-        if let Some(endpoint) = object_storage.endpoint.as_deref() {
-            builder = builder.endpoint(endpoint);
+/// ChecksumReader implements the AsyncRead trait.
+impl<R> AsyncRead for ChecksumReader<R>
+where
+    R: AsyncRead + Unpin,
+{
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        let filled = buf.filled().len();
+        ready!(Pin::new(&mut self.reader).poll_read(cx, buf))?;
+
+        let n = buf.filled().len() - filled;
+        let eof = n == 0 && buf.remaining() > 0;
+        let this = &mut *self;
+        match this.hasher.as_mut() {
+            Some(ChecksumHasher::Crc32c(crc)) => {
+                *crc = crc32c::crc32c_append(*crc, &buf.filled()[filled..])
+            }
+            Some(ChecksumHasher::Sha256(hasher)) => hasher.update(&buf.filled()[filled..]),
+            None => return Poll::Ready(Ok(())),
         }
 
-        // Configure the predefined ACL if it is provided.
-        if let Some(predefined_acl) = object_storage.predefined_acl.as_deref() {
-            builder = builder.predefined_acl(predefined_acl);
+        if !eof {
+            return Poll::Ready(Ok(()));
         }
 
-        // Choose the http client using dangerous client or not by insecure_skip_verify.
-        let http_client = match object_storage.insecure_skip_verify {
-            Some(true) => self.danger_client.clone(),
-            _ => self.client.clone(),
+        let actual = match this.hasher.take() {
+            Some(ChecksumHasher::Crc32c(crc)) => ServerChecksum::Crc32c(crc),
+            Some(ChecksumHasher::Sha256(hasher)) => {
+                ServerChecksum::Sha256(hasher.finalize().to_vec())
+            }
+            None => return Poll::Ready(Ok(())),
         };
 
-        Ok(Operator::new(builder)?
-            .finish()
-            .layer(TimeoutLayer::new().with_timeout(timeout))
-            .layer(HttpClientLayer::new(HttpClient::with(http_client))))
+        if actual != this.expected {
+            return Poll::Ready(Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!(
+                    "checksum mismatch, expected {:?}, actual {:?}",
+                    this.expected, actual
+                ),
+            )));
+        }
+
+        Poll::Ready(Ok(()))
     }
+}
 
-    /// ABS operator initializes the ABS operator with the parsed URL and object storage.
-    pub fn abs_operator(
-        &self,
-        parsed_url: &ParsedURL,
-        object_storage: common::v2::ObjectStorage,
-        timeout: Duration,
-    ) -> ClientResult<Operator> {
-        // ABS requires the account name and the account key.
-        let (Some(access_key_id), Some(access_key_secret), Some(endpoint)) = (
-            &object_storage.access_key_id,
-            &object_storage.access_key_secret,
-            &object_storage.endpoint,
-        ) else {
-            return Err(ClientError::BackendError(Box::new(BackendError {
-                message: format!(
-                    "{} {}",
-                    self.scheme,
-                    make_need_fields_message!(object_storage {
-                        access_key_id,
-                        access_key_secret,
-                        endpoint
-                    })
-                ),
-                status_code: None,
-                header: None,
-            })));
-        };
+/// InflightStat is the in-flight stat of the object shared by the concurrent stats.
+type InflightStat = Shared<BoxFuture<'static, Result<Metadata, Arc<opendal::Error>>>>;
 
-        // Initialize the ABS operator with the object storage.
-        let mut builder = opendal::services::Azblob::default();
-        builder = builder
-            .account_name(access_key_id)
-            .account_key(access_key_secret)
-            .container(&parsed_url.bucket)
-            .endpoint(endpoint);
+/// InflightStatEntry is the in-flight stat with the number of its waiters.
+struct InflightStatEntry {
+    /// Stat is the in-flight stat.
+    stat: InflightStat,
 
-        // Choose the http client using dangerous client or not by insecure_skip_verify.
-        let http_client = match object_storage.insecure_skip_verify {
-            Some(true) => self.danger_client.clone(),
-            _ => self.client.clone(),
-        };
+    /// Waiters is the number of the stats waiting for the in-flight stat.
+    waiters: usize,
+}
 
-        Ok(Operator::new(builder)?
-            .finish()
-            .layer(TimeoutLayer::new().with_timeout(timeout))
-            .layer(HttpClientLayer::new(HttpClient::with(http_client))))
-    }
+/// InflightStatGuard leaves the in-flight stat when the waiter is dropped. The entry is removed
+/// once the stat completes, so the later stats issue a new request, or once the last waiter
+/// leaves before the stat completes, such as the cancelled or timed out stats, so the
+/// abandoned stat is not left in the map.
+struct InflightStatGuard<'a> {
+    /// Inflight stats are the in-flight stats of the backend.
+    inflight_stats: &'a Mutex<HashMap<String, InflightStatEntry>>,
 
-    /// OSS operator initializes the OSS operator with the parsed URL and object storage.
-    pub fn oss_operator(
-        &self,
-        parsed_url: &ParsedURL,
-        object_storage: common::v2::ObjectStorage,
-        timeout: Duration,
-    ) -> ClientResult<Operator> {
-        // OSS requires the access key id, access key secret, and endpoint.
-        let (Some(access_key_id), Some(access_key_secret), Some(endpoint)) = (
-            &object_storage.access_key_id,
-            &object_storage.access_key_secret,
-            &object_storage.endpoint,
-        ) else {
-            return Err(ClientError::BackendError(Box::new(BackendError {
-                message: format!(
-                    "{} {}",
-                    self.scheme,
-                    make_need_fields_message!(object_storage {
-                        access_key_id,
-                        access_key_secret,
-                        endpoint
-                    })
-                ),
-                status_code: None,
-                header: None,
-            })));
-        };
+    /// Flight key is the key of the in-flight stat.
+    flight_key: String,
 
-        // Initialize the OSS operator with the object storage.
-        let mut builder = opendal::services::Oss::default();
-        builder = if let Some(security_token) = &object_storage.security_token {
-            builder
-                .access_key_id(access_key_id)
-                .access_key_secret(access_key_secret)
-                .endpoint(endpoint)
-                .root("/")
-                .bucket(&parsed_url.bucket)
-                .security_token(security_token)
-        } else {
-            builder
-                .access_key_id(access_key_id)
-                .access_key_secret(access_key_secret)
-                .endpoint(endpoint)
-                .root("/")
-                .bucket(&parsed_url.bucket)
-        };
+    /// Stat identifies the in-flight stat joined by the waiter, it is never polled.
+    stat: InflightStat,
 
-        // Choose the http client using dangerous client or not by insecure_skip_verify.
-        let http_client = match object_storage.insecure_skip_verify {
-            Some(true) => self.danger_client.clone(),
-            _ => self.client.clone(),
+    /// Completed is true if the in-flight stat is completed.
+    completed: bool,
+}
+
+/// InflightStatGuard implements the Drop trait.
+impl Drop for InflightStatGuard<'_> {
+    fn drop(&mut self) {
+        let mut inflight_stats = self.inflight_stats.lock();
+        let Some(entry) = inflight_stats
+            .get_mut(&self.flight_key)
+            .filter(|entry| entry.stat.ptr_eq(&self.stat))
+        else {
+            return;
         };
 
-        Ok(Operator::new(builder)?
-            .finish()
-            .layer(TimeoutLayer::new().with_timeout(timeout))
-            .layer(HttpClientLayer::new(HttpClient::with(http_client))))
+        entry.waiters -= 1;
+        if self.completed || entry.waiters == 0 {
+            inflight_stats.remove(&self.flight_key);
+        }
     }
+}
 
-    /// OBS operator initializes the OBS operator with the parsed URL and object storage.
-    pub fn obs_operator(
-        &self,
-        parsed_url: &ParsedURL,
-        object_storage: common::v2::ObjectStorage,
-        timeout: Duration,
-    ) -> ClientResult<Operator> {
-        // OBS requires the endpoint, access key id, and access key secret.
-        let (Some(access_key_id), Some(access_key_secret), Some(endpoint)) = (
-            &object_storage.access_key_id,
-            &object_storage.access_key_secret,
-            &object_storage.endpoint,
-        ) else {
-            return Err(ClientError::BackendError(Box::new(BackendError {
-                message: format!(
-                    "{} {}",
-                    self.scheme,
-                    make_need_fields_message!(object_storage {
-                        access_key_id,
-                        access_key_secret,
-                        endpoint
-                    })
-                ),
-                status_code: None,
-                header: None,
-            })));
-        };
+/// OperatorLayer wraps the operator built by the object storage backend, such as adding the
+/// opendal layers for logging, tracing and metrics.
+pub type OperatorLayer = Arc<dyn Fn(Operator) -> Operator + Send + Sync>;
 
-        // Initialize the OBS operator with the object storage.
-        let mut builder = opendal::services::Obs::default();
-        builder = builder
-            .access_key_id(access_key_id)
-            .secret_access_key(access_key_secret)
-            .endpoint(endpoint)
-            .bucket(&parsed_url.bucket);
+/// ObjectStorage is a struct that implements the backend trait.
+pub struct ObjectStorage {
+    /// Scheme is the scheme of the object storage.
+    scheme: Scheme,
 
-        // Choose the http client using dangerous client or not by insecure_skip_verify.
-        let http_client = match object_storage.insecure_skip_verify {
-            Some(true) => self.danger_client.clone(),
-            _ => self.client.clone(),
-        };
+    /// Config is the configuration of the dfdaemon.
+    config: Arc<Config>,
 
-        Ok(Operator::new(builder)?
-            .finish()
-            .layer(TimeoutLayer::new().with_timeout(timeout))
-            .layer(HttpClientLayer::new(HttpClient::with(http_client))))
-    }
+    /// Client is the reqwest client.
+    client: reqwest::Client,
 
-    /// COS operator initializes the COS operator with the parsed URL and object storage.
-    pub fn cos_operator(
-        &self,
-        parsed_url: &ParsedURL,
-        object_storage: common::v2::ObjectStorage,
-        timeout: Duration,
-    ) -> ClientResult<Operator> {
-        // COS requires the access key id, the access key secret, and the endpoint.
-        let (Some(access_key_id), Some(access_key_secret), Some(endpoint)) = (
-            &object_storage.access_key_id,
-            &object_storage.access_key_secret,
-            &object_storage.endpoint,
-        ) else {
-            return Err(ClientError::BackendError(Box::new(BackendError {
-                message: format!(
-                    "{} {}",
-                    self.scheme,
-                    make_need_fields_message!(object_storage {
-                        access_key_id,
-                        access_key_secret,
-                        endpoint
-                    })
-                ),
-                status_code: None,
-                header: None,
-            })));
-        };
+    // Danger client is the reqwest dangerous client, which skips certificate verification.
+    danger_client: reqwest::Client,
 
-        // Initialize the COS operator with the object storage.
-        let mut builder = opendal::services::Cos::default();
-        builder = builder
-            .secret_id(access_key_id)
-            .secret_key(access_key_secret)
-            .endpoint(endpoint)
-            .bucket(&parsed_url.bucket);
+    /// Stat cache stores the stat of the object keyed by the URL with TTL (LRU eviction), it is
+    /// None if the stat cache is disabled.
+    stat_cache: Option<Arc<Mutex<LruCache<String, StatCacheEntry>>>>,
 
-        // Choose the http client using dangerous client or not by insecure_skip_verify.
-        let http_client = match object_storage.insecure_skip_verify {
-            Some(true) => self.danger_client.clone(),
-            _ => self.client.clone(),
-        };
+    /// Operators stores the built operators keyed by the fingerprint of the operator options
+    /// (LRU eviction), so the requests to the same bucket with the same credentials reuse the
+    /// same operator.
+    operators: Mutex<LruCache<String, Operator>>,
 
-        Ok(Operator::new(builder)?
-            .finish()
-            .layer(TimeoutLayer::new().with_timeout(timeout))
-            .layer(HttpClientLayer::new(HttpClient::with(http_client))))
-    }
+    /// Operator layers are applied to the built operators in the order of registration, they
+    /// can be shared by the object storage backends of the backend factory.
+    operator_layers: Arc<RwLock<Vec<OperatorLayer>>>,
+
+    /// Inflight stats store the in-flight stats keyed by the object, the concurrent stats of
+    /// the same object share the in-flight stat instead of issuing their own requests.
+    inflight_stats: Mutex<HashMap<String, InflightStatEntry>>,
+
+    /// Profile credentials cache the S3 credentials of the configured profile with the modified
+    /// time of the credentials file, which are used when the request has no inline credentials.
+    /// It is None until the credentials are loaded by the first request.
+    profile_credentials: Mutex<Option<(SystemTime, ProfileCredentials)>>,
+
+    /// Unhealthy endpoints store the endpoints failed by the connection-level failures and the
+    /// instant until which they are tried last. The endpoints are provided by the requests, so
+    /// the cache is bounded (LRU eviction) and the recovered endpoints are removed.
+    unhealthy_endpoints: Mutex<LruCache<String, Instant>>,
 }
 
-/// Backend implements the Backend trait.
-#[async_trait]
-impl crate::Backend for ObjectStorage {
-    /// Scheme returns the scheme of the object storage.
-    fn scheme(&self) -> String {
-        self.scheme.to_string()
-    }
+/// ProfileCredentials are the credentials of the profile in the shared credentials file, such
+/// as `~/.aws/credentials`.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct ProfileCredentials {
+    /// Access key id is the `aws_access_key_id` of the profile.
+    access_key_id: Option<String>,
 
-    /// Stat the metadata from the backend.
-    #[instrument(skip_all)]
-    async fn stat(&self, request: StatRequest) -> ClientResult<StatResponse> {
-        debug!(
-            "stat request {} {}: {:?}",
-            request.task_id, request.url, request.http_header
-        );
+    /// Access key secret is the `aws_secret_access_key` of the profile.
+    access_key_secret: Option<String>,
 
-        // Parse the URL and convert it to a ParsedURL for create the ObjectStorage operator.
-        let url: Url = request
-            .url
-            .parse()
-            .map_err(|_| ClientError::InvalidURI(request.url.clone()))?;
+    /// Session token is the `aws_session_token` of the profile.
+    session_token: Option<String>,
 
-        let parsed_url: ParsedURL = url.try_into().inspect_err(|err| {
-            error!(
-                "parse stat request url failed {} {}: {}",
-                request.task_id, request.url, err
-            );
-        })?;
+    /// Region is the `region` of the profile.
+    region: Option<String>,
+}
 
-        // Initialize the operator with the parsed URL, object storage, and timeout.
-        let operator = self.operator(&parsed_url, request.object_storage, request.timeout)?;
+/// ProfileCredentials implements the ProfileCredentials.
+impl ProfileCredentials {
+    /// Load loads the credentials of the profile from the shared credentials file in the INI
+    /// format, and returns an error if the profile is not found.
+    fn load(path: &Path, profile: &str) -> ClientResult<ProfileCredentials> {
+        let content = std::fs::read_to_string(path).inspect_err(|err| {
+            error!("read credentials file {} failed: {}", path.display(), err);
+        })?;
 
-        // Get the entries if url point to a directory.
-        let entries = if parsed_url.is_dir() {
-            operator
-                .list_with(&parsed_url.key)
-                .recursive(true)
-                .await // Do the list op here.
-                .map_err(|err| {
-                    error!(
-                        "list request failed {} {}: {}",
-                        request.task_id, request.url, err
-                    );
+        let mut credentials = None;
+        for line in content.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+                continue;
+            }
+
+            if let Some(section) = line
+                .strip_prefix('[')
+                .and_then(|line| line.strip_suffix(']'))
+            {
+                // Stop at the next section after the profile.
+                if credentials.is_some() {
+                    break;
+                }
 
-                    ClientError::BackendError(Box::new(BackendError {
-                        message: err.to_string(),
-                        status_code: None,
-                        header: None,
-                    }))
-                })?
-                .into_iter()
-                .map(|entry| {
-                    let metadata = entry.metadata();
-                    DirEntry {
-                        url: parsed_url.make_url_by_entry_path(entry.path()).to_string(),
-                        content_length: metadata.content_length() as usize,
-                        is_dir: metadata.is_dir(),
-                    }
-                })
-                .collect()
-        } else {
-            Vec::new()
-        };
+                if section.trim() == profile {
+                    credentials = Some(ProfileCredentials::default());
+                }
 
-        // Stat the object to get the response from the ObjectStorage.
-        let response = operator.stat_with(&parsed_url.key).await.map_err(|err| {
-            error!(
-                "stat request failed {} {}: {}",
-                request.task_id, request.url, err
-            );
+                continue;
+            }
+
+            let (Some(credentials), Some((key, value))) =
+                (credentials.as_mut(), line.split_once('='))
+            else {
+                continue;
+            };
+
+            let value = Some(value.trim().to_string());
+            match key.trim() {
+                "aws_access_key_id" => credentials.access_key_id = value,
+                "aws_secret_access_key" => credentials.access_key_secret = value,
+                "aws_session_token" => credentials.session_token = value,
+                "region" => credentials.region = value,
+                _ => {}
+            }
+        }
 
+        credentials.ok_or_else(|| {
+            error!("profile {} not found in {}", profile, path.display());
             ClientError::BackendError(Box::new(BackendError {
-                message: err.to_string(),
+                message: format!("profile {} not found in {}", profile, path.display()),
                 status_code: None,
                 header: None,
             }))
-        })?;
+        })
+    }
+}
 
-        debug!(
-            "stat response {} {}: {}",
-            request.task_id,
-            request.url,
-            response.content_length()
-        );
+/// StatCacheEntry is the cached stat of the object, which is used by the ranged gets of the
+/// same object.
+#[derive(Debug, Clone)]
+struct StatCacheEntry {
+    /// ETag is the ETag of the object when it is cached.
+    etag: Option<String>,
 
-        Ok(StatResponse {
-            success: true,
-            content_length: Some(response.content_length()),
-            http_header: None,
-            http_status_code: None,
-            error_message: None,
-            entries,
-        })
+    /// Content length is the content length of the object.
+    content_length: u64,
+
+    /// Cached at is the time when the stat is cached.
+    cached_at: Instant,
+}
+
+/// ListPage is a page of the listed entries of the directory.
+struct ListPage {
+    /// Entries are the paths and the metadata of the listed entries of the page.
+    entries: Vec<(String, Metadata)>,
+
+    /// Next continuation token is the path of the last entry of the page, and the next page
+    /// starts after it. It is None if there is no next page.
+    next_continuation_token: Option<String>,
+
+    /// Error is the error when the listing fails midway, and the entries are the partial
+    /// result listed before the failure.
+    error: Option<opendal::Error>,
+}
+
+/// InterruptedRetryStream wraps the stream of the object content, and polls the stream again in
+/// place when it returns the Interrupted error, which is explicitly retryable. The other errors,
+/// such as NotFound and PermissionDenied, are terminal and returned immediately. It differs from
+/// resuming the download by the offset, because the same stream continues the read. If the
+/// stream ends after the retried error, the error is returned instead of the end of the stream,
+/// so the truncated content is never mistaken for the complete content. Each retry withdraws the
+/// retry budget of the request, and the error is returned once the budget is exhausted.
+struct InterruptedRetryStream<S> {
+    /// Stream is the stream of the object content.
+    stream: S,
+
+    /// Retry budget is the retry budget shared by the requests of the download.
+    retry_budget: Option<Arc<RetryBudget>>,
+
+    /// Retries is the number of the consecutive retries, it is reset once a chunk is read.
+    retries: usize,
+
+    /// Last error is the error of the last retry, it is cleared once a chunk is read.
+    last_error: Option<std::io::Error>,
+}
+
+/// InterruptedRetryStream implements the InterruptedRetryStream.
+impl<S> InterruptedRetryStream<S> {
+    /// MAX_INTERRUPTED_RETRIES is the max number of the consecutive retries of the interrupted
+    /// reads.
+    const MAX_INTERRUPTED_RETRIES: usize = 3;
+
+    /// New returns a new InterruptedRetryStream.
+    fn new(stream: S, retry_budget: Option<Arc<RetryBudget>>) -> Self {
+        Self {
+            stream,
+            retry_budget,
+            retries: 0,
+            last_error: None,
+        }
     }
+}
 
-    /// Get the content from the backend.
-    #[instrument(skip_all)]
-    async fn get(&self, request: GetRequest) -> ClientResult<GetResponse<Body>> {
-        debug!(
-            "get request {} {}: {:?}",
-            request.piece_id, request.url, request.http_header
-        );
+/// InterruptedRetryStream implements the Stream trait.
+impl<S> Stream for InterruptedRetryStream<S>
+where
+    S: Stream<Item = std::io::Result<Bytes>> + Unpin,
+{
+    type Item = std::io::Result<Bytes>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            match ready!(Pin::new(&mut self.stream).poll_next(cx)) {
+                Some(Err(err))
+                    if err.kind() == std::io::ErrorKind::Interrupted
+                        && self.retries < Self::MAX_INTERRUPTED_RETRIES
+                        && self
+                            .retry_budget
+                            .as_ref()
+                            .is_none_or(|retry_budget| retry_budget.try_withdraw()) =>
+                {
+                    self.retries += 1;
+                    debug!("retry interrupted read {}: {}", self.retries, err);
+                    self.last_error = Some(err);
+                }
+                Some(Ok(chunk)) => {
+                    self.retries = 0;
+                    self.last_error = None;
+                    return Poll::Ready(Some(Ok(chunk)));
+                }
+                None => return Poll::Ready(self.last_error.take().map(Err)),
+                item => return Poll::Ready(item),
+            }
+        }
+    }
+}
 
-        // Parse the URL and convert it to a ParsedURL for create the ObjectStorage operator.
-        let url: Url = request
-            .url
-            .parse()
-            .map_err(|_| ClientError::InvalidURI(request.url.clone()))?;
+/// AdaptiveChunkStream reads the range of the object by the chunk requests, and adapts the chunk
+/// size by the duration of the reads. The chunk size starts with a modest size, doubles while the
+/// chunks are read fast, and halves on the slow or interrupted reads, which balances the
+/// per-request overhead on the fast links and the memory on the slow links.
+struct AdaptiveChunkStream {
+    /// Reader is the reader of the object.
+    reader: opendal::Reader,
 
-        let parsed_url: ParsedURL = url.try_into().inspect_err(|err| {
-            error!(
-                "parse get request url failed {} {}: {}",
-                request.piece_id, request.url, err
-            );
-        })?;
+    /// Offset is the offset of the next chunk.
+    offset: u64,
 
-        // Initialize the operator with the parsed URL, object storage, and timeout.
-        let operator_reader = self
-            .operator(&parsed_url, request.object_storage, request.timeout)?
-            .reader(&parsed_url.key)
-            .await
-            .map_err(|err| {
-                error!(
-                    "get request failed {} {}: {}",
-                    request.piece_id, request.url, err
-                );
+    /// End is the exclusive end of the range to read.
+    end: u64,
 
-                ClientError::BackendError(Box::new(BackendError {
-                    message: err.to_string(),
-                    status_code: None,
-                    header: None,
-                }))
-            })?;
+    /// Chunk size is the size of the next chunk.
+    chunk_size: u64,
 
-        let stream = match request.range {
-            Some(range) => operator_reader
-                .into_bytes_stream(range.start..range.start + range.length)
-                .await
-                .map_err(|err| {
-                    error!(
-                        "get request failed {} {}: {}",
-                        request.piece_id, request.url, err
-                    );
+    /// Read is the in-flight read of the chunk and its duration.
+    read: Option<BoxFuture<'static, (opendal::Result<opendal::Buffer>, Duration)>>,
+}
 
-                    ClientError::BackendError(Box::new(BackendError {
-                        message: err.to_string(),
-                        status_code: None,
-                        header: None,
-                    }))
-                })?,
-            None => operator_reader.into_bytes_stream(..).await.map_err(|err| {
-                error!(
-                    "get request failed {} {}: {}",
-                    request.piece_id, request.url, err
-                );
+/// AdaptiveChunkStream implements the AdaptiveChunkStream.
+impl AdaptiveChunkStream {
+    /// INITIAL_CHUNK_SIZE is the size of the first chunk.
+    const INITIAL_CHUNK_SIZE: u64 = 1024 * 1024;
 
-                ClientError::BackendError(Box::new(BackendError {
-                    message: err.to_string(),
-                    status_code: None,
-                    header: None,
-                }))
-            })?,
+    /// MIN_CHUNK_SIZE is the minimum size of the chunk.
+    const MIN_CHUNK_SIZE: u64 = 256 * 1024;
+
+    /// MAX_CHUNK_SIZE is the maximum size of the chunk.
+    const MAX_CHUNK_SIZE: u64 = 64 * 1024 * 1024;
+
+    /// GROW_THRESHOLD is the duration of the read below which the chunk size doubles.
+    const GROW_THRESHOLD: Duration = Duration::from_millis(500);
+
+    /// SHRINK_THRESHOLD is the duration of the read above which the chunk size halves.
+    const SHRINK_THRESHOLD: Duration = Duration::from_secs(5);
+
+    /// New returns a new AdaptiveChunkStream reading the range of the object.
+    fn new(reader: opendal::Reader, range: std::ops::Range<u64>) -> Self {
+        Self {
+            reader,
+            offset: range.start,
+            end: range.end,
+            chunk_size: Self::INITIAL_CHUNK_SIZE,
+            read: None,
+        }
+    }
+
+    /// Adapt adapts the chunk size by the duration of the last read.
+    fn adapt(&mut self, elapsed: Duration) {
+        if elapsed < Self::GROW_THRESHOLD {
+            self.chunk_size = (self.chunk_size * 2).min(Self::MAX_CHUNK_SIZE);
+        } else if elapsed > Self::SHRINK_THRESHOLD {
+            self.chunk_size = (self.chunk_size / 2).max(Self::MIN_CHUNK_SIZE);
+        }
+    }
+}
+
+/// AdaptiveChunkStream implements the Stream trait. The failed chunk is read again by the next
+/// poll, so the interrupted reads are retried by the InterruptedRetryStream.
+impl Stream for AdaptiveChunkStream {
+    type Item = std::io::Result<Bytes>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.read.is_none() {
+            if self.offset >= self.end {
+                return Poll::Ready(None);
+            }
+
+            let range = self.offset..self.end.min(self.offset + self.chunk_size);
+            let reader = self.reader.clone();
+            self.read = Some(Box::pin(async move {
+                let started_at = Instant::now();
+                let result = reader.read(range).await;
+                (result, started_at.elapsed())
+            }));
+        }
+
+        let Some(read) = self.read.as_mut() else {
+            return Poll::Ready(None);
         };
 
-        Ok(crate::GetResponse {
-            success: true,
-            http_header: None,
-            http_status_code: Some(reqwest::StatusCode::OK),
-            reader: Box::new(StreamReader::new(stream)),
-            error_message: None,
+        let (result, elapsed) = ready!(read.poll_unpin(cx));
+        self.read = None;
+        match result {
+            Ok(buffer) if buffer.is_empty() => Poll::Ready(Some(Err(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                format!("object ended at {} before {}", self.offset, self.end),
+            )))),
+            Ok(buffer) => {
+                self.offset += buffer.len() as u64;
+                self.adapt(elapsed);
+                Poll::Ready(Some(Ok(buffer.to_bytes())))
+            }
+            Err(err) => {
+                // The stalled read shrinks the chunk size of the retry.
+                self.chunk_size = (self.chunk_size / 2).max(Self::MIN_CHUNK_SIZE);
+                Poll::Ready(Some(Err(err.into())))
+            }
+        }
+    }
+}
+
+/// ObjectStorage implements the ObjectStorage trait.
+impl ObjectStorage {
+    /// DEFAULT_STAT_CACHE_CAPACITY is the default capacity of the stat cache.
+    const DEFAULT_STAT_CACHE_CAPACITY: usize = 1000;
+
+    /// DEFAULT_OPERATOR_CACHE_CAPACITY is the default capacity of the operator cache.
+    const DEFAULT_OPERATOR_CACHE_CAPACITY: usize = 128;
+
+    /// ENDPOINT_UNHEALTHY_DURATION is the duration for which the endpoint failed by the
+    /// connection-level failure is tried last.
+    const ENDPOINT_UNHEALTHY_DURATION: Duration = Duration::from_secs(30);
+
+    /// UNHEALTHY_ENDPOINT_CACHE_CAPACITY is the capacity of the unhealthy endpoint cache.
+    const UNHEALTHY_ENDPOINT_CACHE_CAPACITY: usize = 256;
+
+    /// PRESIGN_STAT_EXPIRE is the expiration of the presigned stat request to get the storage
+    /// class of the object.
+    const PRESIGN_STAT_EXPIRE: Duration = Duration::from_secs(60);
+
+    /// PRESIGN_GET_EXPIRE is the expiration of the presigned get request of the whole object
+    /// verified by the checksum declared by the object storage.
+    const PRESIGN_GET_EXPIRE: Duration = Duration::from_secs(60);
+
+    /// STORAGE_CLASS_HEADERS are the response headers of the storage class of the object.
+    const STORAGE_CLASS_HEADERS: [&str; 2] = ["x-amz-storage-class", "x-goog-storage-class"];
+
+    /// S3_CANNED_ACLS are the canned ACLs of the S3 objects.
+    const S3_CANNED_ACLS: [&str; 7] = [
+        "private",
+        "public-read",
+        "public-read-write",
+        "authenticated-read",
+        "aws-exec-read",
+        "bucket-owner-read",
+        "bucket-owner-full-control",
+    ];
+
+    /// GCS_PREDEFINED_ACLS are the predefined ACLs of the GCS objects.
+    const GCS_PREDEFINED_ACLS: [&str; 6] = [
+        "authenticatedRead",
+        "bucketOwnerFullControl",
+        "bucketOwnerRead",
+        "private",
+        "projectPrivate",
+        "publicRead",
+    ];
+
+    /// OSS_CANNED_ACLS are the canned ACLs of the OSS objects, the `default` inherits the ACL of
+    /// the bucket.
+    const OSS_CANNED_ACLS: [&str; 4] = ["default", "private", "public-read", "public-read-write"];
+
+    /// DEFAULT_PREFETCH_CHUNK_SIZE is the default size of the chunks prefetched by the reader if
+    /// the read buffer size is not provided.
+    const DEFAULT_PREFETCH_CHUNK_SIZE: usize = 4 * 1024 * 1024;
+
+    /// SNIFF_CONTENT_TYPE_LENGTH is the length of the head of the object read to sniff the
+    /// content type, which covers the magic bytes of the common file types.
+    const SNIFF_CONTENT_TYPE_LENGTH: u64 = 4096;
+
+    /// DEFAULT_VALIDATE_CREDENTIALS_TIMEOUT is the default timeout for validating credentials.
+    const DEFAULT_VALIDATE_CREDENTIALS_TIMEOUT: Duration = Duration::from_secs(30);
+
+    /// DEFAULT_WARM_UP_TIMEOUT is the default timeout for warming up the connections.
+    const DEFAULT_WARM_UP_TIMEOUT: Duration = Duration::from_secs(30);
+
+    /// Returns ObjectStorage that implements the Backend trait.
+    pub fn new(scheme: Scheme, config: Arc<Config>) -> ClientResult<ObjectStorage> {
+        let pool_max_idle_per_host = config
+            .backend
+            .pool_max_idle_per_host
+            .unwrap_or(POOL_MAX_IDLE_PER_HOST);
+
+        // Initialize the reqwest client.
+        let mut client_builder = reqwest::Client::builder()
+            .no_gzip()
+            .no_brotli()
+            .no_zstd()
+            .no_deflate()
+            .hickory_dns(config.backend.enable_hickory_dns)
+            .pool_idle_timeout(config.backend.pool_idle_timeout)
+            .pool_max_idle_per_host(pool_max_idle_per_host)
+            .tcp_keepalive(KEEP_ALIVE_INTERVAL)
+            .tcp_nodelay(true)
+            .http2_adaptive_window(true)
+            .http2_initial_stream_window_size(Some(HTTP2_STREAM_WINDOW_SIZE))
+            .http2_initial_connection_window_size(Some(HTTP2_CONNECTION_WINDOW_SIZE))
+            .http2_keep_alive_timeout(HTTP2_KEEP_ALIVE_TIMEOUT)
+            .http2_keep_alive_interval(HTTP2_KEEP_ALIVE_INTERVAL)
+            .http2_keep_alive_while_idle(true);
+
+        if let Some(min_tls_version) = config.backend.min_tls_version {
+            client_builder = client_builder.min_tls_version(min_tls_version.into());
+        }
+
+        // Resolve the hosts of the endpoints to the configured addresses instead of the system DNS.
+        for (host, addr) in &config.backend.dns_overrides {
+            client_builder = client_builder.resolve(host, *addr);
+        }
+
+        let client = client_builder.build()?;
+
+        // Initialize the reqwest dangerous client.
+        let mut danger_client_builder = tls_client_builder(None, config.backend.min_tls_version)
+            .no_gzip()
+            .no_brotli()
+            .no_zstd()
+            .no_deflate()
+            .hickory_dns(config.backend.enable_hickory_dns)
+            .pool_idle_timeout(config.backend.pool_idle_timeout)
+            .pool_max_idle_per_host(pool_max_idle_per_host)
+            .tcp_keepalive(KEEP_ALIVE_INTERVAL)
+            .tcp_nodelay(true)
+            .http2_adaptive_window(true)
+            .http2_initial_stream_window_size(Some(HTTP2_STREAM_WINDOW_SIZE))
+            .http2_initial_connection_window_size(Some(HTTP2_CONNECTION_WINDOW_SIZE))
+            .http2_keep_alive_timeout(HTTP2_KEEP_ALIVE_TIMEOUT)
+            .http2_keep_alive_interval(HTTP2_KEEP_ALIVE_INTERVAL)
+            .http2_keep_alive_while_idle(true);
+
+        for (host, addr) in &config.backend.dns_overrides {
+            danger_client_builder = danger_client_builder.resolve(host, *addr);
+        }
+
+        let danger_client = danger_client_builder.build()?;
+
+        // Initialize the stat cache if it is enabled.
+        let stat_cache = config.backend.enable_object_storage_stat_cache.then(|| {
+            Arc::new(Mutex::new(LruCache::new(
+                NonZeroUsize::new(Self::DEFAULT_STAT_CACHE_CAPACITY).unwrap(),
+            )))
+        });
+
+        Ok(Self {
+            scheme,
+            config,
+            client,
+            danger_client,
+            stat_cache,
+            operators: Mutex::new(LruCache::new(
+                NonZeroUsize::new(Self::DEFAULT_OPERATOR_CACHE_CAPACITY).unwrap(),
+            )),
+            operator_layers: Arc::new(RwLock::new(Vec::new())),
+            inflight_stats: Mutex::new(HashMap::new()),
+            profile_credentials: Mutex::new(None),
+            unhealthy_endpoints: Mutex::new(LruCache::new(
+                NonZeroUsize::new(Self::UNHEALTHY_ENDPOINT_CACHE_CAPACITY).unwrap(),
+            )),
         })
     }
 
-    /// Put the content to the backend.
-    #[instrument(skip_all)]
-    async fn put(&self, request: PutRequest) -> ClientResult<PutResponse> {
-        debug!("put request {:?} {}", request.path, request.url);
+    /// With operator layer registers the layer applied to the operators built after it.
+    pub fn with_operator_layer(&mut self, layer: OperatorLayer) {
+        self.operator_layers.write().push(layer);
+    }
+
+    /// With operator layers shares the registry of the operator layers, which is used by the
+    /// backend factory to apply the same layers to all object storage backends.
+    pub(crate) fn with_operator_layers(
+        mut self,
+        operator_layers: Arc<RwLock<Vec<OperatorLayer>>>,
+    ) -> Self {
+        self.operator_layers = operator_layers;
+        self
+    }
+
+    /// Parse url parses the URL to the ParsedURL, and keeps the key verbatim if the decoding of
+    /// the object storage key is disabled.
+    fn parse_url(&self, url: Url) -> ClientResult<ParsedURL> {
+        let mut parsed_url: ParsedURL = url.try_into()?;
+        if !self.config.backend.decode_object_storage_key {
+            parsed_url.key = parsed_url.raw_key().to_string();
+        }
+
+        Ok(parsed_url)
+    }
+
+    /// Failover endpoints returns the endpoints ordered by the health if the endpoint of the
+    /// object storage is the comma-separated list, otherwise returns None. The healthy endpoints
+    /// are in the configured order, followed by the unhealthy endpoints in the order of their
+    /// recovery.
+    fn failover_endpoints(
+        &self,
+        object_storage: Option<&common::v2::ObjectStorage>,
+    ) -> Option<Vec<String>> {
+        let endpoint = object_storage?.endpoint.as_deref()?;
+        if !endpoint.contains(',') {
+            return None;
+        }
+
+        let now = Instant::now();
+        let mut unhealthy_endpoints = self.unhealthy_endpoints.lock();
+        let mut endpoints: Vec<(Option<Instant>, String)> = Vec::new();
+        for endpoint in endpoint.split(',').map(str::trim) {
+            if endpoint.is_empty() {
+                continue;
+            }
+
+            // Remove the endpoint recovered from the unhealthy state.
+            let unhealthy_until = match unhealthy_endpoints.peek(endpoint).copied() {
+                Some(unhealthy_until) if unhealthy_until > now => Some(unhealthy_until),
+                Some(_) => {
+                    unhealthy_endpoints.pop(endpoint);
+                    None
+                }
+                None => None,
+            };
+
+            endpoints.push((unhealthy_until, endpoint.to_string()));
+        }
+
+        // The stable sort keeps the configured order of the healthy endpoints.
+        endpoints.sort_by_key(|(unhealthy_until, _)| *unhealthy_until);
+        Some(
+            endpoints
+                .into_iter()
+                .map(|(_, endpoint)| endpoint)
+                .collect(),
+        )
+    }
+
+    /// Failover runs the operation with the endpoints in order, and fails over to the next
+    /// endpoint on the connection-level failures. The failed endpoint is marked unhealthy, and
+    /// the endpoint reached by the operation is marked healthy. Each failover to the next
+    /// endpoint withdraws the retry budget, and the last error is returned once the budget is
+    /// exhausted.
+    async fn failover<'a, T>(
+        &'a self,
+        endpoints: Vec<String>,
+        retry_budget: Option<&RetryBudget>,
+        operation: impl Fn(String) -> BoxFuture<'a, ClientResult<T>>,
+    ) -> ClientResult<T> {
+        let mut last_err = None;
+        for endpoint in endpoints {
+            if last_err.is_some()
+                && retry_budget.is_some_and(|retry_budget| !retry_budget.try_withdraw())
+            {
+                debug!(
+                    "skip failing over to {}, because the retry budget is exhausted",
+                    endpoint
+                );
+
+                break;
+            }
+
+            match operation(endpoint.clone()).await {
+                Err(err) if is_connection_error(&err) => {
+                    warn!("endpoint {} is unhealthy, failing over: {}", endpoint, err);
+                    self.unhealthy_endpoints
+                        .lock()
+                        .put(endpoint, Instant::now() + Self::ENDPOINT_UNHEALTHY_DURATION);
+                    last_err = Some(err);
+                }
+                result => {
+                    self.unhealthy_endpoints.lock().pop(&endpoint);
+                    return result;
+                }
+            }
+        }
+
+        Err(last_err.unwrap_or_else(|| {
+            ClientError::ValidationError("endpoint of object storage is empty".to_string())
+        }))
+    }
+
+    /// Override endpoint returns the object storage with the endpoint override of the request,
+    /// which takes precedence over the configured endpoint. The endpoint override must be the
+    /// http or https URL.
+    fn override_endpoint(
+        object_storage: Option<common::v2::ObjectStorage>,
+        endpoint_override: String,
+    ) -> ClientResult<Option<common::v2::ObjectStorage>> {
+        let url = Url::parse(&endpoint_override).map_err(|err| {
+            ClientError::ValidationError(format!(
+                "invalid endpoint override {}: {}",
+                endpoint_override, err
+            ))
+        })?;
+
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(ClientError::ValidationError(format!(
+                "unsupported scheme {} of endpoint override {}",
+                url.scheme(),
+                endpoint_override
+            )));
+        }
 
+        Ok(Some(common::v2::ObjectStorage {
+            endpoint: Some(endpoint_override),
+            ..object_storage.unwrap_or_default()
+        }))
+    }
+
+    /// With endpoint returns the object storage with the single endpoint of the failover.
+    fn with_endpoint(
+        object_storage: &Option<common::v2::ObjectStorage>,
+        endpoint: String,
+    ) -> Option<common::v2::ObjectStorage> {
+        object_storage
+            .clone()
+            .map(|object_storage| common::v2::ObjectStorage {
+                endpoint: Some(endpoint),
+                ..object_storage
+            })
+    }
+
+    /// Writer returns the writer of the object of the put request, the object is written with
+    /// the ACL of the request, and only if it does not exist if the service supports the
+    /// conditional write.
+    async fn writer(&self, request: &PutRequest) -> ClientResult<opendal::Writer> {
         // Parse the URL and convert it to a ParsedURL for create the ObjectStorage operator.
         let url: Url = request
             .url
             .parse()
             .map_err(|_| ClientError::InvalidURI(request.url.clone()))?;
 
-        let parsed_url: ParsedURL = url.try_into().inspect_err(|err| {
+        let parsed_url = self.parse_url(url).inspect_err(|err| {
             error!(
                 "parse put request url failed {:?} {}: {}",
                 request.path, request.url, err
             );
         })?;
 
-        // Initialize the object storage operator to write the object.
-        let mut object_storage_writer = self
-            .operator(&parsed_url, request.object_storage, request.timeout)?
-            .writer_with(&parsed_url.key)
-            .concurrent(self.config.backend.put_concurrent_chunk_count as usize)
-            .chunk(self.config.backend.put_chunk_size.as_u64() as usize)
-            .await
-            .map_err(|err| {
+        // Set the ACL of the object by the predefined ACL of the operator, so the operators of
+        // the different ACLs are not shared.
+        let mut object_storage = request.object_storage.clone();
+        if let Some(acl) = request.acl.as_deref() {
+            self.validate_acl(acl).inspect_err(|err| {
                 error!(
                     "put request failed {:?} {}: {}",
                     request.path, request.url, err
                 );
-
-                ClientError::BackendError(Box::new(BackendError {
-                    message: err.to_string(),
-                    status_code: None,
-                    header: None,
-                }))
             })?;
 
-        // Initialize the fs operator to read the local file.
-        let fs_operator = Operator::new(opendal::services::Fs::default().root("/"))
-            .inspect_err(|err| {
-                error!("initialize fs operator failed: {}", err);
-            })?
-            .finish();
+            if let Some(object_storage) = object_storage.as_mut() {
+                object_storage.predefined_acl = Some(acl.to_string());
+            }
+        }
 
-        let fs_reader = fs_operator
-            .reader_with(&request.path.to_string_lossy())
+        // Initialize the object storage operator to write the object.
+        let operator = self.operator(&parsed_url, object_storage, request.timeout)?;
+
+        // Write the object only if it does not exist by the conditional write, which falls back
+        // to the unconditional write if the service does not support it.
+        let mut if_not_exists = request.if_not_exists;
+        if if_not_exists && !operator.info().full_capability().write_with_if_not_exists {
+            warn!(
+                "{} does not support conditional write, fall back to unconditional write {}",
+                self.scheme, request.url
+            );
+
+            if_not_exists = false;
+        }
+
+        operator
+            .writer_with(&parsed_url.key)
             .concurrent(self.config.backend.put_concurrent_chunk_count as usize)
             .chunk(self.config.backend.put_chunk_size.as_u64() as usize)
-            .await?;
-
-        let content_length = fs_operator
-            .stat(&request.path.to_string_lossy())
+            .if_not_exists(if_not_exists)
             .await
-            .inspect_err(|err| {
+            .map_err(|err| {
                 error!(
-                    "stat local file failed {:?} {}: {}",
+                    "put request failed {:?} {}: {}",
                     request.path, request.url, err
                 );
-            })?
-            .content_length();
 
-        let mut offset: u64 = 0;
-        while offset < content_length {
-            let end = std::cmp::min(
-                offset + self.config.backend.put_chunk_size.as_u64(),
-                content_length,
-            );
+                Self::put_error(err, &request.url)
+            })
+    }
 
-            let buf = fs_reader.read(offset..end).await.inspect_err(|err| {
-                error!(
-                    "read local file failed {:?} {}: {}",
-                    request.path, request.url, err
-                );
-            })?;
+    /// Header value returns the header value of the option, and returns the validation error
+    /// if the value is not a valid header value.
+    fn header_value(name: &str, value: &str) -> ClientResult<http::HeaderValue> {
+        http::HeaderValue::from_str(value).map_err(|err| {
+            ClientError::ValidationError(format!("invalid {} {}: {}", name, value, err))
+        })
+    }
 
-            object_storage_writer.write(buf).await.inspect_err(|err| {
-                error!(
-                    "put request failed {:?} {}: {}",
-                    request.path, request.url, err
-                );
-            })?;
+    /// Object ACL headers returns the header of the canned ACL of the object if the predefined
+    /// ACL of the object storage is provided, which is set by the ACL of the put.
+    fn object_acl_headers(
+        name: &'static str,
+        object_storage: &common::v2::ObjectStorage,
+    ) -> ClientResult<http::HeaderMap> {
+        let mut headers = http::HeaderMap::new();
+        if let Some(acl) = object_storage.predefined_acl.as_deref() {
+            headers.insert(name, Self::header_value("acl", acl)?);
+        }
 
-            offset = end;
+        Ok(headers)
+    }
+
+    /// With signed headers wraps the HTTP client with the SignedHeadersFetcher if any header is
+    /// set, otherwise the requests signed by the operator are sent as is.
+    fn with_signed_headers(
+        http_client: HttpClient,
+        signer: HeaderSigner,
+        headers: http::HeaderMap,
+        object_write_headers: http::HeaderMap,
+    ) -> HttpClient {
+        if headers.is_empty() && object_write_headers.is_empty() {
+            return http_client;
         }
 
-        object_storage_writer.close().await.inspect_err(|err| {
-            error!(
-                "close put request failed {:?} {}: {}",
-                request.path, request.url, err
-            );
-        })?;
+        HttpClient::with(SignedHeadersFetcher {
+            client: http_client,
+            signer,
+            headers,
+            object_write_headers,
+        })
+    }
 
-        Ok(crate::PutResponse {
-            success: true,
-            http_header: None,
-            http_status_code: Some(reqwest::StatusCode::OK),
-            content_length: Some(content_length),
-            error_message: None,
+    /// With response errors wraps the HTTP client with the ResponseErrorFetcher, so the errors
+    /// of the throttled and the clock skewed responses keep the headers of the response.
+    fn with_response_errors(http_client: HttpClient) -> HttpClient {
+        HttpClient::with(ResponseErrorFetcher {
+            client: http_client,
         })
     }
 
-    /// Exists checks whether the file exists in the backend.
-    #[instrument(skip_all)]
-    async fn exists(&self, request: ExistsRequest) -> ClientResult<bool> {
-        debug!(
-            "exists request {} {}: {:?}",
-            request.task_id, request.url, request.http_header
-        );
+    /// Validate ACL validates the canned ACL of the put against the ACLs of the object storage,
+    /// and returns the Unsupported error if the object storage does not support the ACL.
+    fn validate_acl(&self, acl: &str) -> ClientResult<()> {
+        let acls = match self.scheme {
+            Scheme::S3 => Self::S3_CANNED_ACLS.as_slice(),
+            Scheme::GCS => Self::GCS_PREDEFINED_ACLS.as_slice(),
+            Scheme::OSS => Self::OSS_CANNED_ACLS.as_slice(),
+            _ => return Err(ClientError::Unsupported(format!("{} acl", self.scheme))),
+        };
 
-        // Parse the URL and convert it to a ParsedURL for create the ObjectStorage operator.
-        let url: Url = request
-            .url
-            .parse()
-            .map_err(|_| ClientError::InvalidURI(request.url.clone()))?;
+        if !acls.contains(&acl) {
+            return Err(ClientError::ValidationError(format!(
+                "invalid {} acl {}, expected one of {}",
+                self.scheme,
+                acl,
+                acls.join(", ")
+            )));
+        }
 
-        let parsed_url: ParsedURL = url.try_into().inspect_err(|err| {
-            error!(
-                "parse exists request url failed {} {}: {}",
-                request.task_id, request.url, err
-            );
-        })?;
+        Ok(())
+    }
 
-        // Initialize the operator with the parsed URL, object storage, and timeout.
-        let operator = self.operator(&parsed_url, request.object_storage, request.timeout)?;
-        Ok(operator.exists(&parsed_url.key).await?)
+    /// Host header override returns the configured host header override of the endpoint, which
+    /// is keyed by the authority of the endpoint, so the overrides of the different endpoints,
+    /// such as the endpoint override and the failover endpoints, are independent.
+    fn host_header_override(&self, endpoint: Option<&str>) -> Option<&str> {
+        let uri = endpoint?.parse::<http::Uri>().ok()?;
+        self.config
+            .backend
+            .host_header_overrides
+            .get(uri.authority()?.as_str())
+            .map(String::as_str)
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use dragonfly_api::common::v2::ObjectStorage as ObjectStorageInfo;
+    /// Override host returns the endpoint with the authority replaced by the host header
+    /// override, which is signed by the operator, and the authority of the endpoint connected
+    /// by the requests.
+    fn override_host(
+        endpoint: &str,
+        host_header_override: &str,
+    ) -> ClientResult<(String, http::uri::Authority)> {
+        let authority = endpoint
+            .parse::<http::Uri>()
+            .ok()
+            .and_then(|uri| uri.authority().cloned())
+            .ok_or_else(|| {
+                ClientError::ValidationError(format!("invalid endpoint {}", endpoint))
+            })?;
 
-    #[test]
-    fn should_return_true_for_supported_schemes() {
-        let supported = vec!["s3", "gs", "abs", "oss", "obs", "cos"];
-        for scheme in supported {
-            assert!(Scheme::is_supported(scheme));
+        host_header_override
+            .parse::<http::uri::Authority>()
+            .map_err(|err| {
+                ClientError::ValidationError(format!(
+                    "invalid host header override {}: {}",
+                    host_header_override, err
+                ))
+            })?;
+
+        Ok((
+            endpoint.replacen(authority.as_str(), host_header_override, 1),
+            authority,
+        ))
+    }
+
+    /// Operator initializes the operator with the parsed URL and object storage.
+    pub fn operator(
+        &self,
+        parsed_url: &ParsedURL,
+        object_storage: Option<common::v2::ObjectStorage>,
+        timeout: Duration,
+    ) -> ClientResult<Operator> {
+        self.fingerprinted_operator(parsed_url, object_storage, None, timeout)
+            .map(|(operator, _)| operator)
+    }
+
+    /// Profile credentials returns the S3 credentials of the configured profile, and returns
+    /// None if the profile is not configured. The credentials file is loaded by the first
+    /// request and reloaded when it is modified, so the rotated credentials are picked up and
+    /// the invalid file only fails the requests using the profile.
+    fn profile_credentials(&self) -> ClientResult<Option<ProfileCredentials>> {
+        let Some(profile) = self
+            .config
+            .backend
+            .object_storage_profile
+            .as_deref()
+            .filter(|_| self.scheme == Scheme::S3)
+        else {
+            return Ok(None);
+        };
+
+        let path = &self.config.backend.object_storage_credentials_file;
+        let modified = std::fs::metadata(path)
+            .and_then(|metadata| metadata.modified())
+            .inspect_err(|err| {
+                error!("stat credentials file {} failed: {}", path.display(), err);
+            })?;
+
+        let mut profile_credentials = self.profile_credentials.lock();
+        if let Some((cached_modified, credentials)) = profile_credentials.as_ref() {
+            if *cached_modified == modified {
+                return Ok(Some(credentials.clone()));
+            }
         }
+
+        let credentials = ProfileCredentials::load(path, profile)?;
+        *profile_credentials = Some((modified, credentials.clone()));
+        Ok(Some(credentials))
     }
-    #[test]
-    fn should_return_false_for_unsupported_schemes() {
-        let unsupported = vec!["http", "https", "ftp", "hdfs", "file", "", "S3", "GCS"];
-        for scheme in unsupported {
-            assert!(!Scheme::is_supported(scheme));
+
+    /// Resolve object storage fills the options of the object storage not provided by the
+    /// request, such as the credentials of the profile and the default region, and validates
+    /// the required fields without any network call.
+    fn resolve_object_storage(
+        &self,
+        parsed_url: &ParsedURL,
+        object_storage: Option<common::v2::ObjectStorage>,
+    ) -> ClientResult<common::v2::ObjectStorage> {
+        // If download backend is object storage, object_storage parameter is required.
+        let Some(mut object_storage) = object_storage else {
+            return Err(ClientError::BackendError(Box::new(BackendError {
+                message: format!("{} need object_storage parameter", self.scheme),
+                status_code: None,
+                header: None,
+            })));
+        };
+
+        // Use the credentials of the profile if the request has no inline credentials and uses
+        // the default endpoint of the scheme. The endpoint of the request, such as the endpoint
+        // override, may be any host, and the credentials of dfdaemon must not be sent to it.
+        let profile_credentials = if object_storage.access_key_id.is_none()
+            && object_storage.access_key_secret.is_none()
+            && object_storage.endpoint.is_none()
+        {
+            self.profile_credentials()?
+        } else {
+            None
+        };
+        if let Some(profile_credentials) = &profile_credentials {
+            object_storage.access_key_id = profile_credentials.access_key_id.clone();
+            object_storage.access_key_secret = profile_credentials.access_key_secret.clone();
+            if object_storage.session_token.is_none() {
+                object_storage.session_token = profile_credentials.session_token.clone();
+            }
+        }
+
+        // Use the region embedded in the URL host if the region is not configured, and fall
+        // back to the region of the profile and the default region of the scheme.
+        if object_storage.region.is_none() {
+            object_storage.region = parsed_url
+                .region
+                .clone()
+                .or_else(|| profile_credentials.and_then(|credentials| credentials.region))
+                .or_else(|| {
+                    self.config
+                        .backend
+                        .default_regions
+                        .get(&self.scheme.to_string())
+                        .cloned()
+                });
+        }
+
+        // Validate the required fields before building the operator, which fails fast without
+        // any network call.
+        self.validate(&object_storage)?;
+
+        // Expand the allowed environment variables in the endpoint, such as
+        // `https://${REGION}.oss.example.com`.
+        if let Some(endpoint) = &object_storage.endpoint {
+            object_storage.endpoint = Some(expand_env(
+                endpoint,
+                &self.config.backend.endpoint_env_allowlist,
+                |name| std::env::var(name).ok(),
+            )?);
+        }
+
+        // Apply the transport of the compound scheme to the endpoint, such as `http` of
+        // `s3+http://bucket/key`. The default endpoint of the scheme is used as is.
+        if let (Some(transport), Some(endpoint)) = (parsed_url.transport, &object_storage.endpoint)
+        {
+            object_storage.endpoint = Some(transport.apply(endpoint));
         }
+
+        Ok(object_storage)
     }
 
-    #[test]
-    fn should_get_parsed_url() {
-        let file_key = "test-bucket/file";
-        let dir_key = "test-bucket/path/to/dir/";
-        let schemes = vec![
-            Scheme::OBS,
-            Scheme::S3,
-            Scheme::ABS,
-            Scheme::OSS,
-            Scheme::COS,
-            Scheme::GCS,
-        ];
+    /// Fingerprinted operator initializes the operator with the parsed URL and object storage,
+    /// and returns the operator with the fingerprint of its options, such as the endpoint and
+    /// the credentials. The expected bucket owner of the request only applies to S3.
+    fn fingerprinted_operator(
+        &self,
+        parsed_url: &ParsedURL,
+        object_storage: Option<common::v2::ObjectStorage>,
+        expected_bucket_owner: Option<&str>,
+        timeout: Duration,
+    ) -> ClientResult<(Operator, String)> {
+        let object_storage = self.resolve_object_storage(parsed_url, object_storage)?;
+        let expected_bucket_owner = expected_bucket_owner.filter(|_| self.scheme == Scheme::S3);
+
+        // Reuse the cached operator if the operator options are the same.
+        let fingerprint =
+            self.operator_fingerprint(parsed_url, &object_storage, expected_bucket_owner, timeout);
+        if let Some(operator) = self.operators.lock().get(&fingerprint) {
+            return Ok((operator.clone(), fingerprint));
+        }
 
-        // Test each scheme for both file and directory URLs.
-        for scheme in schemes {
-            let file_url = format!("{}://{}", scheme, file_key);
-            let url: Url = file_url.parse().unwrap();
-            let parsed_url: ParsedURL = url.try_into().unwrap();
+        let operator = match self.scheme {
+            Scheme::S3 | Scheme::S3Compatible => {
+                self.s3_operator(parsed_url, object_storage, expected_bucket_owner, timeout)
+            }
+            Scheme::GCS => self.gcs_operator(parsed_url, object_storage, timeout),
+            Scheme::ABS => self.abs_operator(parsed_url, object_storage, timeout),
+            Scheme::OSS => self.oss_operator(parsed_url, object_storage, timeout),
+            Scheme::OBS => self.obs_operator(parsed_url, object_storage, timeout),
+            Scheme::COS => self.cos_operator(parsed_url, object_storage, timeout),
+            Scheme::OCI => self.oci_operator(parsed_url, object_storage, timeout),
+        }?;
+
+        // Apply the registered layers to the operator in the order of registration.
+        let operator = self
+            .operator_layers
+            .read()
+            .iter()
+            .fold(operator, |operator, layer| layer(operator));
+
+        self.operators
+            .lock()
+            .put(fingerprint.clone(), operator.clone());
+        Ok((operator, fingerprint))
+    }
 
-            assert!(!parsed_url.is_dir());
-            assert_eq!(parsed_url.bucket, "test-bucket");
-            assert_eq!(parsed_url.key, "file");
-            assert_eq!(parsed_url.scheme, scheme);
+    /// List page lists a page of the directory recursively, and returns the entries and the next
+    /// continuation token. The continuation token is the path of the last entry of the page,
+    /// and the next page starts after it. If the page size is None, all entries are listed.
+    ///
+    /// The entries are collected incrementally, so if the listing fails midway after some
+    /// entries are listed, the partial entries are returned with the error instead of dropping
+    /// the progress, and the continuation token resumes the listing after the last entry.
+    ///
+    /// If the max depth is provided, the entry deeper than it is collapsed to the directory at
+    /// the max depth, and the listing restarts after the subtree of the directory, so the deep
+    /// hierarchy costs one listing per directory at the boundary instead of listing every entry.
+    async fn list_page(
+        operator: &Operator,
+        parsed_url: &ParsedURL,
+        page_size: Option<usize>,
+        continuation_token: Option<String>,
+        filter: Option<&glob::Pattern>,
+        max_depth: Option<usize>,
+    ) -> opendal::Result<ListPage> {
+        let make_lister = |start_after: Option<String>| async move {
+            let mut lister = operator.lister_with(&parsed_url.key).recursive(true);
+            if let Some(page_size) = page_size {
+                // List one more entry to check whether there is a next page.
+                lister = lister.limit(page_size + 1);
+            }
+
+            if let Some(start_after) = start_after {
+                lister = lister.start_after(&start_after);
+            }
+
+            lister.await
+        };
 
-            let dir_url = format!("{}://{}", scheme, dir_key);
-            let url: Url = dir_url.parse().unwrap();
-            let parsed_url: ParsedURL = url.try_into().unwrap();
+        let mut lister = make_lister(continuation_token.clone()).await?;
+        let mut entries: Vec<(String, Metadata)> = Vec::new();
+        loop {
+            let entry = match lister.try_next().await {
+                Ok(Some(entry)) => entry,
+                Ok(None) => break,
+                Err(err) if entries.is_empty() => return Err(err),
+                Err(err) => {
+                    let next_continuation_token = entries.last().map(|(path, _)| path.clone());
+                    return Ok(ListPage {
+                        entries,
+                        next_continuation_token,
+                        error: Some(err),
+                    });
+                }
+            };
+
+            // The directory itself is not counted in the page.
+            if entry.path() == parsed_url.key {
+                continue;
+            }
+
+            // The entry deeper than the max depth is replaced by the directory at the max depth,
+            // which is skipped if it has been returned by this page or the previous page.
+            let (path, metadata) = match max_depth
+                .and_then(|max_depth| parsed_url.depth_boundary(entry.path(), max_depth))
+            {
+                Some(boundary) => {
+                    // Restart the listing after the subtree of the directory, the `char::MAX`
+                    // sorts after any key under the directory.
+                    let start_after = format!("{}{}", boundary, char::MAX);
+                    lister = match make_lister(Some(start_after)).await {
+                        Ok(lister) => lister,
+                        Err(err) if entries.is_empty() => return Err(err),
+                        Err(err) => {
+                            let next_continuation_token =
+                                entries.last().map(|(path, _)| path.clone());
+                            return Ok(ListPage {
+                                entries,
+                                next_continuation_token,
+                                error: Some(err),
+                            });
+                        }
+                    };
+
+                    if continuation_token.as_ref() == Some(&boundary)
+                        || entries.last().is_some_and(|(path, _)| *path == boundary)
+                    {
+                        continue;
+                    }
 
-            assert!(parsed_url.is_dir());
-            assert_eq!(parsed_url.bucket, "test-bucket");
-            assert_eq!(parsed_url.key, "path/to/dir/");
-            assert_eq!(parsed_url.scheme, scheme);
+                    (boundary, Metadata::new(EntryMode::DIR))
+                }
+                None => entry.into_parts(),
+            };
+
+            // The entries not matching the filter are dropped before they are collected, and
+            // they are not counted in the page.
+            if filter.is_some_and(|filter| !parsed_url.matches_filter(filter, &path)) {
+                continue;
+            }
+
+            if page_size.is_some_and(|page_size| entries.len() == page_size) {
+                let next_continuation_token = entries.last().map(|(path, _)| path.clone());
+                return Ok(ListPage {
+                    entries,
+                    next_continuation_token,
+                    error: None,
+                });
+            }
+
+            entries.push((path, metadata));
         }
+
+        Ok(ListPage {
+            entries,
+            next_continuation_token: None,
+            error: None,
+        })
     }
 
-    #[test]
-    fn should_get_url_with_the_same_prefix() {
-        let file_key = "test-bucket/file";
-        let schemes = vec![
-            Scheme::OBS,
-            Scheme::S3,
-            Scheme::ABS,
-            Scheme::OSS,
-            Scheme::COS,
-            Scheme::GCS,
-        ];
+    /// Stat dir lists the entries of the directory, the directory is not an object in the object
+    /// storage, so the stat of the directory key is skipped, which often returns not found or the
+    /// meaningless content length.
+    async fn stat_dir(
+        &self,
+        operator: &Operator,
+        parsed_url: &ParsedURL,
+        request: &StatRequest,
+        redacted_url: &str,
+    ) -> ClientResult<StatResponse> {
+        let filter = request
+            .filter
+            .as_deref()
+            .map(glob::Pattern::new)
+            .transpose()
+            .map_err(|err| {
+                error!(
+                    "invalid filter {} {}: {}",
+                    request.task_id, redacted_url, err
+                );
 
-        // Test each scheme for both file and directory URLs.
-        for scheme in schemes {
-            let file_url = format!("{}://{}", scheme, file_key);
-            let url: Url = file_url.parse().unwrap();
-            let parsed_url: ParsedURL = url.try_into().unwrap();
+                ClientError::ValidationError(format!("invalid filter: {}", err))
+            })?;
 
-            let new_url = parsed_url.make_url_by_entry_path("test-entry");
-            let new_parsed_url: ParsedURL = new_url.try_into().unwrap();
+        // The max depth of 0 can not return any entry.
+        if request.max_depth == Some(0) {
+            error!("invalid max depth {} {}: 0", request.task_id, redacted_url);
 
-            assert_eq!(parsed_url.bucket, new_parsed_url.bucket);
-            assert_eq!(parsed_url.scheme, new_parsed_url.scheme);
-            assert_eq!(new_parsed_url.key, "test-entry");
+            return Err(ClientError::ValidationError(
+                "max depth must be greater than 0".to_string(),
+            ));
         }
+
+        // The continuation token resumes the listing started after the start after, so it
+        // takes precedence.
+        let start_after = request
+            .continuation_token
+            .clone()
+            .or_else(|| request.start_after.clone());
+        let page = run_until_cancelled(
+            Self::list_page(
+                operator,
+                parsed_url,
+                request.page_size,
+                start_after,
+                filter.as_ref(),
+                request.max_depth,
+            ),
+            request.cancel_token.as_ref(),
+        )
+        .await?;
+        let page = match page {
+            Ok(page) => page,
+            Err(err) => {
+                error!(
+                    "list request failed {} {}: {}",
+                    request.task_id, redacted_url, err
+                );
+
+                return Self::stat_failure(&err);
+            }
+        };
+
+        let entries = parsed_url.make_dir_entries(page.entries, &request.list_metakeys);
+        debug!(
+            "stat response {} {}: {} entries",
+            request.task_id,
+            redacted_url,
+            entries.len()
+        );
+
+        // Return the partial entries if the listing fails midway, and the caller decides
+        // whether the partial result is usable.
+        let truncated = page.error.is_some();
+        let failure_class = page.error.as_ref().map(|err| FailureClass::from_error(err));
+        let error_message = page.error.map(|err| {
+            error!(
+                "list request failed midway {} {} after {} entries: {}",
+                request.task_id,
+                redacted_url,
+                entries.len(),
+                err
+            );
+
+            format!("list failed after {} entries: {}", entries.len(), err)
+        });
+
+        Ok(StatResponse {
+            success: !truncated,
+            content_length: None,
+            http_header: None,
+            http_status_code: None,
+            error_message,
+            entries,
+            user_metadata: HashMap::new(),
+            next_continuation_token: page.next_continuation_token,
+            storage_class: None,
+            content_type: None,
+            accept_ranges: true,
+            suggested_filename: None,
+            truncated,
+            is_dir: true,
+            failure_class,
+            etag: None,
+        })
     }
 
-    #[test]
-    fn should_return_error_when_scheme_not_valid() {
-        let url: Url = "github://test-bucket/file".parse().unwrap();
-        let result = TryInto::<ParsedURL>::try_into(url);
+    /// Is prefix checks whether any object exists under the key as the prefix by a limit-1
+    /// listing.
+    async fn is_prefix(operator: &Operator, key: &str) -> opendal::Result<bool> {
+        let mut lister = operator
+            .lister_with(&format!("{}/", key.trim_end_matches('/')))
+            .limit(1)
+            .await?;
 
-        assert!(result.is_err());
-        assert!(matches!(result.unwrap_err(), ClientError::InvalidURI(..)));
+        Ok(lister.try_next().await?.is_some())
     }
 
-    #[test]
-    fn should_return_error_when_bucket_not_valid() {
-        let schemes = vec![
-            Scheme::OBS,
-            Scheme::S3,
-            Scheme::ABS,
-            Scheme::OSS,
-            Scheme::COS,
-            Scheme::GCS,
-        ];
+    /// List urls lists the directory recursively and returns the stream of the entry URLs, which
+    /// yields each URL as the entry is listed instead of collecting all entries, so the listing
+    /// of the large bucket can be piped to the other tools. The directory itself is excluded
+    /// like the entries of the stat.
+    #[instrument(skip_all)]
+    pub async fn list_urls(
+        &self,
+        request: StatRequest,
+    ) -> ClientResult<impl Stream<Item = ClientResult<String>> + Send> {
+        let redacted_url = redact_url(&request.url);
+        debug!("list urls request {} {}", request.task_id, redacted_url);
+
+        let url =
+            Url::parse(&request.url).map_err(|_| ClientError::InvalidURI(request.url.clone()))?;
+        let parsed_url = self.parse_url(url)?;
+        if !parsed_url.is_dir() {
+            error!(
+                "list urls request url is not a directory {} {}",
+                request.task_id, redacted_url
+            );
+            return Err(ClientError::InvalidURI(redacted_url));
+        }
 
-        for scheme in schemes {
-            let url: Url = format!("{}:///file", scheme).parse().unwrap();
-            let result = TryInto::<ParsedURL>::try_into(url);
+        let (operator, _) = self.fingerprinted_operator(
+            &parsed_url,
+            request.object_storage,
+            request.expected_bucket_owner.as_deref(),
+            request.timeout,
+        )?;
+        let lister = run_until_cancelled(
+            async { operator.lister_with(&parsed_url.key).recursive(true).await },
+            request.cancel_token.as_ref(),
+        )
+        .await?
+        .map_err(|err| {
+            error!(
+                "list urls request failed {} {}: {}",
+                request.task_id, redacted_url, err
+            );
 
-            assert!(result.is_err());
-            assert!(matches!(result.unwrap_err(), ClientError::InvalidURI(..)));
+            Self::opendal_error(err)
+        })?;
+
+        let key = parsed_url.key.clone();
+        Ok(lister
+            .try_filter(move |entry| future::ready(entry.path() != key))
+            .map_ok(move |entry| parsed_url.make_url_by_entry_path(entry.path()).to_string())
+            .map_err(Self::opendal_error))
+    }
+
+    /// Validate checks the required fields of the object storage for the scheme, and returns
+    /// the error naming the missing fields. GCS has no required fields, because it uses the
+    /// Application Default Credentials (ADC) or the VM metadata if the credential path is not
+    /// provided.
+    pub fn validate(&self, object_storage: &common::v2::ObjectStorage) -> ClientResult<()> {
+        let required_fields = match self.scheme {
+            Scheme::S3 | Scheme::OCI => vec![
+                ("access_key_id", object_storage.access_key_id.is_some()),
+                (
+                    "access_key_secret",
+                    object_storage.access_key_secret.is_some(),
+                ),
+                ("region", object_storage.region.is_some()),
+            ],
+            Scheme::ABS | Scheme::OSS | Scheme::OBS | Scheme::COS => vec![
+                ("access_key_id", object_storage.access_key_id.is_some()),
+                (
+                    "access_key_secret",
+                    object_storage.access_key_secret.is_some(),
+                ),
+                ("endpoint", object_storage.endpoint.is_some()),
+            ],
+            // Most S3-compatible providers ignore the region, but it is required to sign the
+            // requests, so it falls back to the default region of the scheme.
+            Scheme::S3Compatible => vec![
+                ("access_key_id", object_storage.access_key_id.is_some()),
+                (
+                    "access_key_secret",
+                    object_storage.access_key_secret.is_some(),
+                ),
+                ("endpoint", object_storage.endpoint.is_some()),
+                ("region", object_storage.region.is_some()),
+            ],
+            Scheme::GCS => Vec::new(),
+        };
+
+        let need_fields: Vec<&str> = required_fields
+            .into_iter()
+            .filter(|(_, present)| !present)
+            .map(|(field, _)| field)
+            .collect();
+        if need_fields.is_empty() {
+            return Ok(());
         }
+
+        Err(ClientError::BackendError(Box::new(BackendError {
+            message: format!("{} need {}", self.scheme, need_fields.join(", ")),
+            status_code: None,
+            header: None,
+        })))
     }
 
-    #[test]
-    fn should_get_operator() {
-        let test_cases = vec![
-            (
-                Scheme::S3,
-                ObjectStorageInfo {
-                    region: Some("test-region".into()),
-                    access_key_id: Some("access-key-id".into()),
-                    access_key_secret: Some("access-key-secret".into()),
+    /// Operator fingerprint returns the SHA-256 of the options to build the operator, which is
+    /// used as the key of the operator cache. The options are serialized as the JSON array, so
+    /// the different options never share the key, and the raw secrets are not stored in the key.
+    fn operator_fingerprint(
+        &self,
+        parsed_url: &ParsedURL,
+        object_storage: &common::v2::ObjectStorage,
+        expected_bucket_owner: Option<&str>,
+        timeout: Duration,
+    ) -> String {
+        let options = serde_json::json!([
+            self.scheme.to_string(),
+            parsed_url.bucket,
+            parsed_url.region,
+            parsed_url.namespace,
+            object_storage.region,
+            object_storage.endpoint,
+            object_storage.access_key_id,
+            object_storage.access_key_secret,
+            object_storage.session_token,
+            object_storage.security_token,
+            object_storage.credential_path,
+            object_storage.predefined_acl,
+            object_storage.insecure_skip_verify,
+            expected_bucket_owner,
+            self.host_header_override(object_storage.endpoint.as_deref()),
+            timeout.as_nanos().to_string(),
+            // The operators built before a layer is registered are not reused.
+            self.operator_layers.read().len(),
+        ]);
+
+        hex::encode(sha2::Sha256::digest(options.to_string()))
+    }
+
+    /// S3 operator initializes the S3 operator with the parsed URL and object storage, which is
+    /// shared by S3, OCI and the generic S3-compatible object storage. The virtual-host-style is
+    /// not enabled, so the path-style addressing is used for the endpoint.
+    fn s3_operator(
+        &self,
+        parsed_url: &ParsedURL,
+        object_storage: common::v2::ObjectStorage,
+        expected_bucket_owner: Option<&str>,
+        timeout: Duration,
+    ) -> ClientResult<Operator> {
+        // The required fields are checked by the validate before the operator is built.
+        let access_key_id = object_storage.access_key_id.as_deref().unwrap_or_default();
+        let access_key_secret = object_storage
+            .access_key_secret
+            .as_deref()
+            .unwrap_or_default();
+        let region = object_storage.region.as_deref().unwrap_or_default();
+
+        // Initialize the S3 operator with the object storage. The config load is disabled,
+        // because it reads the profile files synchronously when building the operator, which
+        // blocks the async runtime, and the credentials are always provided by the request.
+        // The versioning is enabled to read the specific version of the object.
+        let mut builder = opendal::services::S3::default();
+        builder = builder
+            .access_key_id(access_key_id)
+            .secret_access_key(access_key_secret)
+            .root(OPERATOR_ROOT)
+            .bucket(&parsed_url.bucket)
+            .region(region)
+            .enable_versioning(true)
+            .disable_config_load();
+
+        // Configure the endpoint if it is provided. The endpoint is signed with the overridden
+        // host if the host header override of the endpoint is configured, and the requests are
+        // sent to the authority of the endpoint.
+        let mut endpoint_authority = None;
+        if let Some(endpoint) = object_storage.endpoint.as_deref() {
+            match self.host_header_override(Some(endpoint)) {
+                Some(host_header_override) => {
+                    let (endpoint, authority) =
+                        Self::override_host(endpoint, host_header_override)?;
+                    builder = builder.endpoint(&endpoint);
+                    endpoint_authority = Some(authority);
+                }
+                None => builder = builder.endpoint(endpoint),
+            }
+        }
+
+        // Configure the session token if it is provided.
+        if let Some(session_token) = object_storage.session_token.as_deref() {
+            builder = builder.session_token(session_token);
+        }
+
+        // Choose the http client using dangerous client or not by insecure_skip_verify.
+        let http_client = match object_storage.insecure_skip_verify {
+            Some(true) => self.danger_client.clone(),
+            _ => self.client.clone(),
+        };
+
+        // Set the expected bucket owner of the request on every request of the operator, so
+        // the requests to the bucket owned by another account fail.
+        let mut headers = http::HeaderMap::new();
+        if let Some(expected_bucket_owner) = expected_bucket_owner {
+            headers.insert(
+                X_AMZ_EXPECTED_BUCKET_OWNER,
+                Self::header_value("expected bucket owner", expected_bucket_owner)?,
+            );
+        }
+
+        // Set the canned ACL on the writes of the object if it is configured by the put. The
+        // requests are signed again with the headers, as opendal does not support them.
+        let http_client = Self::with_signed_headers(
+            HttpClient::with(http_client),
+            HeaderSigner::S3(
+                reqsign::AwsV4Signer::new("s3", region),
+                reqsign::AwsCredential {
+                    access_key_id: access_key_id.to_string(),
+                    secret_access_key: access_key_secret.to_string(),
+                    session_token: object_storage.session_token.clone(),
+                    expires_in: None,
+                },
+            ),
+            headers,
+            Self::object_acl_headers("x-amz-acl", &object_storage)?,
+        );
+
+        let http_client = match endpoint_authority {
+            Some(endpoint_authority) => HttpClient::with(HostHeaderFetcher {
+                client: http_client,
+                endpoint_authority,
+            }),
+            None => http_client,
+        };
+
+        Ok(Operator::new(builder)?
+            .finish()
+            .layer(TimeoutLayer::new().with_timeout(timeout))
+            .layer(HttpClientLayer::new(Self::with_response_errors(
+                http_client,
+            ))))
+    }
+
+    /// GCS operator initializes the GCS operator with the parsed URL and object storage.
+    fn gcs_operator(
+        &self,
+        parsed_url: &ParsedURL,
+        object_storage: common::v2::ObjectStorage,
+        timeout: Duration,
+    ) -> ClientResult<Operator> {
+        // Initialize the GCS operator with the object storage.
+        let mut builder = opendal::services::Gcs::default();
+        builder = builder.root(OPERATOR_ROOT).bucket(&parsed_url.bucket);
+
+        // Configure the credentials using the local path to the credential file if provided.
+        // Otherwise, configure using the Application Default Credentials (ADC) if it is enabled.
+        // The config load is disabled without the ADC, because it reads the environment and the
+        // well-known credentials file synchronously when building the operator.
+        match object_storage.credential_path.as_deref() {
+            Some(credential_path) => builder = builder.credential_path(credential_path),
+            None if self
+                .config
+                .backend
+                .enable_object_storage_application_default_credentials => {}
+            None => builder = builder.disable_config_load(),
+        }
+
+        // Configure the endpoint if it is provided.
+        if let Some(endpoint) = object_storage.endpoint.as_deref() {
+            builder = builder.endpoint(endpoint);
+        }
+
+        // Configure the predefined ACL if it is provided.
+        if let Some(predefined_acl) = object_storage.predefined_acl.as_deref() {
+            builder = builder.predefined_acl(predefined_acl);
+        }
+
+        // Choose the http client using dangerous client or not by insecure_skip_verify.
+        let http_client = match object_storage.insecure_skip_verify {
+            Some(true) => self.danger_client.clone(),
+            _ => self.client.clone(),
+        };
+
+        Ok(Operator::new(builder)?
+            .finish()
+            .layer(TimeoutLayer::new().with_timeout(timeout))
+            .layer(HttpClientLayer::new(Self::with_response_errors(
+                HttpClient::with(http_client),
+            ))))
+    }
+
+    /// ABS operator initializes the ABS operator with the parsed URL and object storage.
+    fn abs_operator(
+        &self,
+        parsed_url: &ParsedURL,
+        object_storage: common::v2::ObjectStorage,
+        timeout: Duration,
+    ) -> ClientResult<Operator> {
+        // The required fields are checked by the validate before the operator is built.
+        let access_key_id = object_storage.access_key_id.as_deref().unwrap_or_default();
+        let access_key_secret = object_storage
+            .access_key_secret
+            .as_deref()
+            .unwrap_or_default();
+        let endpoint = object_storage.endpoint.as_deref().unwrap_or_default();
+
+        // Initialize the ABS operator with the object storage.
+        let mut builder = opendal::services::Azblob::default();
+        builder = builder
+            .account_name(access_key_id)
+            .account_key(access_key_secret)
+            .root(OPERATOR_ROOT)
+            .container(&parsed_url.bucket)
+            .endpoint(endpoint);
+
+        // Choose the http client using dangerous client or not by insecure_skip_verify.
+        let http_client = match object_storage.insecure_skip_verify {
+            Some(true) => self.danger_client.clone(),
+            _ => self.client.clone(),
+        };
+
+        Ok(Operator::new(builder)?
+            .finish()
+            .layer(TimeoutLayer::new().with_timeout(timeout))
+            .layer(HttpClientLayer::new(Self::with_response_errors(
+                HttpClient::with(http_client),
+            ))))
+    }
+
+    /// OSS operator initializes the OSS operator with the parsed URL and object storage.
+    fn oss_operator(
+        &self,
+        parsed_url: &ParsedURL,
+        object_storage: common::v2::ObjectStorage,
+        timeout: Duration,
+    ) -> ClientResult<Operator> {
+        // The required fields are checked by the validate before the operator is built.
+        let access_key_id = object_storage.access_key_id.as_deref().unwrap_or_default();
+        let access_key_secret = object_storage
+            .access_key_secret
+            .as_deref()
+            .unwrap_or_default();
+        let endpoint = object_storage.endpoint.as_deref().unwrap_or_default();
+
+        // Initialize the OSS operator with the object storage.
+        let mut builder = opendal::services::Oss::default();
+        builder = if let Some(security_token) = &object_storage.security_token {
+            builder
+                .access_key_id(access_key_id)
+                .access_key_secret(access_key_secret)
+                .endpoint(endpoint)
+                .root(OPERATOR_ROOT)
+                .bucket(&parsed_url.bucket)
+                .security_token(security_token)
+        } else {
+            builder
+                .access_key_id(access_key_id)
+                .access_key_secret(access_key_secret)
+                .endpoint(endpoint)
+                .root(OPERATOR_ROOT)
+                .bucket(&parsed_url.bucket)
+        };
+
+        // Enable the versioning to read the specific version of the object.
+        builder = builder.enable_versioning(true);
+
+        // Choose the http client using dangerous client or not by insecure_skip_verify.
+        let http_client = match object_storage.insecure_skip_verify {
+            Some(true) => self.danger_client.clone(),
+            _ => self.client.clone(),
+        };
+
+        // Set the canned ACL on the writes of the object if it is configured by the put. The
+        // requests are signed again with the header, as opendal does not support it.
+        let http_client = Self::with_signed_headers(
+            HttpClient::with(http_client),
+            HeaderSigner::OSS(
+                reqsign::AliyunOssSigner::new(&parsed_url.bucket),
+                reqsign::AliyunCredential {
+                    access_key_id: access_key_id.to_string(),
+                    access_key_secret: access_key_secret.to_string(),
+                    security_token: object_storage.security_token.clone(),
+                    expires_in: None,
+                },
+            ),
+            http::HeaderMap::new(),
+            Self::object_acl_headers("x-oss-object-acl", &object_storage)?,
+        );
+
+        Ok(Operator::new(builder)?
+            .finish()
+            .layer(TimeoutLayer::new().with_timeout(timeout))
+            .layer(HttpClientLayer::new(Self::with_response_errors(
+                http_client,
+            ))))
+    }
+
+    /// OBS operator initializes the OBS operator with the parsed URL and object storage.
+    fn obs_operator(
+        &self,
+        parsed_url: &ParsedURL,
+        object_storage: common::v2::ObjectStorage,
+        timeout: Duration,
+    ) -> ClientResult<Operator> {
+        // The required fields are checked by the validate before the operator is built.
+        let access_key_id = object_storage.access_key_id.as_deref().unwrap_or_default();
+        let access_key_secret = object_storage
+            .access_key_secret
+            .as_deref()
+            .unwrap_or_default();
+        let endpoint = object_storage.endpoint.as_deref().unwrap_or_default();
+
+        // Initialize the OBS operator with the object storage.
+        let mut builder = opendal::services::Obs::default();
+        builder = builder
+            .access_key_id(access_key_id)
+            .secret_access_key(access_key_secret)
+            .endpoint(endpoint)
+            .root(OPERATOR_ROOT)
+            .bucket(&parsed_url.bucket);
+
+        // Choose the http client using dangerous client or not by insecure_skip_verify.
+        let http_client = match object_storage.insecure_skip_verify {
+            Some(true) => self.danger_client.clone(),
+            _ => self.client.clone(),
+        };
+
+        Ok(Operator::new(builder)?
+            .finish()
+            .layer(TimeoutLayer::new().with_timeout(timeout))
+            .layer(HttpClientLayer::new(Self::with_response_errors(
+                HttpClient::with(http_client),
+            ))))
+    }
+
+    /// COS operator initializes the COS operator with the parsed URL and object storage.
+    fn cos_operator(
+        &self,
+        parsed_url: &ParsedURL,
+        object_storage: common::v2::ObjectStorage,
+        timeout: Duration,
+    ) -> ClientResult<Operator> {
+        // The required fields are checked by the validate before the operator is built.
+        let access_key_id = object_storage.access_key_id.as_deref().unwrap_or_default();
+        let access_key_secret = object_storage
+            .access_key_secret
+            .as_deref()
+            .unwrap_or_default();
+        let endpoint = object_storage.endpoint.as_deref().unwrap_or_default();
+
+        // Initialize the COS operator with the object storage.
+        let mut builder = opendal::services::Cos::default();
+        builder = builder
+            .secret_id(access_key_id)
+            .secret_key(access_key_secret)
+            .endpoint(endpoint)
+            .root(OPERATOR_ROOT)
+            .bucket(&parsed_url.bucket)
+            .disable_config_load();
+
+        // Choose the http client using dangerous client or not by insecure_skip_verify.
+        let http_client = match object_storage.insecure_skip_verify {
+            Some(true) => self.danger_client.clone(),
+            _ => self.client.clone(),
+        };
+
+        Ok(Operator::new(builder)?
+            .finish()
+            .layer(TimeoutLayer::new().with_timeout(timeout))
+            .layer(HttpClientLayer::new(Self::with_response_errors(
+                HttpClient::with(http_client),
+            ))))
+    }
+
+    /// OCI operator initializes the OCI operator with the parsed URL and object storage. OCI is
+    /// S3-compatible, so the S3 service of opendal is used with the path-style addressing.
+    fn oci_operator(
+        &self,
+        parsed_url: &ParsedURL,
+        object_storage: common::v2::ObjectStorage,
+        timeout: Duration,
+    ) -> ClientResult<Operator> {
+        // Use the endpoint if it is provided, otherwise derive the endpoint from the namespace
+        // and the region, which is checked by the validate before the operator is built.
+        let region = object_storage.region.as_deref().unwrap_or_default();
+        let endpoint = match (&object_storage.endpoint, &parsed_url.namespace) {
+            (Some(endpoint), _) => endpoint.clone(),
+            (None, Some(namespace)) => format!(
+                "https://{}.compat.objectstorage.{}.oraclecloud.com",
+                namespace, region
+            ),
+            (None, None) => {
+                return Err(ClientError::BackendError(Box::new(BackendError {
+                    message: format!("{} need endpoint or namespace", self.scheme),
+                    status_code: None,
+                    header: None,
+                })));
+            }
+        };
+
+        // The S3 service of opendal uses the path-style addressing by default, which is
+        // required by OCI.
+        let object_storage = common::v2::ObjectStorage {
+            endpoint: Some(endpoint),
+            ..object_storage
+        };
+        self.s3_operator(parsed_url, object_storage, None, timeout)
+    }
+
+    /// Prefetch chunks returns the prefetch chunks of the request clamped to the max prefetch
+    /// chunks of the config.
+    fn prefetch_chunks(&self, prefetch_chunks: usize) -> usize {
+        prefetch_chunks.min(self.config.backend.max_prefetch_chunks as usize)
+    }
+
+    /// Reader creates the reader of the object. If the read buffer size is provided, it is used as
+    /// the chunk size of the reader, otherwise the default chunk size of opendal is used. If the
+    /// prefetch chunks is not 0, the chunks are fetched concurrently ahead of the read cursor,
+    /// and at most the prefetch chunks are buffered. If the ETag is provided, the read fails
+    /// when the ETag of the object does not match. If the version id is provided, the specific
+    /// version of the object is read.
+    async fn reader(
+        operator: &Operator,
+        key: &str,
+        read_buffer_size: Option<usize>,
+        prefetch_chunks: usize,
+        if_match: Option<&str>,
+        version_id: Option<&str>,
+    ) -> opendal::Result<opendal::Reader> {
+        let mut reader = operator.reader_with(key);
+        if let Some(read_buffer_size) = read_buffer_size {
+            reader = reader.chunk(read_buffer_size);
+        }
+
+        // The prefetch requires the chunk size, which bounds the memory of the buffered chunks.
+        if prefetch_chunks > 0 {
+            reader = reader
+                .chunk(read_buffer_size.unwrap_or(Self::DEFAULT_PREFETCH_CHUNK_SIZE))
+                .concurrent(prefetch_chunks);
+        }
+
+        if let Some(if_match) = if_match {
+            reader = reader.if_match(if_match);
+        }
+
+        if let Some(version_id) = version_id {
+            reader = reader.version(version_id);
+        }
+
+        reader.await
+    }
+
+    /// Stat with version stats the object, and stats the specific version of the object if the
+    /// version id is provided.
+    async fn stat_with_version(
+        operator: &Operator,
+        key: &str,
+        version_id: Option<&str>,
+    ) -> opendal::Result<Metadata> {
+        let mut stat = operator.stat_with(key);
+        if let Some(version_id) = version_id {
+            stat = stat.version(version_id);
+        }
+
+        stat.await
+    }
+
+    /// Check version returns the Unsupported error if the version of the object is requested,
+    /// but the object storage does not support the versioned reads, instead of reading the
+    /// latest version silently.
+    fn check_version(&self, operator: &Operator, version_id: Option<&str>) -> ClientResult<()> {
+        let capability = operator.info().full_capability();
+        if version_id.is_some() && !(capability.read_with_version && capability.stat_with_version) {
+            error!("{} does not support the versioned object", self.scheme);
+            return Err(ClientError::Unsupported(format!(
+                "{} versioned object",
+                self.scheme
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Absolute range converts the suffix length to the range by the content length of the
+    /// object, and returns the range as is if the suffix length is not provided.
+    async fn absolute_range(
+        operator: &Operator,
+        key: &str,
+        range: Option<common::v2::Range>,
+        suffix_length: Option<u64>,
+        version_id: Option<&str>,
+    ) -> opendal::Result<Option<common::v2::Range>> {
+        let Some(suffix_length) = suffix_length else {
+            return Ok(range);
+        };
+
+        let content_length = Self::stat_with_version(operator, key, version_id)
+            .await?
+            .content_length();
+        Ok(Some(common::v2::Range {
+            start: content_length.saturating_sub(suffix_length),
+            length: suffix_length.min(content_length),
+        }))
+    }
+
+    /// Read buffer reads the content of the object into the buffer of opendal, which holds the
+    /// chunks of the responses as `Bytes` without copying them. It is the fast path of the get
+    /// for the callers that want the bytes, because the AsyncRead path of the get copies each
+    /// chunk into the read buffer of the caller. The whole range is held in memory, so it is
+    /// used for the piece-sized ranges, and the max body size bounds the read without range.
+    #[instrument(skip_all)]
+    pub async fn read_buffer(&self, mut request: GetRequest) -> ClientResult<opendal::Buffer> {
+        // Override the endpoint of the object storage by the endpoint of the request.
+        if let Some(endpoint_override) = request.endpoint_override.take() {
+            request.object_storage =
+                Self::override_endpoint(request.object_storage.take(), endpoint_override)?;
+        }
+
+        let redacted_url = redact_url(&request.url);
+        debug!(
+            "read buffer request {} {}: {:?}",
+            request.piece_id, redacted_url, request.range
+        );
+
+        let url =
+            Url::parse(&request.url).map_err(|_| ClientError::InvalidURI(request.url.clone()))?;
+        let parsed_url = self.parse_url(url).inspect_err(|err| {
+            error!(
+                "parse read buffer request url failed {} {}: {}",
+                request.piece_id, redacted_url, err
+            );
+        })?;
+
+        // Initialize the operator with the parsed URL, object storage, and timeout.
+        let (operator, _) = self.fingerprinted_operator(
+            &parsed_url,
+            request.object_storage,
+            request.expected_bucket_owner.as_deref(),
+            request.timeout,
+        )?;
+        let version_id = request.version_id.as_deref();
+        self.check_version(&operator, version_id)?;
+
+        let result = run_until_cancelled(
+            async {
+                let range = Self::absolute_range(
+                    &operator,
+                    &parsed_url.key,
+                    request.range,
+                    request.suffix_length,
+                    version_id,
+                )
+                .await?;
+
+                let reader = Self::reader(
+                    &operator,
+                    &parsed_url.key,
+                    request.read_buffer_size,
+                    self.prefetch_chunks(request.prefetch_chunks),
+                    None,
+                    version_id,
+                )
+                .await?;
+
+                // Read one more byte than the max body size to detect the oversized body
+                // without reading the whole object.
+                match (range, request.max_body_size) {
+                    (Some(range), _) => reader.read(range.start..range.start + range.length).await,
+                    (None, Some(max_body_size)) => reader.read(..max_body_size + 1).await,
+                    (None, None) => reader.read(..).await,
+                }
+            },
+            request.cancel_token.as_ref(),
+        )
+        .await?;
+
+        let buffer = result.map_err(|err| {
+            error!(
+                "read buffer request failed {} {}: {}",
+                request.piece_id, redacted_url, err
+            );
+
+            Self::opendal_error(err)
+        })?;
+
+        if let Some(max_body_size) = request.max_body_size {
+            if buffer.len() as u64 > max_body_size {
+                error!(
+                    "read buffer request body too large {} {}: {}",
+                    request.piece_id,
+                    redacted_url,
+                    buffer.len()
+                );
+
+                return Err(ClientError::BackendError(Box::new(BackendError {
+                    message: format!(
+                        "response body exceeds the max body size of {} bytes",
+                        max_body_size
+                    ),
+                    status_code: None,
+                    header: None,
+                })));
+            }
+        }
+
+        Ok(buffer)
+    }
+
+    /// Presigned stat header returns the response headers of the presigned stat request, which
+    /// carry the metadata not exposed by opendal. It is best-effort and returns None if the
+    /// request fails.
+    async fn presigned_stat_header(
+        &self,
+        operator: &Operator,
+        key: &str,
+        insecure_skip_verify: Option<bool>,
+        timeout: Duration,
+    ) -> Option<reqwest::header::HeaderMap> {
+        let presigned_request = operator
+            .presign_stat(key, Self::PRESIGN_STAT_EXPIRE)
+            .await
+            .inspect_err(|err| debug!("presign stat failed {}: {}", key, err))
+            .ok()?;
+
+        // Choose the http client using dangerous client or not by insecure_skip_verify.
+        let http_client = match insecure_skip_verify {
+            Some(true) => &self.danger_client,
+            _ => &self.client,
+        };
+
+        let response = http_client
+            .head(presigned_request.uri().to_string())
+            .headers(presigned_request.header().clone())
+            .timeout(timeout)
+            .send()
+            .await
+            .inspect_err(|err| debug!("presigned stat failed {}: {}", key, err))
+            .ok()?;
+
+        if !response.status().is_success() {
+            debug!("presigned stat failed {}: {}", key, response.status());
+            return None;
+        }
+
+        Some(response.headers().clone())
+    }
+
+    /// Storage class returns the storage class of the object for S3 and GCS. The storage class is
+    /// not exposed by the metadata of opendal, so it is read from the response headers of the
+    /// presigned stat request, which is only sent if the StorageClass metakey is requested. It
+    /// is best-effort and returns None if the request fails.
+    async fn storage_class(
+        &self,
+        operator: &Operator,
+        key: &str,
+        insecure_skip_verify: Option<bool>,
+        timeout: Duration,
+    ) -> Option<String> {
+        if !matches!(self.scheme, Scheme::S3 | Scheme::GCS) {
+            return None;
+        }
+
+        let header = self
+            .presigned_stat_header(operator, key, insecure_skip_verify, timeout)
+            .await?;
+
+        let storage_class = Self::STORAGE_CLASS_HEADERS.iter().find_map(|name| {
+            header
+                .get(*name)
+                .and_then(|value| value.to_str().ok())
+                .map(|value| value.to_string())
+        });
+
+        // S3 omits the storage class header for the objects of the STANDARD storage class.
+        match (storage_class, &self.scheme) {
+            (None, Scheme::S3) => Some("STANDARD".to_string()),
+            (storage_class, _) => storage_class,
+        }
+    }
+
+    /// Checksum get gets the whole object by the presigned request if the checksum validation
+    /// is enabled, and returns the response with the checksum of the whole object declared by
+    /// S3 or GCS. The checksum is read from the response of the get itself, so the object
+    /// overwritten after the stat is never verified by the stale checksum. S3 returns the
+    /// checksums only if the checksum mode is enabled by the request, so the request is signed
+    /// again with the checksum mode header. It is best-effort and returns None if the presign
+    /// or the request fails, and the object is read by the operator without the verification.
+    async fn checksum_get(
+        &self,
+        operator: &Operator,
+        parsed_url: &ParsedURL,
+        object_storage: Option<common::v2::ObjectStorage>,
+    ) -> Option<(reqwest::Response, Option<ServerChecksum>)> {
+        if !self
+            .config
+            .backend
+            .enable_object_storage_checksum_validation
+            || !matches!(self.scheme, Scheme::S3 | Scheme::GCS)
+        {
+            return None;
+        }
+
+        let presigned_request = operator
+            .presign_read(&parsed_url.key, Self::PRESIGN_GET_EXPIRE)
+            .await
+            .inspect_err(|err| debug!("presign get failed {}: {}", parsed_url.key, err))
+            .ok()?;
+
+        // Choose the http client using dangerous client or not by insecure_skip_verify.
+        let object_storage = self
+            .resolve_object_storage(parsed_url, object_storage)
+            .ok()?;
+        let http_client = match object_storage.insecure_skip_verify {
+            Some(true) => &self.danger_client,
+            _ => &self.client,
+        };
+
+        let mut request = http::Request::get(presigned_request.uri().clone())
+            .body(())
+            .ok()?;
+        *request.headers_mut() = presigned_request.header().clone();
+        if self.scheme == Scheme::S3 {
+            Self::sign_checksum_mode(&mut request, &object_storage)
+                .inspect_err(|err| debug!("sign get failed {}: {}", parsed_url.key, err))
+                .ok()?;
+        }
+
+        let response = http_client
+            .get(request.uri().to_string())
+            .headers(request.headers().clone())
+            .send()
+            .await
+            .inspect_err(|err| debug!("presigned get failed {}: {}", parsed_url.key, err))
+            .ok()?;
+
+        if !response.status().is_success() {
+            debug!(
+                "presigned get failed {}: {}",
+                parsed_url.key,
+                response.status()
+            );
+            return None;
+        }
+
+        let checksum = ServerChecksum::from_header(response.headers());
+        Some((response, checksum))
+    }
+
+    /// Sign checksum mode replaces the query signature of the presigned S3 request by the
+    /// signature of the headers including the checksum mode header, because S3 rejects the
+    /// unsigned `x-amz-*` headers.
+    fn sign_checksum_mode(
+        request: &mut http::Request<()>,
+        object_storage: &common::v2::ObjectStorage,
+    ) -> ClientResult<()> {
+        let (Some(access_key_id), Some(access_key_secret), Some(region)) = (
+            &object_storage.access_key_id,
+            &object_storage.access_key_secret,
+            &object_storage.region,
+        ) else {
+            return Err(ClientError::Unsupported(
+                "s3 checksum mode without credentials".to_string(),
+            ));
+        };
+
+        // Remove the query signature of the presigned request, such as `X-Amz-Signature`.
+        let mut parts = request.uri().clone().into_parts();
+        if let Some(path_and_query) = &parts.path_and_query {
+            let query = path_and_query
+                .query()
+                .unwrap_or_default()
+                .split('&')
+                .filter(|pair| !pair.is_empty() && !pair.to_ascii_lowercase().starts_with("x-amz-"))
+                .collect::<Vec<_>>()
+                .join("&");
+            let path_and_query = match query.is_empty() {
+                true => path_and_query.path().to_string(),
+                false => format!("{}?{}", path_and_query.path(), query),
+            };
+
+            parts.path_and_query = Some(path_and_query.parse().or_err(ErrorType::ParseError)?);
+        }
+
+        *request.uri_mut() = http::Uri::from_parts(parts).or_err(ErrorType::ParseError)?;
+        request.headers_mut().insert(
+            X_AMZ_CHECKSUM_MODE,
+            http::HeaderValue::from_static("ENABLED"),
+        );
+
+        reqsign::AwsV4Signer::new("s3", region)
+            .sign(
+                request,
+                &reqsign::AwsCredential {
+                    access_key_id: access_key_id.clone(),
+                    secret_access_key: access_key_secret.clone(),
+                    session_token: object_storage.session_token.clone(),
+                    expires_in: None,
+                },
+            )
+            .or_err(ErrorType::ValidationError)?;
+
+        Ok(())
+    }
+
+    /// Sniff content type detects the content type by the magic bytes of the head of the object,
+    /// which is used when the stored metadata has no content type. It is best-effort and returns
+    /// None if the read fails or the content type is unknown.
+    async fn sniff_content_type(
+        operator: &Operator,
+        key: &str,
+        content_length: u64,
+    ) -> Option<String> {
+        if content_length == 0 {
+            return None;
+        }
+
+        let buffer = operator
+            .read_with(key)
+            .range(0..content_length.min(Self::SNIFF_CONTENT_TYPE_LENGTH))
+            .await
+            .inspect_err(|err| debug!("sniff content type failed {}: {}", key, err))
+            .ok()?;
+
+        infer::get(&buffer.to_vec()).map(|kind| kind.mime_type().to_string())
+    }
+
+    /// Put error converts the error of the put request, the condition not match error of the
+    /// conditional write means the object already exists.
+    fn put_error(err: opendal::Error, url: &str) -> ClientError {
+        match err.kind() {
+            opendal::ErrorKind::ConditionNotMatch => {
+                ClientError::PreconditionFailed(format!("{} already exists", url))
+            }
+            _ => Self::opendal_error(err),
+        }
+    }
+
+    /// Stat failure returns the failed stat response with the failure class classified by the
+    /// opendal error, as the HTTP backend does, so the caller can tell the missing object from
+    /// the rejected credentials. The throttled, the clock skew and the temporary failures are
+    /// returned as the errors, which are retried or failed over by the callers.
+    fn stat_failure(err: &opendal::Error) -> ClientResult<StatResponse> {
+        match Self::opendal_error(err) {
+            ClientError::BackendError(backend_error) => Ok(StatResponse {
+                success: false,
+                content_length: None,
+                http_header: backend_error.header,
+                http_status_code: backend_error.status_code,
+                error_message: Some(backend_error.message),
+                entries: Vec::new(),
+                user_metadata: HashMap::new(),
+                next_continuation_token: None,
+                storage_class: None,
+                content_type: None,
+                accept_ranges: false,
+                suggested_filename: None,
+                truncated: false,
+                is_dir: false,
+                failure_class: Some(FailureClass::from_error(err)),
+                etag: None,
+            }),
+            err => Err(err),
+        }
+    }
+
+    /// Opendal error converts the error of opendal to the client error. The errors of the
+    /// responses classified by the ResponseErrorFetcher are converted to the Throttled error
+    /// with the Retry-After hint and the ClockSkew error with the server time, and the other
+    /// rate limited errors, such as the `SlowDown` of S3, are converted to the Throttled error
+    /// without the hint. The temporary errors, such as the connection resets, are kept as the
+    /// opendal errors, so they are classified as retryable.
+    fn opendal_error(err: impl Borrow<opendal::Error>) -> ClientError {
+        let err = err.borrow();
+        let response_error = std::error::Error::source(err)
+            .and_then(|source| source.downcast_ref::<ResponseError>());
+        match response_error {
+            Some(ResponseError::Throttled { retry_after }) => {
+                return ClientError::Throttled {
+                    retry_after: *retry_after,
+                };
+            }
+            Some(ResponseError::ClockSkew { server_time }) => {
+                return ClientError::ClockSkew {
+                    server_time: Some(*server_time),
+                };
+            }
+            None => {}
+        }
+
+        if err.kind() == opendal::ErrorKind::RateLimited {
+            return ClientError::Throttled { retry_after: None };
+        }
+
+        if err.is_temporary() {
+            return ClientError::OpenDALError(
+                opendal::Error::new(err.kind(), err.to_string()).set_temporary(),
+            );
+        }
+
+        ClientError::BackendError(Box::new(BackendError::from_opendal(err)))
+    }
+
+    /// Singleflight stat stats the object, and the concurrent stats of the same object share
+    /// the in-flight stat, so only one request is issued. It is distinct from the stat cache,
+    /// the completed stat is not reused. The flight key includes the fingerprint of the
+    /// operator, so the stats with the different endpoints or credentials are not shared.
+    async fn singleflight_stat(
+        &self,
+        operator: &Operator,
+        fingerprint: &str,
+        parsed_url: &ParsedURL,
+        version_id: Option<&str>,
+    ) -> Result<Metadata, Arc<opendal::Error>> {
+        let flight_key = format!(
+            "{}/{}?{}",
+            fingerprint,
+            parsed_url.key,
+            version_id.unwrap_or_default()
+        );
+
+        let stat = {
+            let mut inflight_stats = self.inflight_stats.lock();
+            let entry = inflight_stats.entry(flight_key.clone()).or_insert_with(|| {
+                let operator = operator.clone();
+                let key = parsed_url.key.clone();
+                let version_id = version_id.map(str::to_string);
+                InflightStatEntry {
+                    stat: async move {
+                        Self::stat_with_version(&operator, &key, version_id.as_deref())
+                            .await
+                            .map_err(Arc::new)
+                    }
+                    .boxed()
+                    .shared(),
+                    waiters: 0,
+                }
+            });
+
+            entry.waiters += 1;
+            entry.stat.clone()
+        };
+
+        let mut guard = InflightStatGuard {
+            inflight_stats: &self.inflight_stats,
+            flight_key,
+            stat: stat.clone(),
+            completed: false,
+        };
+
+        let result = stat.await;
+        guard.completed = true;
+        result
+    }
+
+    /// Cached stat returns the stat of the object from the stat cache, and stats the object if
+    /// the cached stat is missing or expired.
+    async fn cached_stat(
+        &self,
+        stat_cache: &Mutex<LruCache<String, StatCacheEntry>>,
+        operator: &Operator,
+        url: &str,
+        key: &str,
+    ) -> opendal::Result<StatCacheEntry> {
+        if let Some(entry) = stat_cache.lock().get(url) {
+            if entry.cached_at.elapsed() < self.config.backend.object_storage_stat_cache_ttl {
+                return Ok(entry.clone());
+            }
+        }
+
+        let metadata = operator.stat_with(key).await?;
+        let entry = StatCacheEntry {
+            etag: metadata.etag().map(|etag| etag.to_string()),
+            content_length: metadata.content_length(),
+            cached_at: Instant::now(),
+        };
+
+        stat_cache.lock().put(url.to_string(), entry.clone());
+        Ok(entry)
+    }
+
+    /// Validate credentials validates the credentials of the object storage for the bucket,
+    /// which lists the bucket root with limit 1 as the minimal authenticated operation. It
+    /// returns Unauthorized error if the credentials are denied by the object storage, so the
+    /// bad credentials are distinguished from the network errors.
+    #[instrument(skip_all)]
+    pub async fn validate_credentials(
+        &self,
+        object_storage: common::v2::ObjectStorage,
+        bucket: &str,
+    ) -> ClientResult<()> {
+        let raw_url = format!("{}://{}/", self.scheme, bucket);
+        let url: Url = raw_url
+            .parse()
+            .map_err(|_| ClientError::InvalidURI(raw_url.clone()))?;
+        let parsed_url: ParsedURL = url.try_into()?;
+
+        let operator = self.operator(
+            &parsed_url,
+            Some(object_storage),
+            Self::DEFAULT_VALIDATE_CREDENTIALS_TIMEOUT,
+        )?;
+
+        let result = match operator.lister_with(&parsed_url.key).limit(1).await {
+            Ok(mut lister) => lister.try_next().await.map(|_| ()),
+            Err(err) => Err(err),
+        };
+
+        result.map_err(|err| {
+            error!("validate credentials failed {}: {}", raw_url, err);
+            if err.kind() == opendal::ErrorKind::PermissionDenied {
+                return ClientError::Unauthorized;
+            }
+
+            ClientError::BackendError(Box::new(BackendError::from_opendal(&err)))
+        })
+    }
+
+    /// Copy object copies the object from the source key to the destination key. If the
+    /// destination operator is None, the object is copied in the source operator by the
+    /// server-side copy, otherwise the content is streamed from the source operator to the
+    /// destination operator.
+    async fn copy_object(
+        &self,
+        source_operator: &Operator,
+        source_key: &str,
+        dest_operator: Option<&Operator>,
+        dest_key: &str,
+    ) -> ClientResult<()> {
+        let dest_operator = match dest_operator {
+            Some(dest_operator) => dest_operator,
+            None => match source_operator.copy(source_key, dest_key).await {
+                Ok(()) => return Ok(()),
+                Err(err) if err.kind() == opendal::ErrorKind::Unsupported => {
+                    debug!(
+                        "server-side copy is unsupported, fall back to stream copy {} {}",
+                        source_key, dest_key
+                    );
+
+                    source_operator
+                }
+                Err(err) => {
+                    error!("copy failed {} {}: {}", source_key, dest_key, err);
+                    return Err(err.into());
+                }
+            },
+        };
+
+        let chunk_size = self.config.backend.put_chunk_size.as_u64();
+        let content_length = source_operator
+            .stat(source_key)
+            .await
+            .inspect_err(|err| {
+                error!("stat source object failed {}: {}", source_key, err);
+            })?
+            .content_length();
+
+        let source_reader = source_operator
+            .reader_with(source_key)
+            .concurrent(self.config.backend.put_concurrent_chunk_count as usize)
+            .chunk(chunk_size as usize)
+            .await?;
+
+        let mut dest_writer = dest_operator
+            .writer_with(dest_key)
+            .concurrent(self.config.backend.put_concurrent_chunk_count as usize)
+            .chunk(chunk_size as usize)
+            .await?;
+
+        let mut offset: u64 = 0;
+        while offset < content_length {
+            let end = std::cmp::min(offset + chunk_size, content_length);
+            let buf = source_reader.read(offset..end).await.inspect_err(|err| {
+                error!("read source object failed {}: {}", source_key, err);
+            })?;
+
+            dest_writer.write(buf).await.inspect_err(|err| {
+                error!("write destination object failed {}: {}", dest_key, err);
+            })?;
+
+            offset = end;
+        }
+
+        dest_writer.close().await.inspect_err(|err| {
+            error!("close destination object failed {}: {}", dest_key, err);
+        })?;
+
+        Ok(())
+    }
+}
+
+/// Backend implements the Backend trait.
+#[async_trait]
+impl crate::Backend for ObjectStorage {
+    /// Scheme returns the scheme of the object storage.
+    fn scheme(&self) -> String {
+        self.scheme.to_string()
+    }
+
+    /// Stat the metadata from the backend.
+    #[instrument(skip_all)]
+    async fn stat(&self, mut request: StatRequest) -> ClientResult<StatResponse> {
+        // Link the span of the stat to the trace of the caller.
+        set_trace_parent(request.trace_context.as_ref());
+
+        // Override the endpoint of the object storage by the endpoint of the request.
+        if let Some(endpoint_override) = request.endpoint_override.take() {
+            request.object_storage =
+                Self::override_endpoint(request.object_storage.take(), endpoint_override)?;
+        }
+
+        // Fail over between the endpoints if the multiple endpoints are configured.
+        if let Some(endpoints) = self.failover_endpoints(request.object_storage.as_ref()) {
+            return self
+                .failover(endpoints, request.retry_budget.as_deref(), |endpoint| {
+                    self.stat(StatRequest {
+                        object_storage: Self::with_endpoint(&request.object_storage, endpoint),
+                        ..request.clone()
+                    })
+                })
+                .await;
+        }
+
+        // Deposit the retry budget by the request of each endpoint, which is withdrawn by the
+        // failover.
+        if let Some(retry_budget) = &request.retry_budget {
+            retry_budget.deposit();
+        }
+
+        let redacted_url = redact_url(&request.url);
+        debug!(
+            "stat request {} {}: {:?}",
+            request.task_id, redacted_url, request.http_header
+        );
+
+        // Parse the URL and convert it to a ParsedURL for create the ObjectStorage operator.
+        let url: Url = request
+            .url
+            .parse()
+            .map_err(|_| ClientError::InvalidURI(request.url.clone()))?;
+
+        let parsed_url = self.parse_url(url).inspect_err(|err| {
+            error!(
+                "parse stat request url failed {} {}: {}",
+                request.task_id, redacted_url, err
+            );
+        })?;
+
+        // Initialize the operator with the parsed URL, object storage, and timeout.
+        let insecure_skip_verify = request
+            .object_storage
+            .as_ref()
+            .and_then(|object_storage| object_storage.insecure_skip_verify);
+        let (operator, fingerprint) = self.fingerprinted_operator(
+            &parsed_url,
+            request.object_storage.clone(),
+            request.expected_bucket_owner.as_deref(),
+            request.timeout,
+        )?;
+
+        // Get the entries if url point to a directory.
+        if parsed_url.is_dir() {
+            return self
+                .stat_dir(&operator, &parsed_url, &request, &redacted_url)
+                .await;
+        }
+
+        // Stat the object to get the response from the ObjectStorage, the concurrent stats of
+        // the same object share the in-flight stat. The stat is bounded by the stat timeout, so
+        // the hung metadata call fails fast regardless of the timeout of the transfer.
+        self.check_version(&operator, request.version_id.as_deref())?;
+        let stat_timeout = request.stat_timeout.unwrap_or(request.timeout);
+        let response = match tokio::time::timeout(
+            stat_timeout,
+            run_until_cancelled(
+                self.singleflight_stat(
+                    &operator,
+                    &fingerprint,
+                    &parsed_url,
+                    request.version_id.as_deref(),
+                ),
+                request.cancel_token.as_ref(),
+            ),
+        )
+        .await
+        .inspect_err(|_| {
+            error!(
+                "stat request timed out {} {} after {:?}",
+                request.task_id, redacted_url, stat_timeout
+            );
+        })?? {
+            Ok(response) => response,
+            Err(err) => {
+                // The URL without the trailing slash may address a prefix instead of an object,
+                // such as `s3://bucket/dir`, so fall back to the listing if any object exists
+                // under the prefix.
+                if err.kind() == opendal::ErrorKind::NotFound
+                    && request.version_id.is_none()
+                    && Self::is_prefix(&operator, &parsed_url.key)
+                        .await
+                        .unwrap_or(false)
+                {
+                    debug!(
+                        "stat request resolved as directory {} {}",
+                        request.task_id, redacted_url
+                    );
+
+                    return self
+                        .stat_dir(&operator, &parsed_url.to_dir(), &request, &redacted_url)
+                        .await;
+                }
+
+                error!(
+                    "stat request failed {} {}: {}",
+                    request.task_id, redacted_url, err
+                );
+
+                return Self::stat_failure(err.as_ref());
+            }
+        };
+
+        // Get the storage class of the object if it is requested, which detects the archived
+        // objects before get at the cost of an extra request.
+        let storage_class = if request.list_metakeys.contains(&ListMetakey::StorageClass) {
+            self.storage_class(
+                &operator,
+                &parsed_url.key,
+                insecure_skip_verify,
+                request.timeout,
+            )
+            .await
+        } else {
+            None
+        };
+
+        // Sniff the content type by the magic bytes if the stored metadata has no content type.
+        let content_type = match response.content_type() {
+            Some(content_type) => Some(content_type.to_string()),
+            None if request.sniff_content_type => {
+                Self::sniff_content_type(&operator, &parsed_url.key, response.content_length())
+                    .await
+            }
+            None => None,
+        };
+
+        debug!(
+            "stat response {} {}: {} {:?} {:?}",
+            request.task_id,
+            redacted_url,
+            response.content_length(),
+            storage_class,
+            content_type
+        );
+
+        Ok(StatResponse {
+            success: true,
+            content_length: Some(response.content_length()),
+            http_header: None,
+            http_status_code: None,
+            error_message: None,
+            entries: Vec::new(),
+            user_metadata: response.user_metadata().cloned().unwrap_or_default(),
+            next_continuation_token: None,
+            storage_class,
+            content_type,
+            accept_ranges: true,
+            suggested_filename: None,
+            truncated: false,
+            is_dir: false,
+            failure_class: None,
+            etag: response.etag().map(str::to_string),
+        })
+    }
+
+    /// Get the content from the backend.
+    #[instrument(skip_all)]
+    async fn get(&self, mut request: GetRequest) -> ClientResult<GetResponse<Body>> {
+        // Link the span of the get to the trace of the caller.
+        set_trace_parent(request.trace_context.as_ref());
+
+        // Override the endpoint of the object storage by the endpoint of the request.
+        if let Some(endpoint_override) = request.endpoint_override.take() {
+            request.object_storage =
+                Self::override_endpoint(request.object_storage.take(), endpoint_override)?;
+        }
+
+        // Fail over between the endpoints if the multiple endpoints are configured.
+        if let Some(endpoints) = self.failover_endpoints(request.object_storage.as_ref()) {
+            return self
+                .failover(endpoints, request.retry_budget.as_deref(), |endpoint| {
+                    self.get(GetRequest {
+                        object_storage: Self::with_endpoint(&request.object_storage, endpoint),
+                        ..request.clone()
+                    })
+                })
+                .await;
+        }
+
+        // Deposit the retry budget by the request of each endpoint, which is withdrawn by the
+        // failover and the retries of the interrupted reads.
+        if let Some(retry_budget) = &request.retry_budget {
+            retry_budget.deposit();
+        }
+
+        let redacted_url = redact_url(&request.url);
+        debug!(
+            "get request {} {}: {:?}",
+            request.piece_id, redacted_url, request.http_header
+        );
+
+        // Parse the URL and convert it to a ParsedURL for create the ObjectStorage operator.
+        let url: Url = request
+            .url
+            .parse()
+            .map_err(|_| ClientError::InvalidURI(request.url.clone()))?;
+
+        let parsed_url = self.parse_url(url).inspect_err(|err| {
+            error!(
+                "parse get request url failed {} {}: {}",
+                request.piece_id, redacted_url, err
+            );
+        })?;
+
+        // Initialize the operator with the parsed URL, object storage, and timeout.
+        let (operator, _) = self.fingerprinted_operator(
+            &parsed_url,
+            request.object_storage.clone(),
+            request.expected_bucket_owner.as_deref(),
+            request.timeout,
+        )?;
+
+        // Convert the suffix range to the range by the content length of the object, because
+        // the object storage reader requires the absolute range.
+        let version_id = request.version_id.as_deref();
+        self.check_version(&operator, version_id)?;
+        let range = Self::absolute_range(
+            &operator,
+            &parsed_url.key,
+            request.range,
+            request.suffix_length,
+            version_id,
+        )
+        .await
+        .map_err(|err| {
+            error!(
+                "stat request failed {} {}: {}",
+                request.piece_id, redacted_url, err
+            );
+
+            Self::opendal_error(err)
+        })?;
+
+        // Get the stat of the object from the stat cache for the ranged get, which avoids
+        // stating the same object for each piece. The stat cache is keyed by the URL, so it is
+        // skipped for the versioned get.
+        let cached_stat = match (&self.stat_cache, &range) {
+            (Some(stat_cache), Some(range)) if version_id.is_none() => {
+                let cached_stat = self
+                    .cached_stat(stat_cache, &operator, &request.url, &parsed_url.key)
+                    .await
+                    .map_err(|err| {
+                        error!(
+                            "stat request failed {} {}: {}",
+                            request.piece_id, redacted_url, err
+                        );
+
+                        Self::opendal_error(err)
+                    })?;
+
+                // Validate the range by the content length of the object.
+                if range.start + range.length > cached_stat.content_length {
+                    error!(
+                        "get request range out of bounds {} {}: {:?}",
+                        request.piece_id, redacted_url, range
+                    );
+
+                    return Err(ClientError::BackendError(Box::new(BackendError {
+                        message: format!(
+                            "range {}-{} out of bounds of content length {}",
+                            range.start,
+                            range.start + range.length,
+                            cached_stat.content_length
+                        ),
+                        status_code: None,
+                        header: None,
+                    })));
+                }
+
+                Some(cached_stat)
+            }
+            _ => None,
+        };
+
+        let operator_reader = Self::reader(
+            &operator,
+            &parsed_url.key,
+            request.read_buffer_size,
+            self.prefetch_chunks(request.prefetch_chunks),
+            cached_stat
+                .as_ref()
+                .and_then(|cached_stat| cached_stat.etag.as_deref()),
+            version_id,
+        )
+        .await
+        .map_err(|err| {
+            error!(
+                "get request failed {} {}: {}",
+                request.piece_id, redacted_url, err
+            );
+
+            Self::opendal_error(err)
+        })?;
+
+        // The object storage does not support the multiple ranges in a single request, so the
+        // ranges are fetched concurrently by the single range requests.
+        if !request.ranges.is_empty() {
+            let range_readers = future::try_join_all(request.ranges.iter().map(|range| {
+                operator_reader
+                    .clone()
+                    .into_bytes_stream(range.start..range.start + range.length)
+            }))
+            .await
+            .map_err(|err| {
+                error!(
+                    "get request failed {} {}: {}",
+                    request.piece_id, redacted_url, err
+                );
+
+                Self::opendal_error(err)
+            })?
+            .into_iter()
+            .map(|stream| {
+                cancel_body(
+                    guard_body(
+                        Box::new(StreamReader::new(InterruptedRetryStream::new(
+                            stream,
+                            request.retry_budget.clone(),
+                        ))),
+                        request.min_throughput,
+                        request.min_throughput_window,
+                    ),
+                    request.cancel_token.clone(),
+                )
+            })
+            .collect();
+
+            return Ok(crate::GetResponse {
+                success: true,
+                http_header: None,
+                http_status_code: Some(reqwest::StatusCode::OK),
+                reader: Box::new(tokio::io::empty()),
+                error_message: None,
+                piece_digests: None,
+                range_readers,
+                suggested_filename: None,
+                content_length: None,
+                total_length: None,
+            });
+        }
+
+        // The content length is the length of the range, or the content length of the object
+        // stated for the get of the whole object.
+        let content_length = match &range {
+            Some(range) => range.length,
+            None => Self::stat_with_version(&operator, &parsed_url.key, version_id)
+                .await
+                .map_err(|err| {
+                    error!(
+                        "stat request failed {} {}: {}",
+                        request.piece_id, redacted_url, err
+                    );
+
+                    Self::opendal_error(err)
+                })?
+                .content_length(),
+        };
+
+        // Get the whole object by the presigned request if the checksum validation is enabled,
+        // so the object is verified against the checksum declared by the same response. The
+        // checksum of the whole object can not verify the range of the object.
+        let checksum_response = match range {
+            None if version_id.is_none() => {
+                self.checksum_get(&operator, &parsed_url, request.object_storage)
+                    .await
+            }
+            _ => None,
+        };
+
+        let mut server_checksum = None;
+        let stream: Pin<Box<dyn Stream<Item = std::io::Result<Bytes>> + Send>> =
+            match (checksum_response, range) {
+                (Some((response, checksum)), _) => {
+                    server_checksum = checksum;
+                    Box::pin(response.bytes_stream().map_err(std::io::Error::other))
+                }
+                (None, range) if request.adaptive_chunking => {
+                    let start = range.as_ref().map_or(0, |range| range.start);
+                    Box::pin(AdaptiveChunkStream::new(
+                        operator_reader,
+                        start..start + content_length,
+                    ))
+                }
+                (None, Some(range)) => Box::pin(
+                    operator_reader
+                        .into_bytes_stream(range.start..range.start + range.length)
+                        .await
+                        .map_err(|err| {
+                            error!(
+                                "get request failed {} {}: {}",
+                                request.piece_id, redacted_url, err
+                            );
+
+                            Self::opendal_error(err)
+                        })?,
+                ),
+                (None, None) => {
+                    Box::pin(operator_reader.into_bytes_stream(..).await.map_err(|err| {
+                        error!(
+                            "get request failed {} {}: {}",
+                            request.piece_id, redacted_url, err
+                        );
+
+                        Self::opendal_error(err)
+                    })?)
+                }
+            };
+
+        // Retry the interrupted reads in place, and invalidate the cached stat if the ETag of
+        // the object changes between pieces, so the next get stats the object again.
+        let stat_cache = cached_stat.and(self.stat_cache.clone());
+        let url = request.url.clone();
+        let stream = InterruptedRetryStream::new(stream, request.retry_budget.clone()).inspect_err(
+            move |err| {
+                let Some(stat_cache) = &stat_cache else {
+                    return;
+                };
+
+                let condition_not_match = err
+                    .get_ref()
+                    .and_then(|err| err.downcast_ref::<opendal::Error>())
+                    .is_some_and(|err| err.kind() == opendal::ErrorKind::ConditionNotMatch);
+                if condition_not_match {
+                    debug!(
+                        "invalidate cached stat of {}, because etag changed",
+                        redacted_url
+                    );
+                    stat_cache.lock().pop(&url);
+                }
+            },
+        );
+
+        // Verify the whole object against the checksum declared by the object storage.
+        let mut body: Body = Box::new(StreamReader::new(stream));
+        if let Some(checksum) = server_checksum {
+            debug!(
+                "verify get request {} {} by server checksum {:?}",
+                request.piece_id, redacted_url, checksum
+            );
+            body = Box::new(ChecksumReader::new(body, checksum));
+        }
+
+        let (reader, piece_digests) = digest_body(
+            limit_body(
+                decompress_body(
+                    cancel_body(
+                        guard_body(body, request.min_throughput, request.min_throughput_window),
+                        request.cancel_token,
+                    ),
+                    request.auto_decompress,
+                ),
+                request.max_body_size,
+            ),
+            request.piece_length,
+        );
+
+        Ok(crate::GetResponse {
+            success: true,
+            http_header: None,
+            http_status_code: Some(reqwest::StatusCode::OK),
+            reader,
+            error_message: None,
+            piece_digests,
+            range_readers: Vec::new(),
+            suggested_filename: None,
+            content_length: Some(content_length).filter(|_| !request.auto_decompress),
+            total_length: None,
+        })
+    }
+
+    /// Put the content to the backend.
+    #[instrument(skip_all)]
+    async fn put(&self, request: PutRequest) -> ClientResult<PutResponse> {
+        // Fail over between the endpoints if the multiple endpoints are configured.
+        if let Some(endpoints) = self.failover_endpoints(request.object_storage.as_ref()) {
+            return self
+                .failover(endpoints, None, |endpoint| {
+                    self.put(PutRequest {
+                        object_storage: Self::with_endpoint(&request.object_storage, endpoint),
+                        ..request.clone()
+                    })
+                })
+                .await;
+        }
+
+        debug!("put request {:?} {}", request.path, request.url);
+        let mut object_storage_writer = self.writer(&request).await?;
+
+        // Initialize the fs operator to read the local file.
+        let fs_operator = Operator::new(opendal::services::Fs::default().root("/"))
+            .inspect_err(|err| {
+                error!("initialize fs operator failed: {}", err);
+            })?
+            .finish();
+
+        let fs_reader = fs_operator
+            .reader_with(&request.path.to_string_lossy())
+            .concurrent(self.config.backend.put_concurrent_chunk_count as usize)
+            .chunk(self.config.backend.put_chunk_size.as_u64() as usize)
+            .await?;
+
+        let content_length = fs_operator
+            .stat(&request.path.to_string_lossy())
+            .await
+            .inspect_err(|err| {
+                error!(
+                    "stat local file failed {:?} {}: {}",
+                    request.path, request.url, err
+                );
+            })?
+            .content_length();
+
+        let mut offset: u64 = 0;
+        while offset < content_length {
+            let end = std::cmp::min(
+                offset + self.config.backend.put_chunk_size.as_u64(),
+                content_length,
+            );
+
+            let buf = fs_reader.read(offset..end).await.inspect_err(|err| {
+                error!(
+                    "read local file failed {:?} {}: {}",
+                    request.path, request.url, err
+                );
+            })?;
+
+            object_storage_writer.write(buf).await.map_err(|err| {
+                error!(
+                    "put request failed {:?} {}: {}",
+                    request.path, request.url, err
+                );
+
+                Self::put_error(err, &request.url)
+            })?;
+
+            offset = end;
+        }
+
+        object_storage_writer.close().await.map_err(|err| {
+            error!(
+                "close put request failed {:?} {}: {}",
+                request.path, request.url, err
+            );
+
+            Self::put_error(err, &request.url)
+        })?;
+
+        Ok(crate::PutResponse {
+            success: true,
+            http_header: None,
+            http_status_code: Some(reqwest::StatusCode::OK),
+            content_length: Some(content_length),
+            error_message: None,
+        })
+    }
+
+    /// Put reader streams the content of the reader to the backend in chunks. The reader can not
+    /// be replayed, so the put is sent to the healthiest endpoint without the failover.
+    #[instrument(skip_all)]
+    async fn put_reader(
+        &self,
+        mut request: PutRequest,
+        mut reader: Body,
+        _content_length: Option<u64>,
+    ) -> ClientResult<PutResponse> {
+        if let Some(endpoint) = self
+            .failover_endpoints(request.object_storage.as_ref())
+            .and_then(|endpoints| endpoints.into_iter().next())
+        {
+            request.object_storage = Self::with_endpoint(&request.object_storage, endpoint);
+        }
+
+        debug!("put reader request {} {}", request.task_id, request.url);
+        let mut object_storage_writer = self.writer(&request).await?;
+
+        let mut content_length: u64 = 0;
+        let mut buf = vec![0; self.config.backend.put_chunk_size.as_u64() as usize];
+        loop {
+            let n = reader.read(&mut buf).await.inspect_err(|err| {
+                error!(
+                    "read put reader failed {} {}: {}",
+                    request.task_id, request.url, err
+                );
+            })?;
+            if n == 0 {
+                break;
+            }
+
+            object_storage_writer
+                .write(Bytes::copy_from_slice(&buf[..n]))
+                .await
+                .map_err(|err| {
+                    error!(
+                        "put reader request failed {} {}: {}",
+                        request.task_id, request.url, err
+                    );
+
+                    Self::put_error(err, &request.url)
+                })?;
+            content_length += n as u64;
+        }
+
+        object_storage_writer.close().await.map_err(|err| {
+            error!(
+                "close put reader request failed {} {}: {}",
+                request.task_id, request.url, err
+            );
+
+            Self::put_error(err, &request.url)
+        })?;
+
+        Ok(crate::PutResponse {
+            success: true,
+            http_header: None,
+            http_status_code: Some(reqwest::StatusCode::OK),
+            content_length: Some(content_length),
+            error_message: None,
+        })
+    }
+
+    /// Exists checks whether the file exists in the backend.
+    #[instrument(skip_all)]
+    async fn exists(&self, request: ExistsRequest) -> ClientResult<bool> {
+        // Fail over between the endpoints if the multiple endpoints are configured.
+        if let Some(endpoints) = self.failover_endpoints(request.object_storage.as_ref()) {
+            return self
+                .failover(endpoints, None, |endpoint| {
+                    self.exists(ExistsRequest {
+                        object_storage: Self::with_endpoint(&request.object_storage, endpoint),
+                        ..request.clone()
+                    })
+                })
+                .await;
+        }
+
+        debug!(
+            "exists request {} {}: {:?}",
+            request.task_id, request.url, request.http_header
+        );
+
+        // Parse the URL and convert it to a ParsedURL for create the ObjectStorage operator.
+        let url: Url = request
+            .url
+            .parse()
+            .map_err(|_| ClientError::InvalidURI(request.url.clone()))?;
+
+        let parsed_url = self.parse_url(url).inspect_err(|err| {
+            error!(
+                "parse exists request url failed {} {}: {}",
+                request.task_id, request.url, err
+            );
+        })?;
+
+        // Initialize the operator with the parsed URL, object storage, and timeout.
+        let operator = self.operator(&parsed_url, request.object_storage, request.timeout)?;
+        Ok(operator.exists(&parsed_url.key).await?)
+    }
+
+    /// Copy copies the object from the source url to the destination url in the backend.
+    #[instrument(skip_all)]
+    async fn copy(&self, request: CopyRequest) -> ClientResult<()> {
+        // Fail over between the endpoints if the multiple endpoints are configured.
+        if let Some(endpoints) = self.failover_endpoints(request.object_storage.as_ref()) {
+            return self
+                .failover(endpoints, None, |endpoint| {
+                    self.copy(CopyRequest {
+                        object_storage: Self::with_endpoint(&request.object_storage, endpoint),
+                        ..request.clone()
+                    })
+                })
+                .await;
+        }
+
+        debug!("copy request {} {}", request.source_url, request.dest_url);
+
+        // Parse the source URL and convert it to a ParsedURL for create the ObjectStorage operator.
+        let source_url: Url = request
+            .source_url
+            .parse()
+            .map_err(|_| ClientError::InvalidURI(request.source_url.clone()))?;
+
+        let source_parsed_url = self.parse_url(source_url).inspect_err(|err| {
+            error!(
+                "parse copy request source url failed {}: {}",
+                request.source_url, err
+            );
+        })?;
+
+        // Parse the destination URL and convert it to a ParsedURL for create the ObjectStorage operator.
+        let dest_url: Url = request
+            .dest_url
+            .parse()
+            .map_err(|_| ClientError::InvalidURI(request.dest_url.clone()))?;
+
+        let dest_parsed_url = self.parse_url(dest_url).inspect_err(|err| {
+            error!(
+                "parse copy request destination url failed {}: {}",
+                request.dest_url, err
+            );
+        })?;
+
+        // The source and destination must be in the same object storage.
+        if source_parsed_url.scheme != self.scheme || dest_parsed_url.scheme != self.scheme {
+            error!(
+                "copy request scheme mismatch {} {}",
+                request.source_url, request.dest_url
+            );
+
+            return Err(ClientError::InvalidParameter);
+        }
+
+        // Initialize the operator with the parsed source URL, object storage, and timeout.
+        let source_operator = self.operator(
+            &source_parsed_url,
+            request.object_storage.clone(),
+            request.timeout,
+        )?;
+
+        // Use the server-side copy when the source and destination are in the same bucket,
+        // otherwise stream the content through the client.
+        if source_parsed_url.bucket == dest_parsed_url.bucket {
+            return self
+                .copy_object(
+                    &source_operator,
+                    &source_parsed_url.key,
+                    None,
+                    &dest_parsed_url.key,
+                )
+                .await;
+        }
+
+        let dest_operator =
+            self.operator(&dest_parsed_url, request.object_storage, request.timeout)?;
+        self.copy_object(
+            &source_operator,
+            &source_parsed_url.key,
+            Some(&dest_operator),
+            &dest_parsed_url.key,
+        )
+        .await
+    }
+
+    /// Append appends the content to the object by the append operation of the object storage,
+    /// such as the OSS append object and the ABS append blob. The object storage without the
+    /// append operation, such as S3, returns the Unsupported error.
+    #[instrument(skip_all)]
+    async fn append(&self, request: AppendRequest) -> ClientResult<()> {
+        // Fail over between the endpoints if the multiple endpoints are configured.
+        if let Some(endpoints) = self.failover_endpoints(request.object_storage.as_ref()) {
+            return self
+                .failover(endpoints, None, |endpoint| {
+                    self.append(AppendRequest {
+                        object_storage: Self::with_endpoint(&request.object_storage, endpoint),
+                        ..request.clone()
+                    })
+                })
+                .await;
+        }
+
+        let redacted_url = redact_url(&request.url);
+        debug!(
+            "append request {} {}: {} bytes at {:?}",
+            request.task_id,
+            redacted_url,
+            request.content.len(),
+            request.position
+        );
+
+        // Parse the URL and convert it to a ParsedURL for create the ObjectStorage operator.
+        let url: Url = request
+            .url
+            .parse()
+            .map_err(|_| ClientError::InvalidURI(request.url.clone()))?;
+
+        let parsed_url = self.parse_url(url).inspect_err(|err| {
+            error!(
+                "parse append request url failed {} {}: {}",
+                request.task_id, redacted_url, err
+            );
+        })?;
+
+        // Initialize the operator with the parsed URL, object storage, and timeout.
+        let operator = self.operator(&parsed_url, request.object_storage, request.timeout)?;
+        if !operator.info().full_capability().write_can_append {
+            error!("{} does not support the append", self.scheme);
+            return Err(ClientError::Unsupported(format!("{} append", self.scheme)));
+        }
+
+        // The object storage appends the content at the current length of the object, so the
+        // expected position is checked against the length before the append. The check and the
+        // append are the separate requests, so the check is not atomic with the append.
+        if let Some(position) = request.position {
+            let content_length = match operator.stat(&parsed_url.key).await {
+                Ok(metadata) => metadata.content_length(),
+                Err(err) if err.kind() == opendal::ErrorKind::NotFound => 0,
+                Err(err) => {
+                    error!(
+                        "append request failed {} {}: {}",
+                        request.task_id, redacted_url, err
+                    );
+
+                    return Err(Self::opendal_error(err));
+                }
+            };
+
+            if content_length != position {
+                error!(
+                    "append request position {} mismatches the length {} {} {}",
+                    position, content_length, request.task_id, redacted_url
+                );
+
+                return Err(ClientError::PreconditionFailed(format!(
+                    "append position {} mismatches the length {} of {}",
+                    position, content_length, redacted_url
+                )));
+            }
+        }
+
+        operator
+            .write_with(&parsed_url.key, request.content)
+            .append(true)
+            .await
+            .map_err(|err| {
+                error!(
+                    "append request failed {} {}: {}",
+                    request.task_id, redacted_url, err
+                );
+
+                Self::opendal_error(err)
+            })?;
+
+        Ok(())
+    }
+
+    /// Warm up lists the bucket root of the url with limit 1 as the cheap authenticated
+    /// request, which builds the cached operator and establishes the pooled connection for
+    /// the subsequent requests of the bucket.
+    #[instrument(skip_all)]
+    async fn warm_up(
+        &self,
+        url: &str,
+        object_storage: Option<common::v2::ObjectStorage>,
+    ) -> ClientResult<()> {
+        let parsed_url = ParsedURL::parse(url)?.to_root();
+        let raw_url = parsed_url.url.to_string();
+        debug!("warm up request {}", raw_url);
+
+        let operator = self.operator(&parsed_url, object_storage, Self::DEFAULT_WARM_UP_TIMEOUT)?;
+
+        let mut lister = operator
+            .lister_with(&parsed_url.key)
+            .limit(1)
+            .await
+            .map_err(Self::opendal_error)?;
+        lister.try_next().await.map_err(|err| {
+            error!("warm up failed {}: {}", raw_url, err);
+            Self::opendal_error(err)
+        })?;
+
+        Ok(())
+    }
+
+    /// Shutdown drops the cached operators and the cached stats, so the pooled connections and
+    /// the credentials held by the operators are released.
+    async fn shutdown(&self) -> ClientResult<()> {
+        self.operators.lock().clear();
+        if let Some(stat_cache) = &self.stat_cache {
+            stat_cache.lock().clear();
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Backend;
+    use dragonfly_api::common::v2::{ObjectStorage as ObjectStorageInfo, Range};
+    use dragonfly_client_config::dfdaemon::TLSVersion;
+    use opendal::Entry;
+    use tokio::io::AsyncReadExt;
+    use wiremock::{
+        matchers::{header, method, path, query_param, query_param_is_missing},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    /// Mock object storage info returns the S3 object storage info of the mock server.
+    fn mock_object_storage_info(server: &MockServer) -> ObjectStorageInfo {
+        ObjectStorageInfo {
+            region: Some("test-region".into()),
+            endpoint: Some(server.uri()),
+            access_key_id: Some("access-key-id".into()),
+            access_key_secret: Some("access-key-secret".into()),
+            ..Default::default()
+        }
+    }
+
+    /// Scheme object storage info returns the object storage info with the required fields of
+    /// the scheme.
+    fn scheme_object_storage_info(scheme: Scheme) -> ObjectStorageInfo {
+        match scheme {
+            Scheme::S3 => ObjectStorageInfo {
+                region: Some("test-region".into()),
+                access_key_id: Some("access-key-id".into()),
+                access_key_secret: Some("access-key-secret".into()),
+                ..Default::default()
+            },
+            Scheme::GCS => ObjectStorageInfo::default(),
+            Scheme::ABS => ObjectStorageInfo {
+                endpoint: Some("http://test-endpoint.local".into()),
+                access_key_id: Some("access-key-id".into()),
+                access_key_secret: Some("YWNjZXNzLWtleS1zZWNyZXQK".into()),
+                ..Default::default()
+            },
+            _ => ObjectStorageInfo {
+                region: Some("test-region".into()),
+                endpoint: Some("http://test-endpoint.local".into()),
+                access_key_id: Some("access-key-id".into()),
+                access_key_secret: Some("access-key-secret".into()),
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Signed header returns the matcher of the requests whose AWS signature covers the header.
+    fn signed_header(name: &'static str) -> impl Fn(&wiremock::Request) -> bool {
+        move |request| {
+            request
+                .headers
+                .get(http::header::AUTHORIZATION)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.split("SignedHeaders=").nth(1))
+                .and_then(|value| value.split(',').next())
+                .is_some_and(|value| value.split(';').any(|header| header == name))
+        }
+    }
+
+    #[test]
+    fn should_return_true_for_supported_schemes() {
+        let supported = vec!["s3", "gs", "abs", "oss", "obs", "cos", "oci", "s3c"];
+        for scheme in supported {
+            assert!(Scheme::is_supported(scheme));
+        }
+    }
+    #[test]
+    fn should_return_false_for_unsupported_schemes() {
+        let unsupported = vec![
+            "http", "https", "ftp", "hdfs", "file", "", "S3", "GCS", "s3+ftp", "http+s3",
+        ];
+        for scheme in unsupported {
+            assert!(!Scheme::is_supported(scheme));
+        }
+    }
+
+    #[test]
+    fn should_parse_compound_schemes() {
+        for (url, transport) in [
+            ("s3+https://test-bucket/file", Transport::Https),
+            ("s3+http://test-bucket/file", Transport::Http),
+        ] {
+            let url: Url = url.parse().unwrap();
+            assert_eq!(Scheme::from_url(&url), Ok((Scheme::S3, Some(transport))));
+
+            let parsed_url: ParsedURL = url.try_into().unwrap();
+            assert_eq!(parsed_url.scheme, Scheme::S3);
+            assert_eq!(parsed_url.transport, Some(transport));
+            assert_eq!(parsed_url.bucket, "test-bucket");
+            assert_eq!(parsed_url.key, "file");
+        }
+
+        assert_eq!(Scheme::parse_compound("oss"), Ok((Scheme::OSS, None)));
+        assert_eq!(
+            Scheme::parse_compound("gcs+https"),
+            Ok((Scheme::GCS, Some(Transport::Https)))
+        );
+        assert!(Scheme::parse_compound("s3+ftp").is_err());
+
+        // The transport replaces the scheme of the endpoint.
+        assert_eq!(
+            Transport::Http.apply("https://s3.example.com"),
+            "http://s3.example.com"
+        );
+        assert_eq!(
+            Transport::Https.apply("s3.example.com:9000"),
+            "https://s3.example.com:9000"
+        );
+    }
+
+    #[test]
+    fn should_parse_gcs_alias_scheme() {
+        assert_eq!("gcs".parse::<Scheme>(), Ok(Scheme::GCS));
+        assert_eq!(Scheme::GCS.to_string(), "gs");
+        assert!(Scheme::is_supported("gcs"));
+
+        // The URL of the alias is parsed identically to the URL of the canonical scheme.
+        let gs: ParsedURL = Url::parse("gs://test-bucket/dir/file")
+            .unwrap()
+            .try_into()
+            .unwrap();
+        let gcs: ParsedURL = Url::parse("gcs://test-bucket/dir/file")
+            .unwrap()
+            .try_into()
+            .unwrap();
+        assert_eq!(gcs.scheme, gs.scheme);
+        assert_eq!(gcs.bucket, gs.bucket);
+        assert_eq!(gcs.key, gs.key);
+        assert_eq!(gcs.region, gs.region);
+        assert_eq!(gcs.transport, gs.transport);
+    }
+
+    #[tokio::test]
+    async fn should_stat_object_by_compound_scheme() {
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/test-bucket/file"))
+            .respond_with(ResponseTemplate::new(200).insert_header("Content-Length", "10"))
+            .mount(&server)
+            .await;
+
+        // The endpoint without the scheme is requested by the transport of the URL.
+        let object_storage = ObjectStorage::new(Scheme::S3, Arc::new(Config::default())).unwrap();
+        let response = object_storage
+            .stat(
+                StatRequest::builder()
+                    .task_id("test")
+                    .url("s3+http://test-bucket/file")
+                    .object_storage(ObjectStorageInfo {
+                        region: Some("test-region".into()),
+                        endpoint: Some(server.address().to_string()),
+                        access_key_id: Some("access-key-id".into()),
+                        access_key_secret: Some("access-key-secret".into()),
+                        ..Default::default()
+                    })
+                    .build(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.content_length, Some(10));
+    }
+
+    #[test]
+    fn should_get_parsed_url() {
+        let file_key = "test-bucket/file";
+        let dir_key = "test-bucket/path/to/dir/";
+        let schemes = vec![
+            Scheme::OBS,
+            Scheme::S3,
+            Scheme::ABS,
+            Scheme::OSS,
+            Scheme::COS,
+            Scheme::GCS,
+            Scheme::OCI,
+        ];
+
+        // Test each scheme for both file and directory URLs.
+        for scheme in schemes {
+            let file_url = format!("{}://{}", scheme, file_key);
+            let url: Url = file_url.parse().unwrap();
+            let parsed_url: ParsedURL = url.try_into().unwrap();
+
+            assert!(!parsed_url.is_dir());
+            assert_eq!(parsed_url.bucket, "test-bucket");
+            assert_eq!(parsed_url.key, "file");
+            assert_eq!(parsed_url.scheme, scheme);
+
+            let dir_url = format!("{}://{}", scheme, dir_key);
+            let url: Url = dir_url.parse().unwrap();
+            let parsed_url: ParsedURL = url.try_into().unwrap();
+
+            assert!(parsed_url.is_dir());
+            assert_eq!(parsed_url.bucket, "test-bucket");
+            assert_eq!(parsed_url.key, "path/to/dir/");
+            assert_eq!(parsed_url.scheme, scheme);
+        }
+    }
+
+    #[test]
+    fn should_parse_url_from_string() {
+        let parsed_url = ParsedURL::parse("s3://test-bucket/dir/file").unwrap();
+        assert_eq!(parsed_url.bucket, "test-bucket");
+        assert_eq!(parsed_url.key, "dir/file");
+
+        // Regression inputs of the parsed_url fuzz target.
+        for url in [
+            "",
+            "\0",
+            "data:text/plain,hello",
+            "mailto:user@example.com",
+            "s3://",
+            "s3:///key",
+            "s3://test-bucket",
+            "s3://s3.amazonaws.com/",
+            "s3://.s3.amazonaws.com/key",
+            "oci://namespace@/key",
+            "unknown://test-bucket/key",
+        ] {
+            assert!(ParsedURL::parse(url).is_err(), "{} should be invalid", url);
+        }
+    }
+
+    #[test]
+    fn should_get_url_with_the_same_prefix() {
+        let file_key = "test-bucket/file";
+        let schemes = vec![
+            Scheme::OBS,
+            Scheme::S3,
+            Scheme::ABS,
+            Scheme::OSS,
+            Scheme::COS,
+            Scheme::GCS,
+        ];
+
+        // Test each scheme for both file and directory URLs.
+        for scheme in schemes {
+            let file_url = format!("{}://{}", scheme, file_key);
+            let url: Url = file_url.parse().unwrap();
+            let parsed_url: ParsedURL = url.try_into().unwrap();
+
+            let new_url = parsed_url.make_url_by_entry_path("test-entry");
+            let new_parsed_url: ParsedURL = new_url.try_into().unwrap();
+
+            assert_eq!(parsed_url.bucket, new_parsed_url.bucket);
+            assert_eq!(parsed_url.scheme, new_parsed_url.scheme);
+            assert_eq!(new_parsed_url.key, "test-entry");
+        }
+    }
+
+    #[test]
+    fn should_get_parsed_url_with_region_in_host() {
+        let test_cases = vec![
+            (
+                "s3://test-bucket.s3.eu-west-1.amazonaws.com/path/to/file",
+                "test-bucket",
+                "path/to/file",
+                Some("eu-west-1"),
+            ),
+            (
+                "s3://test.bucket.s3-us-west-2.amazonaws.com/file",
+                "test.bucket",
+                "file",
+                Some("us-west-2"),
+            ),
+            (
+                "s3://test-bucket.s3.dualstack.ap-east-1.amazonaws.com/file",
+                "test-bucket",
+                "file",
+                Some("ap-east-1"),
+            ),
+            (
+                "s3://test-bucket.s3.cn-north-1.amazonaws.com.cn/file",
+                "test-bucket",
+                "file",
+                Some("cn-north-1"),
+            ),
+            (
+                "s3://test-bucket.s3.amazonaws.com/file",
+                "test-bucket",
+                "file",
+                None,
+            ),
+            (
+                "s3://s3.eu-west-1.amazonaws.com/test-bucket/path/to/file",
+                "test-bucket",
+                "path/to/file",
+                Some("eu-west-1"),
+            ),
+            (
+                "s3://test-bucket/path/to/file",
+                "test-bucket",
+                "path/to/file",
+                None,
+            ),
+        ];
+
+        for (raw_url, bucket, key, region) in test_cases {
+            let url: Url = raw_url.parse().unwrap();
+            let parsed_url: ParsedURL = url.try_into().unwrap();
+
+            assert_eq!(parsed_url.bucket, bucket);
+            assert_eq!(parsed_url.key, key);
+            assert_eq!(parsed_url.region.as_deref(), region);
+        }
+    }
+
+    #[test]
+    fn should_keep_bucket_when_make_url_with_path_style_host() {
+        let url: Url = "s3://s3.eu-west-1.amazonaws.com/test-bucket/dir/"
+            .parse()
+            .unwrap();
+        let parsed_url: ParsedURL = url.try_into().unwrap();
+
+        let new_url = parsed_url.make_url_by_entry_path("dir/file");
+        assert_eq!(
+            new_url.as_str(),
+            "s3://s3.eu-west-1.amazonaws.com/test-bucket/dir/file"
+        );
+    }
+
+    #[test]
+    fn should_make_url_by_entry_path_under_deep_prefix() {
+        let test_cases = vec![
+            ("s3://bucket/a/b/c/", "a/b/c/file", "s3://bucket/a/b/c/file"),
+            ("gs://bucket/a/b/c/", "a/b/c/file", "gs://bucket/a/b/c/file"),
+            ("oss://bucket/a/b/", "a/b/c/file", "oss://bucket/a/b/c/file"),
+            ("abs://bucket/a/b/c/", "a/b/c/d/", "abs://bucket/a/b/c/d/"),
+            ("cos://bucket/a/", "/a/b/c/file", "cos://bucket/a/b/c/file"),
+            (
+                "obs://bucket/a/b/c/?versionId=1",
+                "a/b/c/file",
+                "obs://bucket/a/b/c/file",
+            ),
+            (
+                "s3://s3.eu-west-1.amazonaws.com/bucket/a/b/c/",
+                "a/b/c/file",
+                "s3://s3.eu-west-1.amazonaws.com/bucket/a/b/c/file",
+            ),
+        ];
+
+        for (url, entry_path, expected_url) in test_cases {
+            let parsed_url = ParsedURL::parse(url).unwrap();
+            let new_url = parsed_url.make_url_by_entry_path(entry_path);
+            assert_eq!(new_url.as_str(), expected_url);
+
+            // The made URL addresses the entry in the same bucket.
+            let new_parsed_url: ParsedURL = new_url.try_into().unwrap();
+            assert_eq!(new_parsed_url.bucket, "bucket");
+            assert_eq!(new_parsed_url.key, entry_path.trim_start_matches('/'));
+        }
+    }
+
+    #[test]
+    fn should_return_error_when_path_style_host_lacks_of_bucket() {
+        let url: Url = "s3://s3.eu-west-1.amazonaws.com/file".parse().unwrap();
+        let result = TryInto::<ParsedURL>::try_into(url);
+
+        assert!(result.is_err());
+        assert!(matches!(result.unwrap_err(), ClientError::InvalidURI(..)));
+    }
+
+    #[test]
+    fn should_get_s3_operator_with_region_in_host() {
+        let url: Url = "s3://test-bucket.s3.eu-west-1.amazonaws.com/file"
+            .parse()
+            .unwrap();
+        let parsed_url: ParsedURL = url.try_into().unwrap();
+
+        let result = ObjectStorage::new(Scheme::S3, Arc::new(Config::default()))
+            .unwrap()
+            .operator(
+                &parsed_url,
+                Some(ObjectStorageInfo {
+                    access_key_id: Some("access_key_id".into()),
+                    access_key_secret: Some("access_key_secret".into()),
+                    ..Default::default()
+                }),
+                Duration::from_secs(3),
+            );
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn should_build_clients_with_min_tls_version() {
+        let config = Config {
+            backend: dragonfly_client_config::dfdaemon::Backend {
+                min_tls_version: Some(TLSVersion::TLS13),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let url: Url = "s3://test-bucket/file".parse().unwrap();
+        let parsed_url: ParsedURL = url.try_into().unwrap();
+        let result = ObjectStorage::new(Scheme::S3, Arc::new(config))
+            .unwrap()
+            .operator(
+                &parsed_url,
+                Some(ObjectStorageInfo {
+                    access_key_id: Some("access_key_id".into()),
+                    access_key_secret: Some("access_key_secret".into()),
+                    region: Some("us-east-1".into()),
+                    ..Default::default()
+                }),
+                Duration::from_secs(3),
+            );
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_exclude_self_entry_from_dir_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let operator = Operator::new(
+            opendal::services::Fs::default().root(dir.path().to_string_lossy().as_ref()),
+        )
+        .unwrap()
+        .finish();
+        operator.create_dir("dir/").await.unwrap();
+        operator.create_dir("dir/sub/").await.unwrap();
+        operator.write("dir/file", b"file".to_vec()).await.unwrap();
+
+        let url: Url = "s3://test-bucket/dir/".parse().unwrap();
+        let parsed_url: ParsedURL = url.try_into().unwrap();
+
+        let entries = operator
+            .list_with(&parsed_url.key)
+            .recursive(true)
+            .await
+            .unwrap();
+        let dir_entries =
+            parsed_url.make_dir_entries(entries.into_iter().map(Entry::into_parts).collect(), &[]);
+
+        let urls: Vec<String> = dir_entries.iter().map(|entry| entry.url.clone()).collect();
+        assert!(!urls.contains(&"s3://test-bucket/dir/".to_string()));
+        assert!(urls.contains(&"s3://test-bucket/dir/sub/".to_string()));
+        assert!(urls.contains(&"s3://test-bucket/dir/file".to_string()));
+    }
+
+    #[test]
+    fn should_build_object_storage_config() {
+        let object_storage = ObjectStorageConfig::builder()
+            .access_key_id("access-key-id")
+            .access_key_secret("access-key-secret")
+            .endpoint("http://127.0.0.1:9000")
+            .build()
+            .unwrap();
+        assert_eq!(
+            object_storage,
+            ObjectStorageInfo {
+                access_key_id: Some("access-key-id".into()),
+                access_key_secret: Some("access-key-secret".into()),
+                endpoint: Some("http://127.0.0.1:9000".into()),
+                ..Default::default()
+            }
+        );
+
+        let object_storage = ObjectStorageConfig::builder()
+            .credential_path("/etc/gcs/credential.json")
+            .predefined_acl("private")
+            .build()
+            .unwrap();
+        assert_eq!(
+            object_storage.credential_path.as_deref(),
+            Some("/etc/gcs/credential.json")
+        );
+        assert!(object_storage.access_key_id.is_none());
+    }
+
+    #[test]
+    fn should_reject_conflicting_object_storage_config() {
+        let test_cases = vec![
+            ObjectStorageConfig::builder()
+                .credential_path("/etc/gcs/credential.json")
+                .access_key_id("access-key-id")
+                .access_key_secret("access-key-secret"),
+            ObjectStorageConfig::builder().access_key_id("access-key-id"),
+            ObjectStorageConfig::builder().access_key_secret("access-key-secret"),
+            ObjectStorageConfig::builder().session_token("session-token"),
+        ];
+
+        for builder in test_cases {
+            assert!(matches!(
+                builder.build(),
+                Err(ClientError::ValidationError(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn should_infer_dir_entry_when_mode_unknown() {
+        let metadata = Metadata::new(EntryMode::Unknown);
+        assert!(ParsedURL::is_dir_entry("dir/sub/", &metadata));
+        assert!(!ParsedURL::is_dir_entry("dir/file", &metadata));
+
+        // The mode takes precedence over the trailing slash if it is known.
+        assert!(ParsedURL::is_dir_entry(
+            "dir/sub",
+            &Metadata::new(EntryMode::DIR)
+        ));
+        assert!(!ParsedURL::is_dir_entry(
+            "dir/file/",
+            &Metadata::new(EntryMode::FILE)
+        ));
+    }
+
+    #[test]
+    fn should_return_error_when_scheme_not_valid() {
+        let url: Url = "github://test-bucket/file".parse().unwrap();
+        let result = TryInto::<ParsedURL>::try_into(url);
+
+        assert!(result.is_err());
+        assert!(matches!(result.unwrap_err(), ClientError::InvalidURI(..)));
+    }
+
+    #[test]
+    fn should_return_error_when_bucket_not_valid() {
+        let schemes = vec![
+            Scheme::OBS,
+            Scheme::S3,
+            Scheme::ABS,
+            Scheme::OSS,
+            Scheme::COS,
+            Scheme::GCS,
+        ];
+
+        for scheme in schemes {
+            let url: Url = format!("{}:///file", scheme).parse().unwrap();
+            let result = TryInto::<ParsedURL>::try_into(url);
+
+            assert!(result.is_err());
+            assert!(matches!(result.unwrap_err(), ClientError::InvalidURI(..)));
+        }
+    }
+
+    #[test]
+    fn should_get_operator() {
+        let test_cases = vec![
+            (
+                Scheme::S3,
+                ObjectStorageInfo {
+                    region: Some("test-region".into()),
+                    access_key_id: Some("access-key-id".into()),
+                    access_key_secret: Some("access-key-secret".into()),
+                    ..Default::default()
+                },
+            ),
+            (Scheme::GCS, ObjectStorageInfo::default()),
+            (
+                Scheme::ABS,
+                ObjectStorageInfo {
+                    endpoint: Some("test-endpoint.local".into()),
+                    access_key_id: Some("access-key-id".into()),
+                    access_key_secret: Some("YWNjZXNzLWtleS1zZWNyZXQK".into()),
+                    ..Default::default()
+                },
+            ),
+            (
+                Scheme::OSS,
+                ObjectStorageInfo {
+                    endpoint: Some("test-endpoint.local".into()),
+                    access_key_id: Some("access-key-id".into()),
+                    access_key_secret: Some("access-key-secret".into()),
+                    ..Default::default()
+                },
+            ),
+            (
+                Scheme::OBS,
+                ObjectStorageInfo {
+                    endpoint: Some("test-endpoint.local".into()),
+                    access_key_id: Some("access-key-id".into()),
+                    access_key_secret: Some("access-key-secret".into()),
+                    ..Default::default()
+                },
+            ),
+            (
+                Scheme::COS,
+                ObjectStorageInfo {
+                    endpoint: Some("test-endpoint.local".into()),
+                    access_key_id: Some("access-key-id".into()),
+                    access_key_secret: Some("access-key-secret".into()),
+                    ..Default::default()
+                },
+            ),
+        ];
+
+        for (scheme, object_storage) in test_cases {
+            let url: Url = format!("{}://test-bucket/file", scheme).parse().unwrap();
+            let parsed_url: ParsedURL = url.try_into().unwrap();
+
+            let result = ObjectStorage::new(scheme, Arc::new(Config::default()))
+                .unwrap()
+                .operator(&parsed_url, Some(object_storage), Duration::from_secs(3));
+
+            assert!(
+                result.is_ok(),
+                "can not get {} operator, due to: {}",
+                scheme,
+                result.unwrap_err()
+            );
+        }
+    }
+
+    #[test]
+    fn should_get_s3_operator_with_extra_info() {
+        let test_cases = vec![
+            ObjectStorageInfo {
+                access_key_id: Some("access_key_id".into()),
+                access_key_secret: Some("access_key_secret".into()),
+                region: Some("test-region".into()),
+
+                endpoint: Some("test-endpoint.local".into()),
+                ..Default::default()
+            },
+            ObjectStorageInfo {
+                access_key_id: Some("access_key_id".into()),
+                access_key_secret: Some("access_key_secret".into()),
+                region: Some("test-region".into()),
+
+                session_token: Some("session_token".into()),
+                ..Default::default()
+            },
+            ObjectStorageInfo {
+                access_key_id: Some("access_key_id".into()),
+                access_key_secret: Some("access_key_secret".into()),
+                region: Some("test-region".into()),
+
+                endpoint: Some("test-endpoint.local".into()),
+                session_token: Some("session_token".into()),
+                ..Default::default()
+            },
+        ];
+
+        for object_storage in test_cases {
+            let url: Url = "s3://test-bucket/file".parse().unwrap();
+            let parsed_url: ParsedURL = url.try_into().unwrap();
+
+            let result = ObjectStorage::new(Scheme::S3, Arc::new(Config::default()))
+                .unwrap()
+                .operator(&parsed_url, Some(object_storage), Duration::from_secs(3));
+
+            assert!(result.is_ok());
+            assert_eq!(result.unwrap().info().scheme().to_string(), "s3");
+        }
+    }
+
+    #[test]
+    fn should_get_gcs_operator_with_extra_info() {
+        let test_cases = vec![
+            ObjectStorageInfo {
+                credential_path: Some("credential_path".into()),
+                ..Default::default()
+            },
+            ObjectStorageInfo {
+                endpoint: Some("test-endpoint".into()),
+                ..Default::default()
+            },
+            ObjectStorageInfo {
+                predefined_acl: Some("predefine_acl".into()),
+                ..Default::default()
+            },
+            ObjectStorageInfo {
+                credential_path: Some("credential_path".into()),
+                endpoint: Some("test-endpoint".into()),
+                ..Default::default()
+            },
+            ObjectStorageInfo {
+                credential_path: Some("credential_path".into()),
+                predefined_acl: Some("predefine_acl".into()),
+                ..Default::default()
+            },
+            ObjectStorageInfo {
+                endpoint: Some("test-endpoint".into()),
+                predefined_acl: Some("predefine_acl".into()),
+                ..Default::default()
+            },
+            ObjectStorageInfo {
+                credential_path: Some("credential_path".into()),
+                endpoint: Some("test-endpoint".into()),
+                predefined_acl: Some("predefine_acl".into()),
+                ..Default::default()
+            },
+        ];
+
+        for object_storage in test_cases {
+            let url: Url = "gs://test-bucket/file".parse().unwrap();
+            let parsed_url: ParsedURL = url.try_into().unwrap();
+
+            let result = ObjectStorage::new(Scheme::GCS, Arc::new(Config::default()))
+                .unwrap()
+                .operator(&parsed_url, Some(object_storage), Duration::from_secs(3));
+
+            assert!(result.is_ok());
+            assert_eq!(result.unwrap().info().scheme().to_string(), "gcs");
+        }
+    }
+
+    #[test]
+    fn should_return_error_when_lacks_of_info() {
+        let url: Url = "s3://test-bucket/file".parse().unwrap();
+        let parsed_url: ParsedURL = url.try_into().unwrap();
+
+        let result = ObjectStorage::new(Scheme::S3, Arc::new(Config::default()))
+            .unwrap()
+            .operator(&parsed_url, None, Duration::from_secs(3));
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "backend error: s3 need object_storage parameter"
+        )
+    }
+
+    #[test]
+    fn should_return_error_when_s3_lacks_of_info() {
+        let test_cases = vec![
+            (
+                ObjectStorageInfo::default(),
+                "backend error: s3 need access_key_id, access_key_secret, region",
+            ),
+            (
+                ObjectStorageInfo {
+                    access_key_id: Some("access_key_id".into()),
+                    ..Default::default()
+                },
+                "backend error: s3 need access_key_secret, region",
+            ),
+            (
+                ObjectStorageInfo {
+                    access_key_secret: Some("access_key_secret".into()),
+                    ..Default::default()
+                },
+                "backend error: s3 need access_key_id, region",
+            ),
+            (
+                ObjectStorageInfo {
+                    region: Some("test-region".into()),
+                    ..Default::default()
+                },
+                "backend error: s3 need access_key_id, access_key_secret",
+            ),
+            (
+                ObjectStorageInfo {
+                    access_key_id: Some("access_key_id".into()),
+                    access_key_secret: Some("access_key_secret".into()),
+                    ..Default::default()
+                },
+                "backend error: s3 need region",
+            ),
+            (
+                ObjectStorageInfo {
+                    access_key_id: Some("access_key_id".into()),
+                    region: Some("test-region".into()),
+                    ..Default::default()
+                },
+                "backend error: s3 need access_key_secret",
+            ),
+            (
+                ObjectStorageInfo {
+                    access_key_secret: Some("access_key_secret".into()),
+                    region: Some("test-region".into()),
+                    ..Default::default()
+                },
+                "backend error: s3 need access_key_id",
+            ),
+        ];
+
+        // Disable the default region, so the missing region is reported.
+        let mut config = Config::default();
+        config.backend.default_regions.clear();
+        let config = Arc::new(config);
+
+        for (object_storage, error_message) in test_cases {
+            let url: Url = "s3://test-bucket/file".parse().unwrap();
+            let parsed_url: ParsedURL = url.try_into().unwrap();
+
+            let result = ObjectStorage::new(Scheme::S3, config.clone())
+                .unwrap()
+                .operator(&parsed_url, Some(object_storage), Duration::from_secs(3));
+
+            assert!(result.is_err());
+            assert_eq!(result.unwrap_err().to_string(), error_message);
+        }
+    }
+
+    #[tokio::test]
+    async fn should_use_default_region_when_region_absent() {
+        let url: Url = "s3://test-bucket/file".parse().unwrap();
+        let parsed_url: ParsedURL = url.try_into().unwrap();
+        let object_storage_info = ObjectStorageInfo {
+            access_key_id: Some("access_key_id".into()),
+            access_key_secret: Some("access_key_secret".into()),
+            ..Default::default()
+        };
+
+        for (region, expected_region) in [(None, "us-east-1"), (Some("eu-west-1"), "eu-west-1")] {
+            let mut config = Config::default();
+            if let Some(region) = region {
+                config
+                    .backend
+                    .default_regions
+                    .insert("s3".to_string(), region.to_string());
+            }
+
+            let operator = ObjectStorage::new(Scheme::S3, Arc::new(config))
+                .unwrap()
+                .operator(
+                    &parsed_url,
+                    Some(object_storage_info.clone()),
+                    Duration::from_secs(3),
+                )
+                .unwrap();
+
+            // The region is signed in the credential scope of the presigned request.
+            let presigned_request = operator
+                .presign_stat(&parsed_url.key, Duration::from_secs(60))
+                .await
+                .unwrap();
+            assert!(presigned_request
+                .uri()
+                .to_string()
+                .contains(&format!("%2F{}%2Fs3%2Faws4_request", expected_region)));
+        }
+    }
+
+    #[tokio::test]
+    async fn should_build_s3_compatible_operator_with_path_style() {
+        let url: Url = "s3c://test-bucket/path/to/file".parse().unwrap();
+        let parsed_url: ParsedURL = url.try_into().unwrap();
+        assert_eq!(parsed_url.scheme, Scheme::S3Compatible);
+        assert_eq!(parsed_url.bucket, "test-bucket");
+        assert_eq!(parsed_url.key, "path/to/file");
+
+        let object_storage =
+            ObjectStorage::new(Scheme::S3Compatible, Arc::new(Config::default())).unwrap();
+        let operator = object_storage
+            .operator(
+                &parsed_url,
+                Some(ObjectStorageInfo {
+                    endpoint: Some("https://nyc3.digitaloceanspaces.com".into()),
+                    access_key_id: Some("access_key_id".into()),
+                    access_key_secret: Some("access_key_secret".into()),
+                    ..Default::default()
+                }),
+                Duration::from_secs(3),
+            )
+            .unwrap();
+
+        // The bucket is in the path of the endpoint, and the default region is signed.
+        let presigned_request = operator
+            .presign_stat(&parsed_url.key, Duration::from_secs(60))
+            .await
+            .unwrap();
+        let uri = presigned_request.uri().to_string();
+        assert!(uri.starts_with("https://nyc3.digitaloceanspaces.com/test-bucket/path/to/file?"));
+        assert!(uri.contains("%2Fus-east-1%2Fs3%2Faws4_request"));
+
+        // The endpoint is required.
+        let result = object_storage.operator(
+            &parsed_url,
+            Some(ObjectStorageInfo {
+                access_key_id: Some("access_key_id".into()),
+                access_key_secret: Some("access_key_secret".into()),
+                ..Default::default()
+            }),
+            Duration::from_secs(3),
+        );
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "backend error: s3c need endpoint"
+        );
+
+        // The default region of the scheme is configurable.
+        let mut config = Config::default();
+        config
+            .backend
+            .default_regions
+            .insert("s3c".to_string(), "eu-west-1".to_string());
+        let operator = ObjectStorage::new(Scheme::S3Compatible, Arc::new(config))
+            .unwrap()
+            .operator(
+                &parsed_url,
+                Some(ObjectStorageInfo {
+                    endpoint: Some("https://nyc3.digitaloceanspaces.com".into()),
+                    access_key_id: Some("access_key_id".into()),
+                    access_key_secret: Some("access_key_secret".into()),
+                    ..Default::default()
+                }),
+                Duration::from_secs(3),
+            )
+            .unwrap();
+        let presigned_request = operator
+            .presign_stat(&parsed_url.key, Duration::from_secs(60))
+            .await
+            .unwrap();
+        assert!(presigned_request
+            .uri()
+            .to_string()
+            .contains("%2Feu-west-1%2Fs3%2Faws4_request"));
+    }
+
+    #[tokio::test]
+    async fn should_use_credentials_of_profile() {
+        let dir = tempfile::tempdir().unwrap();
+        let credentials_file = dir.path().join("credentials");
+        std::fs::write(
+            &credentials_file,
+            "[default]\n\
+             aws_access_key_id = default-key-id\n\
+             aws_secret_access_key = default-key-secret\n\
+             \n\
+             # Production account.\n\
+             [production]\n\
+             aws_access_key_id = production-key-id\n\
+             aws_secret_access_key = production-key-secret\n\
+             region = eu-central-1\n",
+        )
+        .unwrap();
+
+        let mut config = Config::default();
+        config.backend.object_storage_profile = Some("production".to_string());
+        config.backend.object_storage_credentials_file = credentials_file.clone();
+        let object_storage = ObjectStorage::new(Scheme::S3, Arc::new(config)).unwrap();
+        assert_eq!(
+            object_storage.profile_credentials().unwrap(),
+            Some(ProfileCredentials {
+                access_key_id: Some("production-key-id".to_string()),
+                access_key_secret: Some("production-key-secret".to_string()),
+                session_token: None,
+                region: Some("eu-central-1".to_string()),
+            })
+        );
+
+        // The credentials of the profile are signed in the credential scope of the presigned
+        // request when the request has no inline credentials.
+        let url: Url = "s3://test-bucket/file".parse().unwrap();
+        let parsed_url: ParsedURL = url.try_into().unwrap();
+        let operator = object_storage
+            .operator(
+                &parsed_url,
+                Some(ObjectStorageInfo::default()),
+                Duration::from_secs(3),
+            )
+            .unwrap();
+        let presigned_request = operator
+            .presign_stat(&parsed_url.key, Duration::from_secs(60))
+            .await
+            .unwrap();
+        assert!(presigned_request
+            .uri()
+            .to_string()
+            .contains("production-key-id%2F"));
+        assert!(presigned_request
+            .uri()
+            .to_string()
+            .contains("%2Feu-central-1%2Fs3%2Faws4_request"));
+
+        // The credentials of the profile are never sent to the endpoint of the request.
+        let result = object_storage.operator(
+            &parsed_url,
+            Some(ObjectStorageInfo {
+                endpoint: Some("https://s3.attacker.example.com".into()),
+                ..Default::default()
+            }),
+            Duration::from_secs(3),
+        );
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "backend error: s3 need access_key_id, access_key_secret"
+        );
+
+        // The rotated credentials are reloaded when the credentials file is modified.
+        std::fs::write(
+            &credentials_file,
+            "[production]\n\
+             aws_access_key_id = rotated-key-id\n\
+             aws_secret_access_key = rotated-key-secret\n",
+        )
+        .unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(&credentials_file)
+            .unwrap()
+            .set_modified(SystemTime::now() + Duration::from_secs(60))
+            .unwrap();
+        assert_eq!(
+            object_storage
+                .profile_credentials()
+                .unwrap()
+                .unwrap()
+                .access_key_id,
+            Some("rotated-key-id".to_string())
+        );
+
+        // The missing profile and the missing credentials file only fail the requests using the
+        // profile, and the requests with the inline credentials are not affected.
+        for (profile, credentials_file) in [
+            ("staging", credentials_file.clone()),
+            ("production", dir.path().join("missing")),
+        ] {
+            let mut config = Config::default();
+            config.backend.object_storage_profile = Some(profile.to_string());
+            config.backend.object_storage_credentials_file = credentials_file;
+            let object_storage = ObjectStorage::new(Scheme::S3, Arc::new(config)).unwrap();
+            assert!(object_storage
+                .operator(
+                    &parsed_url,
+                    Some(ObjectStorageInfo::default()),
+                    Duration::from_secs(3),
+                )
+                .is_err());
+            assert!(object_storage
+                .operator(
+                    &parsed_url,
+                    Some(ObjectStorageInfo {
+                        access_key_id: Some("access_key_id".into()),
+                        access_key_secret: Some("access_key_secret".into()),
+                        ..Default::default()
+                    }),
+                    Duration::from_secs(3),
+                )
+                .is_ok());
+        }
+    }
+
+    #[test]
+    fn should_return_error_when_abs_lacks_of_info() {
+        let test_cases = vec![
+            (
+                ObjectStorageInfo::default(),
+                "backend error: abs need access_key_id, access_key_secret, endpoint",
+            ),
+            (
+                ObjectStorageInfo {
+                    access_key_id: Some("access_key_id".into()),
+                    ..Default::default()
+                },
+                "backend error: abs need access_key_secret, endpoint",
+            ),
+            (
+                ObjectStorageInfo {
+                    access_key_secret: Some("access_key_secret".into()),
+                    ..Default::default()
+                },
+                "backend error: abs need access_key_id, endpoint",
+            ),
+            (
+                ObjectStorageInfo {
+                    endpoint: Some("test-endpoint.local".into()),
+                    ..Default::default()
+                },
+                "backend error: abs need access_key_id, access_key_secret",
+            ),
+            (
+                ObjectStorageInfo {
+                    access_key_id: Some("access_key_id".into()),
+                    access_key_secret: Some("access_key_secret".into()),
+                    ..Default::default()
+                },
+                "backend error: abs need endpoint",
+            ),
+            (
+                ObjectStorageInfo {
+                    access_key_id: Some("access_key_id".into()),
+                    endpoint: Some("test-endpoint.local".into()),
+                    ..Default::default()
+                },
+                "backend error: abs need access_key_secret",
+            ),
+            (
+                ObjectStorageInfo {
+                    access_key_secret: Some("access_key_secret".into()),
+                    endpoint: Some("test-endpoint.local".into()),
+                    ..Default::default()
+                },
+                "backend error: abs need access_key_id",
+            ),
+        ];
+
+        for (object_storage, error_message) in test_cases {
+            let url: Url = "abs://test-bucket/file".parse().unwrap();
+            let parsed_url: ParsedURL = url.try_into().unwrap();
+
+            let result = ObjectStorage::new(Scheme::ABS, Arc::new(Config::default()))
+                .unwrap()
+                .operator(&parsed_url, Some(object_storage), Duration::from_secs(3));
+
+            assert!(result.is_err());
+            assert_eq!(result.unwrap_err().to_string(), error_message);
+        }
+    }
+
+    #[test]
+    fn should_return_error_when_oss_lacks_of_info() {
+        let test_cases = vec![
+            (
+                ObjectStorageInfo::default(),
+                "backend error: oss need access_key_id, access_key_secret, endpoint",
+            ),
+            (
+                ObjectStorageInfo {
+                    access_key_id: Some("access_key_id".into()),
+                    ..Default::default()
+                },
+                "backend error: oss need access_key_secret, endpoint",
+            ),
+            (
+                ObjectStorageInfo {
+                    access_key_secret: Some("access_key_secret".into()),
+                    ..Default::default()
+                },
+                "backend error: oss need access_key_id, endpoint",
+            ),
+            (
+                ObjectStorageInfo {
+                    endpoint: Some("test-endpoint.local".into()),
+                    ..Default::default()
+                },
+                "backend error: oss need access_key_id, access_key_secret",
+            ),
+            (
+                ObjectStorageInfo {
+                    access_key_id: Some("access_key_id".into()),
+                    access_key_secret: Some("access_key_secret".into()),
+                    ..Default::default()
+                },
+                "backend error: oss need endpoint",
+            ),
+            (
+                ObjectStorageInfo {
+                    access_key_id: Some("access_key_id".into()),
+                    endpoint: Some("test-endpoint.local".into()),
+                    ..Default::default()
+                },
+                "backend error: oss need access_key_secret",
+            ),
+            (
+                ObjectStorageInfo {
+                    access_key_secret: Some("access_key_secret".into()),
+                    endpoint: Some("test-endpoint.local".into()),
+                    ..Default::default()
+                },
+                "backend error: oss need access_key_id",
+            ),
+        ];
+
+        for (object_storage, error_message) in test_cases {
+            let url: Url = "oss://test-bucket/file".parse().unwrap();
+            let parsed_url: ParsedURL = url.try_into().unwrap();
+
+            let result = ObjectStorage::new(Scheme::OSS, Arc::new(Config::default()))
+                .unwrap()
+                .operator(&parsed_url, Some(object_storage), Duration::from_secs(3));
+
+            assert!(result.is_err());
+            assert_eq!(result.unwrap_err().to_string(), error_message);
+        }
+    }
+
+    #[test]
+    fn should_return_error_when_obs_lacks_of_info() {
+        let test_cases = vec![
+            (
+                ObjectStorageInfo::default(),
+                "backend error: obs need access_key_id, access_key_secret, endpoint",
+            ),
+            (
+                ObjectStorageInfo {
+                    access_key_id: Some("access_key_id".into()),
+                    ..Default::default()
+                },
+                "backend error: obs need access_key_secret, endpoint",
+            ),
+            (
+                ObjectStorageInfo {
+                    access_key_secret: Some("access_key_secret".into()),
+                    ..Default::default()
+                },
+                "backend error: obs need access_key_id, endpoint",
+            ),
+            (
+                ObjectStorageInfo {
+                    endpoint: Some("test-endpoint.local".into()),
+                    ..Default::default()
+                },
+                "backend error: obs need access_key_id, access_key_secret",
+            ),
+            (
+                ObjectStorageInfo {
+                    access_key_id: Some("access_key_id".into()),
+                    access_key_secret: Some("access_key_secret".into()),
+                    ..Default::default()
+                },
+                "backend error: obs need endpoint",
+            ),
+            (
+                ObjectStorageInfo {
+                    access_key_id: Some("access_key_id".into()),
+                    endpoint: Some("test-endpoint.local".into()),
+                    ..Default::default()
+                },
+                "backend error: obs need access_key_secret",
+            ),
+            (
+                ObjectStorageInfo {
+                    access_key_secret: Some("access_key_secret".into()),
+                    endpoint: Some("test-endpoint.local".into()),
                     ..Default::default()
                 },
+                "backend error: obs need access_key_id",
+            ),
+        ];
+
+        for (object_storage, error_message) in test_cases {
+            let url: Url = "obs://test-bucket/file".parse().unwrap();
+            let parsed_url: ParsedURL = url.try_into().unwrap();
+
+            let result = ObjectStorage::new(Scheme::OBS, Arc::new(Config::default()))
+                .unwrap()
+                .operator(&parsed_url, Some(object_storage), Duration::from_secs(3));
+
+            assert!(result.is_err());
+            assert_eq!(result.unwrap_err().to_string(), error_message);
+        }
+    }
+
+    #[test]
+    fn should_return_error_when_cos_lacks_of_info() {
+        let test_cases = vec![
+            (
+                ObjectStorageInfo::default(),
+                "backend error: cos need access_key_id, access_key_secret, endpoint",
             ),
-            (Scheme::GCS, ObjectStorageInfo::default()),
             (
-                Scheme::ABS,
                 ObjectStorageInfo {
-                    endpoint: Some("test-endpoint.local".into()),
-                    access_key_id: Some("access-key-id".into()),
-                    access_key_secret: Some("YWNjZXNzLWtleS1zZWNyZXQK".into()),
+                    access_key_id: Some("access_key_id".into()),
                     ..Default::default()
                 },
+                "backend error: cos need access_key_secret, endpoint",
+            ),
+            (
+                ObjectStorageInfo {
+                    access_key_secret: Some("access_key_secret".into()),
+                    ..Default::default()
+                },
+                "backend error: cos need access_key_id, endpoint",
             ),
             (
-                Scheme::OSS,
                 ObjectStorageInfo {
                     endpoint: Some("test-endpoint.local".into()),
-                    access_key_id: Some("access-key-id".into()),
-                    access_key_secret: Some("access-key-secret".into()),
                     ..Default::default()
                 },
+                "backend error: cos need access_key_id, access_key_secret",
+            ),
+            (
+                ObjectStorageInfo {
+                    access_key_id: Some("access_key_id".into()),
+                    access_key_secret: Some("access_key_secret".into()),
+                    ..Default::default()
+                },
+                "backend error: cos need endpoint",
             ),
             (
-                Scheme::OBS,
                 ObjectStorageInfo {
+                    access_key_id: Some("access_key_id".into()),
                     endpoint: Some("test-endpoint.local".into()),
-                    access_key_id: Some("access-key-id".into()),
-                    access_key_secret: Some("access-key-secret".into()),
                     ..Default::default()
                 },
+                "backend error: cos need access_key_secret",
             ),
             (
-                Scheme::COS,
                 ObjectStorageInfo {
+                    access_key_secret: Some("access_key_secret".into()),
                     endpoint: Some("test-endpoint.local".into()),
-                    access_key_id: Some("access-key-id".into()),
-                    access_key_secret: Some("access-key-secret".into()),
                     ..Default::default()
                 },
+                "backend error: cos need access_key_id",
             ),
         ];
 
-        for (scheme, object_storage) in test_cases {
-            let url: Url = format!("{}://test-bucket/file", scheme).parse().unwrap();
+        for (object_storage, error_message) in test_cases {
+            let url: Url = "cos://test-bucket/file".parse().unwrap();
+            let parsed_url: ParsedURL = url.try_into().unwrap();
+
+            let result = ObjectStorage::new(Scheme::COS, Arc::new(Config::default()))
+                .unwrap()
+                .operator(&parsed_url, Some(object_storage), Duration::from_secs(3));
+
+            assert!(result.is_err());
+            assert_eq!(result.unwrap_err().to_string(), error_message);
+        }
+    }
+
+    #[test]
+    fn should_handle_insecure_skip_verify_parameter() {
+        let test_cases = vec![
+            ObjectStorageInfo {
+                endpoint: Some("https://oss-cn-beijing.aliyuncs.com".into()),
+                access_key_id: Some("test-access-key-id".into()),
+                access_key_secret: Some("test-access-key-secret".into()),
+                insecure_skip_verify: Some(true),
+                ..Default::default()
+            },
+            ObjectStorageInfo {
+                endpoint: Some("https://oss-cn-beijing.aliyuncs.com".into()),
+                access_key_id: Some("test-access-key-id".into()),
+                access_key_secret: Some("test-access-key-secret".into()),
+                insecure_skip_verify: Some(false),
+                ..Default::default()
+            },
+            ObjectStorageInfo {
+                endpoint: Some("https://oss-cn-beijing.aliyuncs.com".into()),
+                access_key_id: Some("test-access-key-id".into()),
+                access_key_secret: Some("test-access-key-secret".into()),
+                insecure_skip_verify: None,
+                ..Default::default()
+            },
+        ];
+
+        for object_storage in test_cases {
+            let config = Arc::new(Config::default());
+            let backend = ObjectStorage::new(Scheme::OSS, config).unwrap();
+            let url: Url = "oss://test-bucket/file".parse().unwrap();
             let parsed_url: ParsedURL = url.try_into().unwrap();
 
-            let result = ObjectStorage::new(scheme, Arc::new(Config::default()))
-                .unwrap()
-                .operator(&parsed_url, Some(object_storage), Duration::from_secs(3));
+            let result =
+                backend.operator(&parsed_url, Some(object_storage), Duration::from_secs(3));
+            assert!(result.is_ok());
+        }
+    }
+
+    #[tokio::test]
+    async fn should_stat_object_with_user_metadata() {
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/test-bucket/file"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("Content-Type", "application/octet-stream")
+                    .insert_header("x-amz-meta-foo", "bar"),
+            )
+            .mount(&server)
+            .await;
+
+        let response = ObjectStorage::new(Scheme::S3, Arc::new(Config::default()))
+            .unwrap()
+            .stat(StatRequest {
+                task_id: "test".to_string(),
+                url: "s3://test-bucket/file".to_string(),
+                http_header: None,
+                timeout: Duration::from_secs(5),
+                client_cert: None,
+                object_storage: Some(mock_object_storage_info(&server)),
+                hdfs: None,
+                hugging_face: None,
+                model_scope: None,
+                page_size: None,
+                continuation_token: None,
+                cancel_token: None,
+                sniff_content_type: false,
+                retry_budget: None,
+                version_id: None,
+                list_metakeys: Vec::new(),
+                filter: None,
+                max_depth: None,
+                endpoint_override: None,
+                expected_bucket_owner: None,
+                head_via_get_fallback: false,
+                start_after: None,
+                stat_timeout: None,
+                trace_context: None,
+            })
+            .await
+            .unwrap();
+
+        assert!(response.success);
+        assert_eq!(response.user_metadata.get("foo"), Some(&"bar".to_string()));
+    }
+
+    #[test]
+    fn should_override_host_of_endpoint() {
+        let (endpoint, authority) =
+            ObjectStorage::override_host("http://10.0.0.1:9000/prefix", "s3.example.com").unwrap();
+        assert_eq!(endpoint, "http://s3.example.com/prefix");
+        assert_eq!(authority.as_str(), "10.0.0.1:9000");
+
+        assert!(matches!(
+            ObjectStorage::override_host("http://10.0.0.1:9000", "s3.example.com/path"),
+            Err(ClientError::ValidationError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn should_send_s3_requests_with_host_header_override() {
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/test-bucket/file"))
+            .and(header("host", "s3.example.com"))
+            .respond_with(ResponseTemplate::new(200).insert_header("Content-Length", "7"))
+            .with_priority(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/test-bucket/file"))
+            .and(header("host", "s3.example.com"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("content"))
+            .with_priority(1)
+            .mount(&server)
+            .await;
+        Mock::given(path("/test-bucket/file"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+
+        // The `s3.example.com` is not resolved, so the requests must connect to the endpoint.
+        let object_storage_info = mock_object_storage_info(&server);
+
+        let mut config = Config::default();
+        config.backend.host_header_overrides =
+            HashMap::from([(server.address().to_string(), "s3.example.com".to_string())]);
+        let backend = ObjectStorage::new(Scheme::S3, Arc::new(config)).unwrap();
+        let response = backend
+            .stat(
+                StatRequest::builder()
+                    .task_id("test")
+                    .url("s3://test-bucket/file")
+                    .object_storage(object_storage_info.clone())
+                    .build(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.content_length, Some(7));
+
+        let mut response = backend
+            .get(
+                GetRequest::builder()
+                    .task_id("test")
+                    .piece_id("test")
+                    .url("s3://test-bucket/file")
+                    .object_storage(object_storage_info.clone())
+                    .build(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.text().await.unwrap(), "content");
+
+        // The endpoint override without the host header override uses its own host.
+        let endpoint_override = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/test-bucket/file"))
+            .and(header(
+                "host",
+                endpoint_override.address().to_string().as_str(),
+            ))
+            .respond_with(ResponseTemplate::new(200).insert_header("Content-Length", "8"))
+            .expect(1)
+            .mount(&endpoint_override)
+            .await;
+
+        let response = backend
+            .stat(
+                StatRequest::builder()
+                    .task_id("test")
+                    .url("s3://test-bucket/file")
+                    .object_storage(object_storage_info)
+                    .endpoint_override(endpoint_override.uri())
+                    .build(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.content_length, Some(8));
+    }
+
+    #[tokio::test]
+    async fn should_set_expected_bucket_owner_on_s3_requests() {
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/test-bucket/file"))
+            .and(header("x-amz-expected-bucket-owner", "123456789012"))
+            .and(signed_header("x-amz-expected-bucket-owner"))
+            .respond_with(ResponseTemplate::new(200).insert_header("Content-Length", "7"))
+            .with_priority(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/test-bucket/file"))
+            .and(header("x-amz-expected-bucket-owner", "123456789012"))
+            .and(signed_header("x-amz-expected-bucket-owner"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("content"))
+            .with_priority(1)
+            .mount(&server)
+            .await;
+        Mock::given(path("/test-bucket/file"))
+            .respond_with(ResponseTemplate::new(403))
+            .mount(&server)
+            .await;
+
+        let object_storage_info = mock_object_storage_info(&server);
+
+        // The expected bucket owner of the request is propagated to the stat and the reader.
+        let backend = ObjectStorage::new(Scheme::S3, Arc::new(Config::default())).unwrap();
+        let response = backend
+            .stat(
+                StatRequest::builder()
+                    .task_id("test")
+                    .url("s3://test-bucket/file")
+                    .object_storage(object_storage_info.clone())
+                    .expected_bucket_owner("123456789012")
+                    .build(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.content_length, Some(7));
+
+        let mut response = backend
+            .get(
+                GetRequest::builder()
+                    .task_id("test")
+                    .piece_id("test")
+                    .url("s3://test-bucket/file")
+                    .object_storage(object_storage_info.clone())
+                    .expected_bucket_owner("123456789012")
+                    .build(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.text().await.unwrap(), "content");
+
+        // The cached operator of the other expected bucket owner is not reused, so the request
+        // fails if the bucket is owned by another account, and the requests without the
+        // expected bucket owner do not send the header.
+        for expected_bucket_owner in [Some("000000000000"), None] {
+            let mut builder = StatRequest::builder()
+                .task_id("test")
+                .url("s3://test-bucket/file")
+                .object_storage(object_storage_info.clone());
+            if let Some(expected_bucket_owner) = expected_bucket_owner {
+                builder = builder.expected_bucket_owner(expected_bucket_owner);
+            }
+
+            let response = backend.stat(builder.build()).await.unwrap();
+            assert!(!response.success);
+            assert_eq!(response.failure_class, Some(FailureClass::Auth));
+        }
+    }
+
+    #[tokio::test]
+    async fn should_return_throttled_error_when_slow_down() {
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/test-bucket/file"))
+            .respond_with(ResponseTemplate::new(503).insert_header("Retry-After", "5"))
+            .mount(&server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/test-bucket"))
+            .and(query_param("list-type", "2"))
+            .respond_with(
+                ResponseTemplate::new(503)
+                    .insert_header("Retry-After", "2")
+                    .set_body_string(
+                        "<Error><Code>SlowDown</Code><Message>Please reduce your request rate.</Message></Error>",
+                    ),
+            )
+            .mount(&server)
+            .await;
+
+        let object_storage = ObjectStorage::new(Scheme::S3, Arc::new(Config::default())).unwrap();
+        for (url, retry_after) in [
+            ("s3://test-bucket/file", Duration::from_secs(5)),
+            ("s3://test-bucket/dir/", Duration::from_secs(2)),
+        ] {
+            let result = object_storage
+                .stat(
+                    StatRequest::builder()
+                        .task_id("test")
+                        .url(url)
+                        .object_storage(mock_object_storage_info(&server))
+                        .build(),
+                )
+                .await;
+
+            match result {
+                Err(ClientError::Throttled {
+                    retry_after: Some(actual),
+                }) => assert_eq!(actual, retry_after),
+                _ => panic!("{} should be throttled", url),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn should_classify_retryable_errors_when_stat_fails() {
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/test-bucket/internal-error"))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&server)
+            .await;
+
+        Mock::given(method("HEAD"))
+            .and(path("/test-bucket/forbidden"))
+            .respond_with(ResponseTemplate::new(403))
+            .mount(&server)
+            .await;
+
+        // The port of the dropped listener refuses the connections.
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let closed_endpoint = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+
+        let object_storage = ObjectStorage::new(Scheme::S3, Arc::new(Config::default())).unwrap();
+        for (url, endpoint, retryable, failure_class) in [
+            ("s3://test-bucket/internal-error", server.uri(), true, None),
+            (
+                "s3://test-bucket/forbidden",
+                server.uri(),
+                false,
+                Some(FailureClass::Auth),
+            ),
+            ("s3://test-bucket/file", closed_endpoint, true, None),
+        ] {
+            let result = object_storage
+                .stat(
+                    StatRequest::builder()
+                        .task_id("test")
+                        .url(url)
+                        .object_storage(ObjectStorageInfo {
+                            region: Some("test-region".into()),
+                            endpoint: Some(endpoint),
+                            access_key_id: Some("access-key-id".into()),
+                            access_key_secret: Some("access-key-secret".into()),
+                            ..Default::default()
+                        })
+                        .build(),
+                )
+                .await;
+
+            // The failed response is converted to the backend error by the callers.
+            let err = match result {
+                Ok(response) => {
+                    assert!(!response.success, "{}", url);
+                    assert_eq!(response.failure_class, failure_class, "{}", url);
+                    ClientError::BackendError(Box::new(BackendError {
+                        message: response.error_message.unwrap_or_default(),
+                        status_code: response.http_status_code,
+                        header: response.http_header,
+                    }))
+                }
+                Err(err) => {
+                    assert!(failure_class.is_none(), "{}: {:?}", url, err);
+                    err
+                }
+            };
+
+            assert_eq!(err.is_retryable(), retryable, "{}: {:?}", url, err);
+        }
+    }
+
+    #[tokio::test]
+    async fn should_fail_over_to_next_endpoint_when_endpoint_unreachable() {
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/test-bucket/file"))
+            .respond_with(ResponseTemplate::new(200).insert_header("Content-Length", "7"))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        // The port of the dropped listener refuses the connections.
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let closed_endpoint = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+
+        let object_storage_info = ObjectStorageInfo {
+            region: Some("test-region".into()),
+            endpoint: Some(format!("{}, {}", closed_endpoint, server.uri())),
+            access_key_id: Some("access-key-id".into()),
+            access_key_secret: Some("access-key-secret".into()),
+            ..Default::default()
+        };
+
+        let object_storage = ObjectStorage::new(Scheme::S3, Arc::new(Config::default())).unwrap();
+        assert_eq!(
+            object_storage.failover_endpoints(Some(&object_storage_info)),
+            Some(vec![closed_endpoint.clone(), server.uri()])
+        );
+
+        for _ in 0..2 {
+            let response = object_storage
+                .stat(
+                    StatRequest::builder()
+                        .task_id("test")
+                        .url("s3://test-bucket/file")
+                        .object_storage(object_storage_info.clone())
+                        .build(),
+                )
+                .await
+                .unwrap();
+            assert!(response.success);
+            assert_eq!(response.content_length, Some(7));
+
+            // The unreachable endpoint is tried last until it recovers.
+            assert_eq!(
+                object_storage.failover_endpoints(Some(&object_storage_info)),
+                Some(vec![server.uri(), closed_endpoint.clone()])
+            );
+        }
+
+        // The recovered endpoint is removed from the unhealthy endpoints.
+        object_storage
+            .unhealthy_endpoints
+            .lock()
+            .put(closed_endpoint.clone(), Instant::now());
+        assert_eq!(
+            object_storage.failover_endpoints(Some(&object_storage_info)),
+            Some(vec![closed_endpoint.clone(), server.uri()])
+        );
+        assert!(object_storage.unhealthy_endpoints.lock().is_empty());
+
+        server.verify().await;
+    }
+
+    #[tokio::test]
+    async fn should_stat_object_with_storage_class() {
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/test-bucket/standard"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+
+        Mock::given(method("HEAD"))
+            .and(path("/test-bucket/archived"))
+            .respond_with(
+                ResponseTemplate::new(200).insert_header("x-amz-storage-class", "GLACIER"),
+            )
+            .mount(&server)
+            .await;
+
+        let object_storage = ObjectStorage::new(Scheme::S3, Arc::new(Config::default())).unwrap();
+        for (key, list_metakeys, storage_class) in [
+            (
+                "standard",
+                vec![ListMetakey::StorageClass],
+                Some("STANDARD"),
+            ),
+            ("archived", vec![ListMetakey::StorageClass], Some("GLACIER")),
+            ("archived", Vec::new(), None),
+        ] {
+            let response = object_storage
+                .stat(
+                    StatRequest::builder()
+                        .task_id("test")
+                        .url(format!("s3://test-bucket/{}", key))
+                        .object_storage(mock_object_storage_info(&server))
+                        .list_metakeys(list_metakeys)
+                        .build(),
+                )
+                .await
+                .unwrap();
+
+            assert!(response.success);
+            assert!(response.accept_ranges);
+            assert_eq!(response.storage_class.as_deref(), storage_class);
+        }
+    }
+
+    #[tokio::test]
+    async fn should_get_object_with_prefetch() {
+        /// RangeResponder responds the range of the content by the Range header.
+        struct RangeResponder(Vec<u8>);
+
+        /// RangeResponder implements the Respond of wiremock.
+        impl wiremock::Respond for RangeResponder {
+            fn respond(&self, request: &wiremock::Request) -> ResponseTemplate {
+                let range = request
+                    .headers
+                    .get("range")
+                    .and_then(|value| value.to_str().ok())
+                    .and_then(|value| value.strip_prefix("bytes="))
+                    .and_then(|value| value.split_once('-'));
+
+                let Some((start, end)) = range else {
+                    return ResponseTemplate::new(200).set_body_bytes(self.0.clone());
+                };
+
+                let start: usize = start.parse().unwrap();
+                let end = match end {
+                    "" => self.0.len() - 1,
+                    end => end.parse::<usize>().unwrap().min(self.0.len() - 1),
+                };
+
+                ResponseTemplate::new(206)
+                    .insert_header(
+                        "Content-Range",
+                        format!("bytes {}-{}/{}", start, end, self.0.len()).as_str(),
+                    )
+                    .set_body_bytes(self.0[start..=end].to_vec())
+            }
+        }
+
+        let content: Vec<u8> = (0..3 * 1024 * 1024 + 123)
+            .map(|i| (i % 251) as u8)
+            .collect();
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/test-bucket/large"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("Content-Length", content.len().to_string().as_str()),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/test-bucket/large"))
+            .respond_with(RangeResponder(content.clone()))
+            .mount(&server)
+            .await;
+
+        // The content read with the prefetch is the same as the content read sequentially.
+        let object_storage = ObjectStorage::new(Scheme::S3, Arc::new(Config::default())).unwrap();
+        for prefetch_chunks in [0, 4] {
+            let mut response = object_storage
+                .get(
+                    GetRequest::builder()
+                        .task_id("test")
+                        .piece_id("test")
+                        .url("s3://test-bucket/large")
+                        .object_storage(mock_object_storage_info(&server))
+                        .read_buffer_size(256 * 1024)
+                        .prefetch_chunks(prefetch_chunks)
+                        .build(),
+                )
+                .await
+                .unwrap();
+
+            let mut buffer = Vec::new();
+            response.reader.read_to_end(&mut buffer).await.unwrap();
+            assert_eq!(buffer.len(), content.len());
+            assert!(buffer == content, "prefetch chunks {}", prefetch_chunks);
+        }
+    }
+
+    #[test]
+    fn should_clamp_prefetch_chunks_by_max_prefetch_chunks() {
+        let mut config = Config::default();
+        config.backend.max_prefetch_chunks = 4;
+        let object_storage = ObjectStorage::new(Scheme::S3, Arc::new(config)).unwrap();
+        assert_eq!(object_storage.prefetch_chunks(0), 0);
+        assert_eq!(object_storage.prefetch_chunks(2), 2);
+        assert_eq!(object_storage.prefetch_chunks(4), 4);
+        assert_eq!(object_storage.prefetch_chunks(1024), 4);
+    }
+
+    #[test]
+    fn should_parse_server_checksum_from_header() {
+        let header = |pairs: &[(&'static str, &'static str)]| {
+            let mut header = reqwest::header::HeaderMap::new();
+            for (name, value) in pairs {
+                header.append(*name, reqwest::header::HeaderValue::from_static(value));
+            }
+
+            header
+        };
+
+        assert_eq!(
+            ServerChecksum::from_header(&header(&[("x-amz-checksum-crc32c", "yZRlqg==")])),
+            Some(ServerChecksum::Crc32c(0xc99465aa))
+        );
+        assert_eq!(
+            ServerChecksum::from_header(&header(&[
+                ("x-amz-checksum-crc32c", "yZRlqg=="),
+                (
+                    "x-amz-checksum-sha256",
+                    "uU0nuZNNPgilLlLX2n2r+sSE7+N6U4DukIj3rOLvzek="
+                ),
+            ])),
+            Some(ServerChecksum::Sha256(
+                sha2::Sha256::digest(b"hello world").to_vec()
+            ))
+        );
+        assert_eq!(
+            ServerChecksum::from_header(&header(&[(
+                "x-goog-hash",
+                "crc32c=yZRlqg==,md5=XrY7u+Ae7tCTyyK7j1rNww=="
+            )])),
+            Some(ServerChecksum::Crc32c(0xc99465aa))
+        );
+
+        // The composite checksum of the multipart object is ignored.
+        assert_eq!(
+            ServerChecksum::from_header(&header(&[("x-amz-checksum-crc32c", "yZRlqg==-3")])),
+            None
+        );
+        assert_eq!(
+            ServerChecksum::from_header(&header(&[
+                ("x-amz-checksum-crc32c", "yZRlqg=="),
+                ("x-amz-checksum-type", "COMPOSITE"),
+            ])),
+            None
+        );
+        assert_eq!(ServerChecksum::from_header(&header(&[])), None);
+    }
+
+    #[tokio::test]
+    async fn should_verify_get_by_server_checksum() {
+        let server = MockServer::start().await;
+        for (key, body) in [("valid", "hello world"), ("corrupted", "hello w0rld")] {
+            Mock::given(method("HEAD"))
+                .and(path(format!("/test-bucket/{}", key)))
+                .respond_with(ResponseTemplate::new(200).insert_header("Content-Length", "11"))
+                .mount(&server)
+                .await;
+
+            // The checksum is declared by the response of the get, whose signature covers the
+            // checksum mode header.
+            Mock::given(method("GET"))
+                .and(path(format!("/test-bucket/{}", key)))
+                .and(header("x-amz-checksum-mode", "ENABLED"))
+                .and(signed_header("x-amz-checksum-mode"))
+                .and(query_param_is_missing("X-Amz-Signature"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .insert_header("x-amz-checksum-crc32c", "yZRlqg==")
+                        .set_body_string(body),
+                )
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+
+        let mut config = Config::default();
+        config.backend.enable_object_storage_checksum_validation = true;
+        let object_storage = ObjectStorage::new(Scheme::S3, Arc::new(config)).unwrap();
+        let get = |key: &str| {
+            object_storage.get(
+                GetRequest::builder()
+                    .task_id("test")
+                    .piece_id("test")
+                    .url(format!("s3://test-bucket/{}", key))
+                    .object_storage(mock_object_storage_info(&server))
+                    .build(),
+            )
+        };
+
+        let mut response = get("valid").await.unwrap();
+        assert_eq!(response.text().await.unwrap(), "hello world");
+
+        let mut response = get("corrupted").await.unwrap();
+        let err = response.text().await.unwrap_err();
+        assert!(err.to_string().contains("checksum mismatch"));
+
+        server.verify().await;
+    }
+
+    #[tokio::test]
+    async fn should_resolve_endpoint_host_by_dns_overrides() {
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/test-bucket/file"))
+            .respond_with(ResponseTemplate::new(200).insert_header("Content-Length", "10"))
+            .mount(&server)
+            .await;
+
+        // The `.invalid` host can not be resolved by the system DNS.
+        let mut config = Config::default();
+        config
+            .backend
+            .dns_overrides
+            .insert("s3.dragonfly.invalid".to_string(), *server.address());
+
+        let object_storage = ObjectStorage::new(Scheme::S3, Arc::new(config)).unwrap();
+        let response = object_storage
+            .stat(
+                StatRequest::builder()
+                    .task_id("test")
+                    .url("s3://test-bucket/file")
+                    .object_storage(ObjectStorageInfo {
+                        region: Some("test-region".into()),
+                        endpoint: Some(format!(
+                            "http://s3.dragonfly.invalid:{}",
+                            server.address().port()
+                        )),
+                        access_key_id: Some("access-key-id".into()),
+                        access_key_secret: Some("access-key-secret".into()),
+                        ..Default::default()
+                    })
+                    .build(),
+            )
+            .await
+            .unwrap();
+
+        assert!(response.success);
+        assert_eq!(response.content_length, Some(10));
+    }
+
+    #[tokio::test]
+    async fn should_share_inflight_stat_of_same_object() {
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/test-bucket/file"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("Content-Length", "10")
+                    .set_delay(Duration::from_millis(500)),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let object_storage = ObjectStorage::new(Scheme::S3, Arc::new(Config::default())).unwrap();
+        let request = StatRequest::builder()
+            .task_id("test")
+            .url("s3://test-bucket/file")
+            .object_storage(mock_object_storage_info(&server))
+            .build();
+
+        let responses =
+            future::join_all((0..50).map(|_| object_storage.stat(request.clone()))).await;
+        for response in responses {
+            assert_eq!(response.unwrap().content_length, Some(10));
+        }
+
+        assert!(object_storage.inflight_stats.lock().is_empty());
+        server.verify().await;
+    }
+
+    #[tokio::test]
+    async fn should_not_share_inflight_stat_of_different_operators() {
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/test-bucket/file"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("Content-Length", "10")
+                    .set_delay(Duration::from_millis(500)),
+            )
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        // The stats with the different secrets do not join the same in-flight stat.
+        let object_storage = ObjectStorage::new(Scheme::S3, Arc::new(Config::default())).unwrap();
+        let request = |access_key_secret: &str| {
+            StatRequest::builder()
+                .task_id("test")
+                .url("s3://test-bucket/file")
+                .object_storage(ObjectStorageInfo {
+                    region: Some("test-region".into()),
+                    endpoint: Some(server.uri()),
+                    access_key_id: Some("access-key-id".into()),
+                    access_key_secret: Some(access_key_secret.into()),
+                    ..Default::default()
+                })
+                .build()
+        };
+
+        let (first, second) = tokio::join!(
+            object_storage.stat(request("access-key-secret")),
+            object_storage.stat(request("other-access-key-secret"))
+        );
+        assert!(first.unwrap().success);
+        assert!(second.unwrap().success);
+        server.verify().await;
+
+        // The in-flight stat abandoned by the timed out stat is removed.
+        let slow_server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/test-bucket/file"))
+            .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_secs(5)))
+            .mount(&slow_server)
+            .await;
+
+        let result = object_storage
+            .stat(
+                StatRequest::builder()
+                    .task_id("test")
+                    .url("s3://test-bucket/file")
+                    .object_storage(ObjectStorageInfo {
+                        region: Some("test-region".into()),
+                        endpoint: Some(slow_server.uri()),
+                        access_key_id: Some("access-key-id".into()),
+                        access_key_secret: Some("access-key-secret".into()),
+                        ..Default::default()
+                    })
+                    .stat_timeout(Duration::from_millis(100))
+                    .build(),
+            )
+            .await;
+        assert!(result.is_err());
+        assert!(object_storage.inflight_stats.lock().is_empty());
+    }
+
+    #[tokio::test]
+    async fn should_sniff_content_type_without_metadata() {
+        let png = [
+            0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x48,
+            0x44, 0x52,
+        ];
+        let gzip = [
+            0x1f, 0x8b, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x03, 0x03, 0x00, 0x00, 0x00,
+            0x00, 0x00,
+        ];
+
+        let server = MockServer::start().await;
+        for (key, body) in [("image", png), ("archive", gzip)] {
+            Mock::given(method("HEAD"))
+                .and(path(format!("/test-bucket/{}", key)))
+                .respond_with(ResponseTemplate::new(200).set_body_bytes(body.to_vec()))
+                .mount(&server)
+                .await;
+
+            Mock::given(method("GET"))
+                .and(path(format!("/test-bucket/{}", key)))
+                .respond_with(
+                    ResponseTemplate::new(206)
+                        .insert_header(
+                            "content-range",
+                            format!("bytes 0-{}/{}", body.len() - 1, body.len()),
+                        )
+                        .set_body_bytes(body.to_vec()),
+                )
+                .mount(&server)
+                .await;
+        }
+
+        let object_storage = ObjectStorage::new(Scheme::S3, Arc::new(Config::default())).unwrap();
+        let stat = |key: &str, sniff_content_type: bool| {
+            object_storage.stat(
+                StatRequest::builder()
+                    .task_id("test")
+                    .url(format!("s3://test-bucket/{}", key))
+                    .object_storage(mock_object_storage_info(&server))
+                    .sniff_content_type(sniff_content_type)
+                    .build(),
+            )
+        };
+
+        let response = stat("image", true).await.unwrap();
+        assert_eq!(response.content_type.as_deref(), Some("image/png"));
+
+        let response = stat("archive", true).await.unwrap();
+        assert_eq!(response.content_type.as_deref(), Some("application/gzip"));
+
+        let response = stat("image", false).await.unwrap();
+        assert!(response.content_type.is_none());
+    }
+
+    #[tokio::test]
+    async fn should_stat_directory_without_stat_request() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(query_param("list-type", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+  <Name>test-bucket</Name>
+  <Prefix>dir/</Prefix>
+  <KeyCount>2</KeyCount>
+  <MaxKeys>1000</MaxKeys>
+  <IsTruncated>false</IsTruncated>
+  <Contents>
+    <Key>dir/file1</Key>
+    <Size>1</Size>
+  </Contents>
+  <Contents>
+    <Key>dir/file2</Key>
+    <Size>2</Size>
+  </Contents>
+</ListBucketResult>"#,
+            ))
+            .mount(&server)
+            .await;
+
+        // The directory key is not an object, so the stat request should not be sent.
+        Mock::given(method("HEAD"))
+            .respond_with(ResponseTemplate::new(404))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let response = ObjectStorage::new(Scheme::S3, Arc::new(Config::default()))
+            .unwrap()
+            .stat(StatRequest {
+                task_id: "test".to_string(),
+                url: "s3://test-bucket/dir/".to_string(),
+                http_header: None,
+                timeout: Duration::from_secs(5),
+                client_cert: None,
+                object_storage: Some(mock_object_storage_info(&server)),
+                hdfs: None,
+                hugging_face: None,
+                model_scope: None,
+                page_size: None,
+                continuation_token: None,
+                cancel_token: None,
+                sniff_content_type: false,
+                retry_budget: None,
+                version_id: None,
+                list_metakeys: Vec::new(),
+                filter: None,
+                max_depth: None,
+                endpoint_override: None,
+                expected_bucket_owner: None,
+                head_via_get_fallback: false,
+                start_after: None,
+                stat_timeout: None,
+                trace_context: None,
+            })
+            .await
+            .unwrap();
+
+        assert!(response.success);
+        assert_eq!(response.content_length, None);
+
+        let mut entries: Vec<(String, usize)> = response
+            .entries
+            .into_iter()
+            .map(|entry| (entry.url, entry.content_length))
+            .collect();
+        entries.sort();
+        assert_eq!(
+            entries,
+            vec![
+                ("s3://test-bucket/dir/file1".to_string(), 1),
+                ("s3://test-bucket/dir/file2".to_string(), 2),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn should_filter_directory_entries_by_glob() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(query_param("list-type", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+  <Name>test-bucket</Name>
+  <Prefix>dir/</Prefix>
+  <KeyCount>6</KeyCount>
+  <MaxKeys>1000</MaxKeys>
+  <IsTruncated>false</IsTruncated>
+  <Contents><Key>dir/a.json</Key><Size>1</Size></Contents>
+  <Contents><Key>dir/b.tar.gz</Key><Size>1</Size></Contents>
+  <Contents><Key>dir/data/c</Key><Size>1</Size></Contents>
+  <Contents><Key>dir/sub/d.json</Key><Size>1</Size></Contents>
+  <Contents><Key>dir/sub/data/e</Key><Size>1</Size></Contents>
+  <Contents><Key>dir/sub/data/deep/f</Key><Size>1</Size></Contents>
+</ListBucketResult>"#,
+            ))
+            .mount(&server)
+            .await;
+
+        let object_storage = ObjectStorage::new(Scheme::S3, Arc::new(Config::default())).unwrap();
+        let stat = |filter: &'static str| {
+            object_storage.stat(
+                StatRequest::builder()
+                    .task_id("test")
+                    .url("s3://test-bucket/dir/")
+                    .object_storage(mock_object_storage_info(&server))
+                    .filter(filter)
+                    .build(),
+            )
+        };
+
+        let test_cases = vec![
+            ("*.json", vec!["s3://test-bucket/dir/a.json"]),
+            (
+                "**/*.json",
+                vec![
+                    "s3://test-bucket/dir/a.json",
+                    "s3://test-bucket/dir/sub/d.json",
+                ],
+            ),
+            (
+                "**/data/*",
+                vec![
+                    "s3://test-bucket/dir/data/c",
+                    "s3://test-bucket/dir/sub/data/e",
+                ],
+            ),
+            ("sub/?.json", vec!["s3://test-bucket/dir/sub/d.json"]),
+        ];
+
+        for (filter, expected) in test_cases {
+            let response = stat(filter).await.unwrap();
+            let mut urls: Vec<String> = response
+                .entries
+                .into_iter()
+                .map(|entry| entry.url)
+                .collect();
+            urls.sort();
+            assert_eq!(urls, expected, "{}", filter);
+        }
+
+        let result = stat("[").await;
+        assert!(matches!(result, Err(ClientError::ValidationError(_))));
+    }
+
+    #[test]
+    fn should_get_depth_boundary_of_entry() {
+        let url: Url = "s3://test-bucket/dir/".parse().unwrap();
+        let parsed_url: ParsedURL = url.try_into().unwrap();
 
-            assert!(
-                result.is_ok(),
-                "can not get {} operator, due to: {}",
-                scheme,
-                result.unwrap_err()
+        let test_cases = vec![
+            ("dir/file", 1, None),
+            ("dir/a/file", 1, Some("dir/a/")),
+            ("dir/a/file", 2, None),
+            ("dir/a/b/", 2, None),
+            ("dir/a/b/c/file", 2, Some("dir/a/b/")),
+        ];
+
+        for (entry_path, max_depth, expected) in test_cases {
+            assert_eq!(
+                parsed_url.depth_boundary(entry_path, max_depth).as_deref(),
+                expected,
+                "{} {}",
+                entry_path,
+                max_depth
             );
         }
     }
 
+    #[tokio::test]
+    async fn should_limit_directory_listing_by_max_depth() {
+        let list_result = |keys: &[&str]| {
+            let contents: String = keys
+                .iter()
+                .map(|key| format!("<Contents><Key>{}</Key><Size>1</Size></Contents>", key))
+                .collect();
+
+            format!(
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+  <Name>test-bucket</Name>
+  <Prefix>dir/</Prefix>
+  <KeyCount>{}</KeyCount>
+  <MaxKeys>1000</MaxKeys>
+  <IsTruncated>false</IsTruncated>
+  {}
+</ListBucketResult>"#,
+                keys.len(),
+                contents
+            )
+        };
+
+        // The 5-level-deep tree is listed, and the listing restarts after the subtree of each
+        // directory at the max depth.
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(query_param("list-type", "2"))
+            .and(query_param_is_missing("start-after"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(list_result(&[
+                "dir/f0",
+                "dir/l1/f1",
+                "dir/l1/l2/f2",
+                "dir/l1/l2/l3/f3",
+                "dir/l1/l2/l3/l4/f4",
+                "dir/l1/l2/l3/l4/l5/f5",
+                "dir/z1/z2/z3/f6",
+            ])))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(query_param("list-type", "2"))
+            .and(query_param(
+                "start-after",
+                format!("dir/l1/l2/{}", char::MAX).as_str(),
+            ))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_string(list_result(&["dir/z1/z2/z3/f6"])),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(query_param("list-type", "2"))
+            .and(query_param(
+                "start-after",
+                format!("dir/z1/z2/{}", char::MAX).as_str(),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_string(list_result(&[])))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let response = ObjectStorage::new(Scheme::S3, Arc::new(Config::default()))
+            .unwrap()
+            .stat(
+                StatRequest::builder()
+                    .task_id("test")
+                    .url("s3://test-bucket/dir/")
+                    .object_storage(mock_object_storage_info(&server))
+                    .max_depth(2)
+                    .build(),
+            )
+            .await
+            .unwrap();
+
+        let entries: Vec<(String, bool)> = response
+            .entries
+            .into_iter()
+            .map(|entry| (entry.url, entry.is_dir))
+            .collect();
+        assert_eq!(
+            entries,
+            vec![
+                ("s3://test-bucket/dir/f0".to_string(), false),
+                ("s3://test-bucket/dir/l1/f1".to_string(), false),
+                ("s3://test-bucket/dir/l1/l2/".to_string(), true),
+                ("s3://test-bucket/dir/z1/z2/".to_string(), true),
+            ]
+        );
+    }
+
     #[test]
-    fn should_get_s3_operator_with_extra_info() {
-        let test_cases = vec![
-            ObjectStorageInfo {
-                access_key_id: Some("access_key_id".into()),
-                access_key_secret: Some("access_key_secret".into()),
-                region: Some("test-region".into()),
+    fn should_expand_env_in_endpoint() {
+        let allowlist = vec!["REGION".to_string()];
+        let lookup = |name: &str| (name == "REGION").then(|| "cn-hangzhou".to_string());
+        assert_eq!(
+            expand_env("https://${REGION}.oss.example.com", &allowlist, lookup).unwrap(),
+            "https://cn-hangzhou.oss.example.com"
+        );
+        assert_eq!(
+            expand_env("https://oss.example.com", &allowlist, lookup).unwrap(),
+            "https://oss.example.com"
+        );
 
-                endpoint: Some("test-endpoint.local".into()),
-                ..Default::default()
-            },
-            ObjectStorageInfo {
-                access_key_id: Some("access_key_id".into()),
-                access_key_secret: Some("access_key_secret".into()),
-                region: Some("test-region".into()),
+        // The variable not in the allowlist is never looked up.
+        let result = expand_env(
+            "https://${AWS_SECRET_ACCESS_KEY}.attacker.com",
+            &allowlist,
+            |_| panic!("the variable not in the allowlist is looked up"),
+        );
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "backend error: environment variable AWS_SECRET_ACCESS_KEY is not allowed in \
+             https://${AWS_SECRET_ACCESS_KEY}.attacker.com"
+        );
 
-                session_token: Some("session_token".into()),
+        // The endpoint of the request is not expanded without the allowlist.
+        let object_storage = ObjectStorage::new(Scheme::OSS, Arc::new(Config::default())).unwrap();
+        let parsed_url: ParsedURL = "oss://test-bucket/file"
+            .parse::<Url>()
+            .unwrap()
+            .try_into()
+            .unwrap();
+        let result = object_storage.operator(
+            &parsed_url,
+            Some(ObjectStorageInfo {
+                endpoint: Some("https://${REGION}.oss.example.com".into()),
+                access_key_id: Some("access-key-id".into()),
+                access_key_secret: Some("access-key-secret".into()),
                 ..Default::default()
-            },
-            ObjectStorageInfo {
-                access_key_id: Some("access_key_id".into()),
-                access_key_secret: Some("access_key_secret".into()),
-                region: Some("test-region".into()),
+            }),
+            Duration::from_secs(3),
+        );
+        assert!(result.is_err());
+    }
 
-                endpoint: Some("test-endpoint.local".into()),
-                session_token: Some("session_token".into()),
-                ..Default::default()
-            },
-        ];
+    #[test]
+    fn should_return_error_when_env_in_endpoint_undefined() {
+        let allowlist = vec!["REGION".to_string()];
+        let result = expand_env("https://${REGION}.oss.example.com", &allowlist, |_| None);
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "backend error: environment variable REGION is undefined in \
+             https://${REGION}.oss.example.com"
+        );
 
-        for object_storage in test_cases {
-            let url: Url = "s3://test-bucket/file".parse().unwrap();
-            let parsed_url: ParsedURL = url.try_into().unwrap();
+        let result = expand_env("https://${REGION.oss.example.com", &allowlist, |_| None);
+        assert!(result.is_err());
+    }
 
-            let result = ObjectStorage::new(Scheme::S3, Arc::new(Config::default()))
-                .unwrap()
-                .operator(&parsed_url, Some(object_storage), Duration::from_secs(3));
+    #[tokio::test]
+    async fn should_time_out_slow_stat_by_stat_timeout() {
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/test-bucket/file"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("Content-Length", "10")
+                    .set_delay(Duration::from_secs(5)),
+            )
+            .mount(&server)
+            .await;
+
+        let object_storage = ObjectStorage::new(Scheme::S3, Arc::new(Config::default())).unwrap();
+        let result = object_storage
+            .stat(
+                StatRequest::builder()
+                    .task_id("test")
+                    .url("s3://test-bucket/file")
+                    .object_storage(mock_object_storage_info(&server))
+                    .timeout(Duration::from_secs(60))
+                    .stat_timeout(Duration::from_millis(100))
+                    .build(),
+            )
+            .await;
+        assert!(matches!(
+            result,
+            Err(ClientError::TokioTimeErrorElapsed(..))
+        ));
+    }
 
-            assert!(result.is_ok());
-            assert_eq!(result.unwrap().info().scheme().to_string(), "s3");
+    #[tokio::test]
+    async fn should_map_request_time_too_skewed_to_clock_skew() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/test-bucket"))
+            .and(query_param("list-type", "2"))
+            .respond_with(
+                ResponseTemplate::new(403)
+                    .insert_header("Date", "Tue, 15 Nov 1994 08:12:31 GMT")
+                    .set_body_string(
+                        "<Error><Code>RequestTimeTooSkewed</Code><Message>The difference between the request time and the current time is too large.</Message></Error>",
+                    ),
+            )
+            .mount(&server)
+            .await;
+
+        let object_storage = ObjectStorage::new(Scheme::S3, Arc::new(Config::default())).unwrap();
+        let result = object_storage
+            .stat(
+                StatRequest::builder()
+                    .task_id("test")
+                    .url("s3://test-bucket/dir/")
+                    .object_storage(mock_object_storage_info(&server))
+                    .build(),
+            )
+            .await;
+
+        let err = result.err().unwrap();
+        assert!(err.to_string().contains("NTP"));
+        match err {
+            ClientError::ClockSkew { server_time } => {
+                assert_eq!(
+                    server_time,
+                    Some(
+                        chrono::DateTime::parse_from_rfc2822("Tue, 15 Nov 1994 08:12:31 GMT")
+                            .unwrap()
+                            .with_timezone(&chrono::Utc)
+                    )
+                );
+            }
+            _ => panic!("request time too skewed should be mapped to clock skew"),
+        }
+    }
+
+    #[tokio::test]
+    async fn should_list_directory_after_start_after() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(query_param("list-type", "2"))
+            .and(query_param("start-after", "dir/file2"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+  <Name>test-bucket</Name>
+  <Prefix>dir/</Prefix>
+  <KeyCount>2</KeyCount>
+  <MaxKeys>1000</MaxKeys>
+  <IsTruncated>false</IsTruncated>
+  <Contents><Key>dir/file3</Key><Size>1</Size></Contents>
+  <Contents><Key>dir/file4</Key><Size>1</Size></Contents>
+</ListBucketResult>"#,
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let object_storage = ObjectStorage::new(Scheme::S3, Arc::new(Config::default())).unwrap();
+        let response = object_storage
+            .stat(
+                StatRequest::builder()
+                    .task_id("test")
+                    .url("s3://test-bucket/dir/")
+                    .object_storage(mock_object_storage_info(&server))
+                    .start_after("dir/file2")
+                    .build(),
+            )
+            .await
+            .unwrap();
+
+        let entries: Vec<String> = response.entries.into_iter().map(|e| e.url).collect();
+        assert_eq!(
+            entries,
+            vec![
+                "s3://test-bucket/dir/file3".to_string(),
+                "s3://test-bucket/dir/file4".to_string()
+            ]
+        );
+        server.verify().await;
+    }
+
+    #[tokio::test]
+    async fn should_paginate_directory_listing() {
+        let list_body = |keys: &[&str]| {
+            let contents: String = keys
+                .iter()
+                .map(|key| format!("<Contents><Key>{}</Key><Size>1</Size></Contents>", key))
+                .collect();
+            format!(
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+  <Name>test-bucket</Name>
+  <Prefix>dir/</Prefix>
+  <KeyCount>{}</KeyCount>
+  <MaxKeys>1000</MaxKeys>
+  <IsTruncated>false</IsTruncated>
+  {}
+</ListBucketResult>"#,
+                keys.len(),
+                contents
+            )
+        };
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(query_param("list-type", "2"))
+            .and(query_param_is_missing("start-after"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(list_body(&[
+                "dir/file1",
+                "dir/file2",
+                "dir/file3",
+            ])))
+            .mount(&server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(query_param("list-type", "2"))
+            .and(query_param("start-after", "dir/file2"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(list_body(&["dir/file3"])))
+            .mount(&server)
+            .await;
+
+        let object_storage = ObjectStorage::new(Scheme::S3, Arc::new(Config::default())).unwrap();
+        let stat = |page_size: Option<usize>, continuation_token: Option<String>| {
+            object_storage.stat(StatRequest {
+                task_id: "test".to_string(),
+                url: "s3://test-bucket/dir/".to_string(),
+                http_header: None,
+                timeout: Duration::from_secs(5),
+                client_cert: None,
+                object_storage: Some(mock_object_storage_info(&server)),
+                hdfs: None,
+                hugging_face: None,
+                model_scope: None,
+                page_size,
+                continuation_token,
+                cancel_token: None,
+                sniff_content_type: false,
+                retry_budget: None,
+                version_id: None,
+                list_metakeys: Vec::new(),
+                filter: None,
+                max_depth: None,
+                endpoint_override: None,
+                expected_bucket_owner: None,
+                head_via_get_fallback: false,
+                start_after: None,
+                stat_timeout: None,
+                trace_context: None,
+            })
+        };
+
+        let response = stat(None, None).await.unwrap();
+        assert!(response.next_continuation_token.is_none());
+        let all_entries: Vec<String> = response.entries.into_iter().map(|e| e.url).collect();
+        assert_eq!(all_entries.len(), 3);
+
+        let first_page = stat(Some(2), None).await.unwrap();
+        assert_eq!(first_page.entries.len(), 2);
+        assert_eq!(
+            first_page.next_continuation_token,
+            Some("dir/file2".to_string())
+        );
+
+        let second_page = stat(Some(2), first_page.next_continuation_token)
+            .await
+            .unwrap();
+        assert_eq!(second_page.entries.len(), 1);
+        assert!(second_page.next_continuation_token.is_none());
+
+        let paged_entries: Vec<String> = first_page
+            .entries
+            .into_iter()
+            .chain(second_page.entries)
+            .map(|e| e.url)
+            .collect();
+        assert_eq!(paged_entries, all_entries);
+    }
+
+    #[tokio::test]
+    async fn should_populate_list_metakeys_of_entries() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(query_param("list-type", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+  <Name>test-bucket</Name>
+  <Prefix>dir/</Prefix>
+  <KeyCount>2</KeyCount>
+  <MaxKeys>1000</MaxKeys>
+  <IsTruncated>false</IsTruncated>
+  <Contents>
+    <Key>dir/file1</Key>
+    <Size>1</Size>
+    <ETag>"etag1"</ETag>
+    <LastModified>2026-01-01T00:00:00.000Z</LastModified>
+  </Contents>
+  <Contents>
+    <Key>dir/file2</Key>
+    <Size>2</Size>
+    <ETag>"etag2"</ETag>
+    <LastModified>2026-01-02T00:00:00.000Z</LastModified>
+  </Contents>
+</ListBucketResult>"#,
+            ))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let object_storage = ObjectStorage::new(Scheme::S3, Arc::new(Config::default())).unwrap();
+        let stat = |list_metakeys: Vec<ListMetakey>| {
+            object_storage.stat(
+                StatRequest::builder()
+                    .task_id("test")
+                    .url("s3://test-bucket/dir/")
+                    .object_storage(mock_object_storage_info(&server))
+                    .list_metakeys(list_metakeys)
+                    .build(),
+            )
+        };
+
+        // The etags are populated from the listing without stating each entry.
+        let response = stat(vec![ListMetakey::Etag, ListMetakey::LastModified])
+            .await
+            .unwrap();
+        let mut entries: Vec<(String, Option<String>)> = response
+            .entries
+            .iter()
+            .map(|entry| {
+                let etag = entry.etag.as_deref().map(|etag| etag.trim_matches('"'));
+                (entry.url.clone(), etag.map(str::to_string))
+            })
+            .collect();
+        entries.sort();
+        assert_eq!(
+            entries,
+            vec![
+                (
+                    "s3://test-bucket/dir/file1".to_string(),
+                    Some("etag1".to_string())
+                ),
+                (
+                    "s3://test-bucket/dir/file2".to_string(),
+                    Some("etag2".to_string())
+                ),
+            ]
+        );
+        assert!(response
+            .entries
+            .iter()
+            .all(|entry| entry.last_modified.is_some() && entry.content_type.is_none()));
+
+        // The metadata is not populated if it is not requested.
+        let response = stat(Vec::new()).await.unwrap();
+        assert!(response
+            .entries
+            .iter()
+            .all(|entry| entry.etag.is_none() && entry.last_modified.is_none()));
+        server.verify().await;
+    }
+
+    #[tokio::test]
+    async fn should_resolve_prefix_without_trailing_slash_as_dir() {
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/test-bucket/dir"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+
+        Mock::given(method("HEAD"))
+            .and(path("/test-bucket/file"))
+            .respond_with(ResponseTemplate::new(200).insert_header("Content-Length", "1"))
+            .mount(&server)
+            .await;
+
+        Mock::given(method("HEAD"))
+            .and(path("/test-bucket/missing"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(query_param("list-type", "2"))
+            .and(query_param("prefix", "dir/"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+  <Name>test-bucket</Name>
+  <Prefix>dir/</Prefix>
+  <KeyCount>2</KeyCount>
+  <MaxKeys>1000</MaxKeys>
+  <IsTruncated>false</IsTruncated>
+  <Contents>
+    <Key>dir/file1</Key>
+    <Size>1</Size>
+  </Contents>
+  <Contents>
+    <Key>dir/file2</Key>
+    <Size>2</Size>
+  </Contents>
+</ListBucketResult>"#,
+            ))
+            .mount(&server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(query_param("list-type", "2"))
+            .and(query_param("prefix", "missing/"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+  <Name>test-bucket</Name>
+  <Prefix>missing/</Prefix>
+  <KeyCount>0</KeyCount>
+  <MaxKeys>1000</MaxKeys>
+  <IsTruncated>false</IsTruncated>
+</ListBucketResult>"#,
+            ))
+            .mount(&server)
+            .await;
+
+        let object_storage_info = mock_object_storage_info(&server);
+
+        let object_storage = ObjectStorage::new(Scheme::S3, Arc::new(Config::default())).unwrap();
+        let stat = |url: &str| {
+            object_storage.stat(
+                StatRequest::builder()
+                    .task_id("test")
+                    .url(url)
+                    .object_storage(object_storage_info.clone())
+                    .build(),
+            )
+        };
+
+        // The prefix without the trailing slash is resolved as a directory with the entries.
+        let response = stat("s3://test-bucket/dir").await.unwrap();
+        assert!(response.is_dir);
+        let mut urls: Vec<String> = response
+            .entries
+            .iter()
+            .map(|entry| entry.url.clone())
+            .collect();
+        urls.sort();
+        assert_eq!(
+            urls,
+            vec![
+                "s3://test-bucket/dir/file1".to_string(),
+                "s3://test-bucket/dir/file2".to_string(),
+            ]
+        );
+
+        let response = stat("s3://test-bucket/file").await.unwrap();
+        assert!(!response.is_dir);
+        assert_eq!(response.content_length, Some(1));
+
+        // The key that is neither an object nor a prefix still returns not found.
+        let response = stat("s3://test-bucket/missing").await.unwrap();
+        assert!(!response.success);
+        assert_eq!(response.failure_class, Some(FailureClass::NotFound));
+    }
+
+    #[tokio::test]
+    async fn should_address_keys_with_special_characters_for_each_scheme() {
+        // The keys are the percent-encoded keys of the URL path and the raw keys.
+        let keys = [
+            ("dir/a%20b", "dir/a b"),
+            ("dir/a%23b", "dir/a#b"),
+            ("dir/a%3Fb", "dir/a?b"),
+            ("dir/%E6%A8%A1%E5%9E%8B", "dir/模型"),
+        ];
+
+        for scheme in [
+            Scheme::S3,
+            Scheme::GCS,
+            Scheme::ABS,
+            Scheme::OSS,
+            Scheme::OBS,
+            Scheme::COS,
+        ] {
+            let object_storage = ObjectStorage::new(scheme, Arc::new(Config::default())).unwrap();
+            for (encoded_key, raw_key) in keys {
+                let url: Url = format!("{}://test-bucket/{}", scheme, encoded_key)
+                    .parse()
+                    .unwrap();
+                let parsed_url = object_storage.parse_url(url.clone()).unwrap();
+
+                // The raw key is handed to the operator, and the entry URL made by the raw key
+                // addresses the same object.
+                assert_eq!(parsed_url.key, raw_key, "{} {}", scheme, encoded_key);
+                assert_eq!(parsed_url.make_url_by_entry_path(raw_key), url);
+
+                // GCS presigns with the service account credential only, the other services
+                // encode the raw key of the presigned request internally.
+                if scheme == Scheme::GCS {
+                    continue;
+                }
+
+                let operator = object_storage
+                    .operator(
+                        &parsed_url,
+                        Some(scheme_object_storage_info(scheme)),
+                        Duration::from_secs(3),
+                    )
+                    .unwrap();
+                let presigned_request = operator
+                    .presign_read(&parsed_url.key, Duration::from_secs(60))
+                    .await
+                    .unwrap();
+                assert!(
+                    presigned_request.uri().path().ends_with(encoded_key),
+                    "{} addresses {} instead of {}",
+                    scheme,
+                    presigned_request.uri().path(),
+                    encoded_key
+                );
+            }
         }
     }
 
     #[test]
-    fn should_get_gcs_operator_with_extra_info() {
-        let test_cases = vec![
-            ObjectStorageInfo {
-                credential_path: Some("credential_path".into()),
-                ..Default::default()
-            },
-            ObjectStorageInfo {
-                endpoint: Some("test-endpoint".into()),
-                ..Default::default()
-            },
-            ObjectStorageInfo {
-                predefined_acl: Some("predefine_acl".into()),
-                ..Default::default()
-            },
-            ObjectStorageInfo {
-                credential_path: Some("credential_path".into()),
-                endpoint: Some("test-endpoint".into()),
-                ..Default::default()
-            },
-            ObjectStorageInfo {
-                credential_path: Some("credential_path".into()),
-                predefined_acl: Some("predefine_acl".into()),
-                ..Default::default()
-            },
-            ObjectStorageInfo {
-                endpoint: Some("test-endpoint".into()),
-                predefined_acl: Some("predefine_acl".into()),
-                ..Default::default()
-            },
-            ObjectStorageInfo {
-                credential_path: Some("credential_path".into()),
-                endpoint: Some("test-endpoint".into()),
-                predefined_acl: Some("predefine_acl".into()),
-                ..Default::default()
-            },
+    fn should_normalize_key() {
+        let keys = [
+            ("a/b/c", "a/b/c"),
+            ("/a/b/c", "a/b/c"),
+            ("a//b/c", "a//b/c"),
+            ("//a/b/c", "/a/b/c"),
+            ("a/b/", "a/b/"),
+            ("/a//b//", "a//b//"),
+            ("/", ""),
+            ("", ""),
         ];
 
-        for object_storage in test_cases {
-            let url: Url = "gs://test-bucket/file".parse().unwrap();
-            let parsed_url: ParsedURL = url.try_into().unwrap();
+        for (key, expected) in keys {
+            assert_eq!(ParsedURL::normalize_key(key), expected, "{}", key);
+        }
+    }
 
-            let result = ObjectStorage::new(Scheme::GCS, Arc::new(Config::default()))
-                .unwrap()
-                .operator(&parsed_url, Some(object_storage), Duration::from_secs(3));
+    #[tokio::test]
+    async fn should_resolve_key_without_leading_slash_for_each_scheme() {
+        for scheme in [
+            Scheme::S3,
+            Scheme::GCS,
+            Scheme::ABS,
+            Scheme::OSS,
+            Scheme::OBS,
+            Scheme::COS,
+            Scheme::OCI,
+            Scheme::S3Compatible,
+        ] {
+            let object_storage = ObjectStorage::new(scheme, Arc::new(Config::default())).unwrap();
+            // The repeated slashes address the different object.
+            let url: Url = format!("{}://test-bucket/a//b/c", scheme).parse().unwrap();
+            assert_eq!(
+                object_storage.parse_url(url).unwrap().key,
+                "a//b/c",
+                "{}",
+                scheme
+            );
 
-            assert!(result.is_ok());
-            assert_eq!(result.unwrap().info().scheme().to_string(), "gcs");
+            for path in ["a/b/c", "/a/b/c"] {
+                let url: Url = format!("{}://test-bucket/{}", scheme, path)
+                    .parse()
+                    .unwrap();
+                let parsed_url = object_storage.parse_url(url).unwrap();
+                assert_eq!(parsed_url.key, "a/b/c", "{} {}", scheme, path);
+
+                // GCS presigns with the service account credential only.
+                if scheme == Scheme::GCS {
+                    continue;
+                }
+
+                let operator = object_storage
+                    .operator(
+                        &parsed_url,
+                        Some(scheme_object_storage_info(scheme)),
+                        Duration::from_secs(3),
+                    )
+                    .unwrap();
+                let presigned_request = operator
+                    .presign_read(&parsed_url.key, Duration::from_secs(60))
+                    .await
+                    .unwrap();
+                let presigned_path = presigned_request.uri().path();
+                assert!(
+                    presigned_path.ends_with("/a/b/c") && !presigned_path.contains("//"),
+                    "{} addresses {} for {}",
+                    scheme,
+                    presigned_path,
+                    path
+                );
+            }
         }
     }
 
+    #[tokio::test]
+    async fn should_get_object_with_special_characters_in_key() {
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/test-bucket/dir/a%20b%23c%3Fd%E2%9C%93"))
+            .respond_with(ResponseTemplate::new(200).insert_header("Content-Length", "7"))
+            .mount(&server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/test-bucket/dir/a%20b%23c%3Fd%E2%9C%93"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("content"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let object_storage = ObjectStorage::new(Scheme::S3, Arc::new(Config::default())).unwrap();
+        let mut response = object_storage
+            .get(
+                GetRequest::builder()
+                    .task_id("test")
+                    .piece_id("test")
+                    .url("s3://test-bucket/dir/a%20b%23c%3Fd%E2%9C%93")
+                    .object_storage(mock_object_storage_info(&server))
+                    .build(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.content_length, Some(7));
+        assert_eq!(response.text().await.unwrap(), "content");
+        server.verify().await;
+    }
+
     #[test]
-    fn should_return_error_when_lacks_of_info() {
-        let url: Url = "s3://test-bucket/file".parse().unwrap();
-        let parsed_url: ParsedURL = url.try_into().unwrap();
+    fn should_keep_raw_key_when_decoding_disabled() {
+        let url: Url = "s3://test-bucket/dir/100%25.txt".parse().unwrap();
+        let object_storage = ObjectStorage::new(Scheme::S3, Arc::new(Config::default())).unwrap();
+        assert_eq!(
+            object_storage.parse_url(url.clone()).unwrap().key,
+            "dir/100%.txt"
+        );
+
+        let mut config = Config::default();
+        config.backend.decode_object_storage_key = false;
+        let object_storage = ObjectStorage::new(Scheme::S3, Arc::new(config)).unwrap();
+        assert_eq!(object_storage.parse_url(url).unwrap().key, "dir/100%25.txt");
+
+        // The key is verbatim including the leading slash.
+        let url: Url = "s3://test-bucket//dir/100%25.txt".parse().unwrap();
+        assert_eq!(
+            object_storage.parse_url(url).unwrap().key,
+            "/dir/100%25.txt"
+        );
+
+        // The path-style URL keeps the bucket out of the raw key.
+        let url: Url = "s3://s3.us-east-1.amazonaws.com/test-bucket/dir/100%25.txt"
+            .parse()
+            .unwrap();
+        assert_eq!(object_storage.parse_url(url).unwrap().key, "dir/100%25.txt");
+    }
+
+    #[test]
+    fn should_convert_parsed_url_to_dir() {
+        let parsed_url = ParsedURL::parse("s3://test-bucket/dir").unwrap().to_dir();
+        assert!(parsed_url.is_dir());
+        assert_eq!(parsed_url.key, "dir/");
+        assert_eq!(parsed_url.url.as_str(), "s3://test-bucket/dir/");
+
+        let parsed_url = ParsedURL::parse("s3://test-bucket/dir/").unwrap().to_dir();
+        assert_eq!(parsed_url.key, "dir/");
+    }
+
+    #[tokio::test]
+    async fn should_return_partial_entries_when_listing_fails_midway() {
+        let contents: String = (0..100)
+            .map(|i| {
+                format!(
+                    "<Contents><Key>dir/file{:03}</Key><Size>1</Size></Contents>",
+                    i
+                )
+            })
+            .collect();
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(query_param("list-type", "2"))
+            .and(query_param_is_missing("continuation-token"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(format!(
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+  <Name>test-bucket</Name>
+  <Prefix>dir/</Prefix>
+  <KeyCount>100</KeyCount>
+  <MaxKeys>100</MaxKeys>
+  <IsTruncated>true</IsTruncated>
+  <NextContinuationToken>next-token</NextContinuationToken>
+  {}
+</ListBucketResult>"#,
+                contents
+            )))
+            .mount(&server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(query_param("list-type", "2"))
+            .and(query_param("continuation-token", "next-token"))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&server)
+            .await;
+
+        let response = ObjectStorage::new(Scheme::S3, Arc::new(Config::default()))
+            .unwrap()
+            .stat(
+                StatRequest::builder()
+                    .task_id("test")
+                    .url("s3://test-bucket/dir/")
+                    .object_storage(mock_object_storage_info(&server))
+                    .build(),
+            )
+            .await
+            .unwrap();
+
+        assert!(!response.success);
+        assert!(response.truncated);
+        assert_eq!(response.entries.len(), 100);
+        assert!(response
+            .error_message
+            .unwrap()
+            .starts_with("list failed after 100 entries"));
+        assert_eq!(
+            response.next_continuation_token,
+            Some("dir/file099".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn should_list_urls_as_stream() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(query_param("list-type", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+  <Name>test-bucket</Name>
+  <Prefix>dir/</Prefix>
+  <KeyCount>4</KeyCount>
+  <MaxKeys>1000</MaxKeys>
+  <IsTruncated>false</IsTruncated>
+  <Contents><Key>dir/</Key><Size>0</Size></Contents>
+  <Contents><Key>dir/file1</Key><Size>1</Size></Contents>
+  <Contents><Key>dir/sub/file2</Key><Size>2</Size></Contents>
+  <Contents><Key>dir/sub/file3</Key><Size>3</Size></Contents>
+</ListBucketResult>"#,
+            ))
+            .mount(&server)
+            .await;
+
+        let object_storage = ObjectStorage::new(Scheme::S3, Arc::new(Config::default())).unwrap();
+        let request = StatRequest {
+            task_id: "test".to_string(),
+            url: "s3://test-bucket/dir/".to_string(),
+            http_header: None,
+            timeout: Duration::from_secs(5),
+            client_cert: None,
+            object_storage: Some(mock_object_storage_info(&server)),
+            hdfs: None,
+            hugging_face: None,
+            model_scope: None,
+            page_size: None,
+            continuation_token: None,
+            cancel_token: None,
+            sniff_content_type: false,
+            retry_budget: None,
+            version_id: None,
+            list_metakeys: Vec::new(),
+            filter: None,
+            max_depth: None,
+            endpoint_override: None,
+            expected_bucket_owner: None,
+            head_via_get_fallback: false,
+            start_after: None,
+            stat_timeout: None,
+            trace_context: None,
+        };
+
+        let mut urls: Vec<String> = object_storage
+            .list_urls(request.clone())
+            .await
+            .unwrap()
+            .try_collect()
+            .await
+            .unwrap();
+        urls.sort();
+
+        let mut entries: Vec<String> = object_storage
+            .stat(request)
+            .await
+            .unwrap()
+            .entries
+            .into_iter()
+            .map(|entry| entry.url)
+            .collect();
+        entries.sort();
+
+        assert_eq!(urls.len(), 3);
+        assert_eq!(urls, entries);
+    }
+
+    #[tokio::test]
+    async fn should_validate_credentials() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(query_param("list-type", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+  <Name>test-bucket</Name>
+  <Prefix></Prefix>
+  <KeyCount>0</KeyCount>
+  <MaxKeys>1</MaxKeys>
+  <IsTruncated>false</IsTruncated>
+</ListBucketResult>"#,
+            ))
+            .mount(&server)
+            .await;
 
         let result = ObjectStorage::new(Scheme::S3, Arc::new(Config::default()))
             .unwrap()
-            .operator(&parsed_url, None, Duration::from_secs(3));
+            .validate_credentials(mock_object_storage_info(&server), "test-bucket")
+            .await;
 
-        assert!(result.is_err());
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_return_unauthorized_when_validate_credentials_denied() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(query_param("list-type", "2"))
+            .respond_with(ResponseTemplate::new(403).set_body_string(
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+<Error>
+  <Code>AccessDenied</Code>
+  <Message>Access Denied</Message>
+</Error>"#,
+            ))
+            .mount(&server)
+            .await;
+
+        let result = ObjectStorage::new(Scheme::S3, Arc::new(Config::default()))
+            .unwrap()
+            .validate_credentials(mock_object_storage_info(&server), "test-bucket")
+            .await;
+
+        assert!(matches!(result, Err(ClientError::Unauthorized)));
+    }
+
+    #[tokio::test]
+    async fn should_return_backend_error_when_validate_credentials_unreachable() {
+        let result = ObjectStorage::new(Scheme::S3, Arc::new(Config::default()))
+            .unwrap()
+            .validate_credentials(
+                ObjectStorageInfo {
+                    region: Some("test-region".into()),
+                    endpoint: Some("http://127.0.0.1:1".into()),
+                    access_key_id: Some("access-key-id".into()),
+                    access_key_secret: Some("access-key-secret".into()),
+                    ..Default::default()
+                },
+                "test-bucket",
+            )
+            .await;
+
+        assert!(matches!(result, Err(ClientError::BackendError(..))));
+    }
+
+    #[tokio::test]
+    async fn should_reuse_connection_of_warm_up() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        // The server counts the accepted connections, and responds the list request with an
+        // empty listing and the other requests with the content.
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let connections = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let server = tokio::spawn({
+            let connections = connections.clone();
+            async move {
+                loop {
+                    let (mut stream, _) = listener.accept().await.unwrap();
+                    connections.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                    tokio::spawn(async move {
+                        let mut buf = Vec::new();
+                        let mut chunk = [0; 4096];
+                        loop {
+                            let n = match stream.read(&mut chunk).await {
+                                Ok(0) | Err(_) => return,
+                                Ok(n) => n,
+                            };
+                            buf.extend_from_slice(&chunk[..n]);
+                            while let Some(end) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+                                let head = String::from_utf8_lossy(&buf[..end]).to_string();
+                                buf.drain(..end + 4);
+
+                                let body = if head.contains("list-type=2") {
+                                    r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+  <Name>test-bucket</Name>
+  <KeyCount>0</KeyCount>
+  <MaxKeys>1</MaxKeys>
+  <IsTruncated>false</IsTruncated>
+</ListBucketResult>"#
+                                } else {
+                                    "content"
+                                };
+                                // The response of the HEAD request has no body.
+                                let response = format!(
+                                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{}",
+                                    body.len(),
+                                    if head.starts_with("HEAD") { "" } else { body }
+                                );
+                                if stream.write_all(response.as_bytes()).await.is_err() {
+                                    return;
+                                }
+                            }
+                        }
+                    });
+                }
+            }
+        });
+
+        let object_storage_info = ObjectStorageInfo {
+            region: Some("test-region".into()),
+            endpoint: Some(format!("http://{}", addr)),
+            access_key_id: Some("access-key-id".into()),
+            access_key_secret: Some("access-key-secret".into()),
+            ..Default::default()
+        };
+
+        let object_storage = ObjectStorage::new(Scheme::S3, Arc::new(Config::default())).unwrap();
+        object_storage
+            .warm_up("s3://test-bucket/file", Some(object_storage_info.clone()))
+            .await
+            .unwrap();
+        assert_eq!(connections.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        let mut response = object_storage
+            .get(
+                GetRequest::builder()
+                    .task_id("test")
+                    .piece_id("test")
+                    .url("s3://test-bucket/file")
+                    .object_storage(object_storage_info)
+                    .build(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.text().await.unwrap(), "content");
+        assert_eq!(connections.load(std::sync::atomic::Ordering::SeqCst), 1);
+        server.abort();
+    }
+
+    #[test]
+    fn should_get_root_of_parsed_url() {
+        let parsed_url =
+            ParsedURL::parse("oci+http://test-bucket@test-namespace/dir/file").unwrap();
+        let root = parsed_url.to_root();
+        assert_eq!(root.url.as_str(), "oci+http://test-bucket@test-namespace/");
+        assert_eq!(root.bucket, "test-bucket");
+        assert_eq!(root.namespace.as_deref(), Some("test-namespace"));
+        assert_eq!(root.transport, Some(Transport::Http));
+        assert_eq!(root.key, "");
+        assert!(root.is_dir());
+
+        // The bucket of the path-style URL is kept in the path.
+        let parsed_url =
+            ParsedURL::parse("s3://s3.eu-west-1.amazonaws.com/test-bucket/dir/file").unwrap();
+        let root = parsed_url.to_root();
         assert_eq!(
-            result.unwrap_err().to_string(),
-            "backend error: s3 need object_storage parameter"
-        )
+            root.url.as_str(),
+            "s3://s3.eu-west-1.amazonaws.com/test-bucket/"
+        );
+        assert_eq!(root.bucket, "test-bucket");
+        assert_eq!(root.region.as_deref(), Some("eu-west-1"));
+        assert_eq!(root.key, "");
+    }
+
+    #[tokio::test]
+    async fn should_warm_up_oci_bucket_of_namespace() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/test-bucket"))
+            .and(query_param("list-type", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+  <Name>test-bucket</Name>
+  <KeyCount>0</KeyCount>
+  <MaxKeys>1</MaxKeys>
+  <IsTruncated>false</IsTruncated>
+</ListBucketResult>"#,
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let object_storage = ObjectStorage::new(Scheme::OCI, Arc::new(Config::default())).unwrap();
+        object_storage
+            .warm_up(
+                "oci://test-bucket@test-namespace/dir/file",
+                Some(mock_object_storage_info(&server)),
+            )
+            .await
+            .unwrap();
+
+        // The root keeps the namespace, so the endpoint is derived from it if it is not
+        // provided.
+        let parsed_url = ParsedURL::parse("oci://test-bucket@test-namespace/dir/file")
+            .unwrap()
+            .to_root();
+        assert!(object_storage
+            .operator(
+                &parsed_url,
+                Some(ObjectStorageInfo {
+                    endpoint: None,
+                    ..mock_object_storage_info(&server)
+                }),
+                Duration::from_secs(3),
+            )
+            .is_ok());
     }
 
     #[test]
-    fn should_return_error_when_s3_lacks_of_info() {
+    fn should_validate_object_storage_for_each_scheme() {
         let test_cases = vec![
             (
-                ObjectStorageInfo::default(),
-                "backend error: s3 need access_key_id, access_key_secret, region",
+                Scheme::S3,
+                "s3 need access_key_id, access_key_secret, region",
             ),
             (
-                ObjectStorageInfo {
-                    access_key_id: Some("access_key_id".into()),
-                    ..Default::default()
-                },
-                "backend error: s3 need access_key_secret, region",
+                Scheme::OCI,
+                "oci need access_key_id, access_key_secret, region",
             ),
             (
-                ObjectStorageInfo {
-                    access_key_secret: Some("access_key_secret".into()),
-                    ..Default::default()
-                },
-                "backend error: s3 need access_key_id, region",
+                Scheme::ABS,
+                "abs need access_key_id, access_key_secret, endpoint",
             ),
             (
-                ObjectStorageInfo {
-                    region: Some("test-region".into()),
-                    ..Default::default()
-                },
-                "backend error: s3 need access_key_id, access_key_secret",
+                Scheme::OSS,
+                "oss need access_key_id, access_key_secret, endpoint",
             ),
             (
-                ObjectStorageInfo {
-                    access_key_id: Some("access_key_id".into()),
-                    access_key_secret: Some("access_key_secret".into()),
-                    ..Default::default()
-                },
-                "backend error: s3 need region",
+                Scheme::OBS,
+                "obs need access_key_id, access_key_secret, endpoint",
             ),
             (
-                ObjectStorageInfo {
-                    access_key_id: Some("access_key_id".into()),
-                    region: Some("test-region".into()),
-                    ..Default::default()
-                },
-                "backend error: s3 need access_key_secret",
+                Scheme::COS,
+                "cos need access_key_id, access_key_secret, endpoint",
             ),
             (
-                ObjectStorageInfo {
-                    access_key_secret: Some("access_key_secret".into()),
-                    region: Some("test-region".into()),
-                    ..Default::default()
-                },
-                "backend error: s3 need access_key_id",
+                Scheme::S3Compatible,
+                "s3c need access_key_id, access_key_secret, endpoint, region",
             ),
         ];
 
-        for (object_storage, error_message) in test_cases {
-            let url: Url = "s3://test-bucket/file".parse().unwrap();
-            let parsed_url: ParsedURL = url.try_into().unwrap();
-
-            let result = ObjectStorage::new(Scheme::S3, Arc::new(Config::default()))
-                .unwrap()
-                .operator(&parsed_url, Some(object_storage), Duration::from_secs(3));
+        for (scheme, error_message) in test_cases {
+            let object_storage = ObjectStorage::new(scheme, Arc::new(Config::default())).unwrap();
+            let result = object_storage.validate(&ObjectStorageInfo::default());
+            assert_eq!(
+                result.unwrap_err().to_string(),
+                format!("backend error: {}", error_message)
+            );
 
+            let result = object_storage.validate(&ObjectStorageInfo {
+                access_key_id: Some("access-key-id".into()),
+                access_key_secret: Some("access-key-secret".into()),
+                ..Default::default()
+            });
             assert!(result.is_err());
-            assert_eq!(result.unwrap_err().to_string(), error_message);
+
+            let result = object_storage.validate(&ObjectStorageInfo {
+                access_key_id: Some("access-key-id".into()),
+                access_key_secret: Some("access-key-secret".into()),
+                region: Some("test-region".into()),
+                endpoint: Some("test-endpoint.local".into()),
+                ..Default::default()
+            });
+            assert!(result.is_ok());
         }
+
+        // GCS uses the Application Default Credentials if the credential path is not provided.
+        let object_storage = ObjectStorage::new(Scheme::GCS, Arc::new(Config::default())).unwrap();
+        assert!(object_storage
+            .validate(&ObjectStorageInfo::default())
+            .is_ok());
     }
 
     #[test]
-    fn should_return_error_when_abs_lacks_of_info() {
-        let test_cases = vec![
-            (
-                ObjectStorageInfo::default(),
-                "backend error: abs need access_key_id, access_key_secret, endpoint",
-            ),
-            (
-                ObjectStorageInfo {
-                    access_key_id: Some("access_key_id".into()),
-                    ..Default::default()
-                },
-                "backend error: abs need access_key_secret, endpoint",
-            ),
-            (
-                ObjectStorageInfo {
-                    access_key_secret: Some("access_key_secret".into()),
-                    ..Default::default()
-                },
-                "backend error: abs need access_key_id, endpoint",
-            ),
-            (
-                ObjectStorageInfo {
-                    endpoint: Some("test-endpoint.local".into()),
-                    ..Default::default()
-                },
-                "backend error: abs need access_key_id, access_key_secret",
-            ),
-            (
-                ObjectStorageInfo {
-                    access_key_id: Some("access_key_id".into()),
-                    access_key_secret: Some("access_key_secret".into()),
-                    ..Default::default()
-                },
-                "backend error: abs need endpoint",
-            ),
-            (
-                ObjectStorageInfo {
-                    access_key_id: Some("access_key_id".into()),
-                    endpoint: Some("test-endpoint.local".into()),
+    fn should_get_oci_operator() {
+        let object_storage_info = ObjectStorageInfo {
+            access_key_id: Some("access-key-id".into()),
+            access_key_secret: Some("access-key-secret".into()),
+            region: Some("us-ashburn-1".into()),
+            ..Default::default()
+        };
+
+        let url: Url = "oci://test-bucket@test-namespace/file".parse().unwrap();
+        let parsed_url: ParsedURL = url.try_into().unwrap();
+        assert_eq!(parsed_url.bucket, "test-bucket");
+        assert_eq!(parsed_url.namespace.as_deref(), Some("test-namespace"));
+        assert_eq!(parsed_url.key, "file");
+
+        let operator = ObjectStorage::new(Scheme::OCI, Arc::new(Config::default()))
+            .unwrap()
+            .operator(
+                &parsed_url,
+                Some(object_storage_info.clone()),
+                Duration::from_secs(3),
+            )
+            .unwrap();
+        assert_eq!(operator.info().name(), "test-bucket");
+
+        // The endpoint is required if the namespace is not in the URL.
+        let url: Url = "oci://test-bucket/file".parse().unwrap();
+        let parsed_url: ParsedURL = url.try_into().unwrap();
+        let result = ObjectStorage::new(Scheme::OCI, Arc::new(Config::default()))
+            .unwrap()
+            .operator(
+                &parsed_url,
+                Some(object_storage_info),
+                Duration::from_secs(3),
+            );
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "backend error: oci need endpoint or namespace"
+        );
+    }
+
+    #[test]
+    fn should_create_object_storage_with_pool_config() {
+        let mut config = Config::default();
+        config.backend.pool_idle_timeout = Some(Duration::from_secs(10));
+        config.backend.pool_max_idle_per_host = Some(8);
+        assert!(ObjectStorage::new(Scheme::S3, Arc::new(config.clone())).is_ok());
+
+        config.backend.pool_idle_timeout = None;
+        assert!(ObjectStorage::new(Scheme::S3, Arc::new(config)).is_ok());
+    }
+
+    #[test]
+    fn should_reuse_operator_with_same_options() {
+        let object_storage = ObjectStorage::new(Scheme::S3, Arc::new(Config::default())).unwrap();
+        let object_storage_info = ObjectStorageInfo {
+            region: Some("test-region".into()),
+            access_key_id: Some("access-key-id".into()),
+            access_key_secret: Some("access-key-secret".into()),
+            ..Default::default()
+        };
+
+        for raw_url in ["s3://test-bucket/file", "s3://test-bucket/dir/file"] {
+            let url: Url = raw_url.parse().unwrap();
+            let parsed_url: ParsedURL = url.try_into().unwrap();
+            object_storage
+                .operator(
+                    &parsed_url,
+                    Some(object_storage_info.clone()),
+                    Duration::from_secs(3),
+                )
+                .unwrap();
+        }
+        assert_eq!(object_storage.operators.lock().len(), 1);
+
+        // The operator is rebuilt if the bucket or the credentials are different.
+        let url: Url = "s3://other-bucket/file".parse().unwrap();
+        let parsed_url: ParsedURL = url.try_into().unwrap();
+        object_storage
+            .operator(
+                &parsed_url,
+                Some(object_storage_info.clone()),
+                Duration::from_secs(3),
+            )
+            .unwrap();
+        assert_eq!(object_storage.operators.lock().len(), 2);
+
+        let url: Url = "s3://test-bucket/file".parse().unwrap();
+        let parsed_url: ParsedURL = url.try_into().unwrap();
+        object_storage
+            .operator(
+                &parsed_url,
+                Some(ObjectStorageInfo {
+                    access_key_secret: Some("other-access-key-secret".into()),
+                    ..object_storage_info
+                }),
+                Duration::from_secs(3),
+            )
+            .unwrap();
+        assert_eq!(object_storage.operators.lock().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn should_clear_operators_on_shutdown() {
+        let mut config = Config::default();
+        config.backend.enable_object_storage_stat_cache = true;
+        let object_storage = ObjectStorage::new(Scheme::S3, Arc::new(config)).unwrap();
+        let url: Url = "s3://test-bucket/file".parse().unwrap();
+        let parsed_url: ParsedURL = url.try_into().unwrap();
+        object_storage
+            .operator(
+                &parsed_url,
+                Some(ObjectStorageInfo {
+                    region: Some("test-region".into()),
+                    access_key_id: Some("access-key-id".into()),
+                    access_key_secret: Some("access-key-secret".into()),
                     ..Default::default()
-                },
-                "backend error: abs need access_key_secret",
-            ),
-            (
-                ObjectStorageInfo {
-                    access_key_secret: Some("access_key_secret".into()),
-                    endpoint: Some("test-endpoint.local".into()),
+                }),
+                Duration::from_secs(3),
+            )
+            .unwrap();
+        assert_eq!(object_storage.operators.lock().len(), 1);
+
+        let stat_cache = object_storage.stat_cache.as_ref().unwrap();
+        stat_cache.lock().put(
+            "s3://test-bucket/file".to_string(),
+            StatCacheEntry {
+                etag: None,
+                content_length: 0,
+                cached_at: Instant::now(),
+            },
+        );
+
+        object_storage.shutdown().await.unwrap();
+        assert!(object_storage.operators.lock().is_empty());
+        assert!(stat_cache.lock().is_empty());
+    }
+
+    #[test]
+    fn should_fingerprint_operator_without_secrets() {
+        let object_storage = ObjectStorage::new(Scheme::S3, Arc::new(Config::default())).unwrap();
+        let parsed_url: ParsedURL = "s3://test-bucket/file"
+            .parse::<Url>()
+            .unwrap()
+            .try_into()
+            .unwrap();
+        let object_storage_info = ObjectStorageInfo {
+            access_key_id: Some("access-key-id".into()),
+            access_key_secret: Some("access-key-secret".into()),
+            ..Default::default()
+        };
+
+        let fingerprint = object_storage.operator_fingerprint(
+            &parsed_url,
+            &object_storage_info,
+            Duration::from_secs(3),
+        );
+        assert_eq!(fingerprint.len(), 64);
+        assert!(!fingerprint.contains("access-key-secret"));
+
+        // The options are delimited, so the shifted options are not the same.
+        let shifted_fingerprint = object_storage.operator_fingerprint(
+            &parsed_url,
+            &ObjectStorageInfo {
+                access_key_id: Some("access-key-idaccess-key-secret".into()),
+                access_key_secret: Some(String::new()),
+                ..Default::default()
+            },
+            Duration::from_secs(3),
+        );
+        assert_ne!(fingerprint, shifted_fingerprint);
+    }
+
+    #[test]
+    fn should_apply_operator_layers() {
+        let mut object_storage =
+            ObjectStorage::new(Scheme::S3, Arc::new(Config::default())).unwrap();
+        let object_storage_info = ObjectStorageInfo {
+            region: Some("test-region".into()),
+            access_key_id: Some("access-key-id".into()),
+            access_key_secret: Some("access-key-secret".into()),
+            ..Default::default()
+        };
+        let url: Url = "s3://test-bucket/file".parse().unwrap();
+        let parsed_url: ParsedURL = url.try_into().unwrap();
+
+        // Build the operator before the layer is registered.
+        object_storage
+            .operator(
+                &parsed_url,
+                Some(object_storage_info.clone()),
+                Duration::from_secs(3),
+            )
+            .unwrap();
+
+        let applied = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        object_storage.with_operator_layer(Arc::new({
+            let applied = applied.clone();
+            move |operator: Operator| {
+                applied.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                operator.layer(TimeoutLayer::new())
+            }
+        }));
+
+        // The cached operator without the layer is not reused, and the operator built with the
+        // layer is reused.
+        for _ in 0..2 {
+            object_storage
+                .operator(
+                    &parsed_url,
+                    Some(object_storage_info.clone()),
+                    Duration::from_secs(3),
+                )
+                .unwrap();
+        }
+        assert_eq!(applied.load(std::sync::atomic::Ordering::SeqCst), 1);
+        assert_eq!(object_storage.operators.lock().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn should_stat_once_for_ranged_gets_with_stat_cache() {
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/test-bucket/file"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("ETag", "\"test-etag\"")
+                    .set_body_string("test"),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        for (range, content) in [("bytes=0-1", "te"), ("bytes=2-3", "st")] {
+            Mock::given(method("GET"))
+                .and(path("/test-bucket/file"))
+                .and(header("Range", range))
+                .and(header("If-Match", "\"test-etag\""))
+                .respond_with(ResponseTemplate::new(206).set_body_string(content))
+                .mount(&server)
+                .await;
+        }
+
+        let mut config = Config::default();
+        config.backend.enable_object_storage_stat_cache = true;
+        let object_storage = ObjectStorage::new(Scheme::S3, Arc::new(config)).unwrap();
+
+        for (start, content) in [(0, "te"), (2, "st"), (0, "te")] {
+            let mut response = object_storage
+                .get(GetRequest {
+                    task_id: "test".to_string(),
+                    piece_id: "test".to_string(),
+                    url: "s3://test-bucket/file".to_string(),
+                    range: Some(Range { start, length: 2 }),
+                    http_header: None,
+                    timeout: Duration::from_secs(5),
+                    client_cert: None,
+                    object_storage: Some(mock_object_storage_info(&server)),
+                    hdfs: None,
+                    hugging_face: None,
+                    model_scope: None,
+                    read_buffer_size: None,
+                    max_body_size: None,
+                    piece_length: None,
+                    ranges: Vec::new(),
+                    cancel_token: None,
+                    retry_budget: None,
+                    suffix_length: None,
+                    version_id: None,
+                    adaptive_chunking: false,
+                })
+                .await
+                .unwrap();
+
+            assert_eq!(response.content_length, Some(2));
+            assert_eq!(response.text().await.unwrap(), content);
+        }
+
+        server.verify().await;
+    }
+
+    #[tokio::test]
+    async fn should_get_multiple_ranges_concurrently() {
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/test-bucket/file"))
+            .respond_with(ResponseTemplate::new(200).insert_header("Content-Length", "10"))
+            .mount(&server)
+            .await;
+
+        for (range, content) in [("bytes=0-1", "01"), ("bytes=6-8", "678")] {
+            Mock::given(method("GET"))
+                .and(path("/test-bucket/file"))
+                .and(header("Range", range))
+                .respond_with(ResponseTemplate::new(206).set_body_string(content))
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+
+        let object_storage = ObjectStorage::new(Scheme::S3, Arc::new(Config::default())).unwrap();
+        let mut response = object_storage
+            .get(
+                GetRequest::builder()
+                    .task_id("test")
+                    .piece_id("test")
+                    .url("s3://test-bucket/file")
+                    .object_storage(mock_object_storage_info(&server))
+                    .ranges(vec![
+                        Range {
+                            start: 0,
+                            length: 2,
+                        },
+                        Range {
+                            start: 6,
+                            length: 3,
+                        },
+                    ])
+                    .build(),
+            )
+            .await
+            .unwrap();
+
+        assert!(response.success);
+        let mut contents = Vec::new();
+        for range_reader in response.range_readers.iter_mut() {
+            let mut content = String::new();
+            range_reader.read_to_string(&mut content).await.unwrap();
+            contents.push(content);
+        }
+        assert_eq!(contents, vec!["01", "678"]);
+
+        server.verify().await;
+    }
+
+    #[tokio::test]
+    async fn should_get_suffix_range_of_object() {
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/test-bucket/file"))
+            .respond_with(ResponseTemplate::new(200).insert_header("Content-Length", "10"))
+            .mount(&server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/test-bucket/file"))
+            .and(header("Range", "bytes=6-9"))
+            .respond_with(ResponseTemplate::new(206).set_body_string("6789"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let object_storage = ObjectStorage::new(Scheme::S3, Arc::new(Config::default())).unwrap();
+        let mut response = object_storage
+            .get(
+                GetRequest::builder()
+                    .task_id("test")
+                    .piece_id("test")
+                    .url("s3://test-bucket/file")
+                    .object_storage(mock_object_storage_info(&server))
+                    .suffix_length(4)
+                    .build(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.text().await.unwrap(), "6789");
+        server.verify().await;
+    }
+
+    #[tokio::test]
+    async fn should_retry_interrupted_read_in_place() {
+        let interrupted = || std::io::Error::new(std::io::ErrorKind::Interrupted, "interrupted");
+
+        // The interrupted read is retried, and the stream continues.
+        let stream = futures::stream::iter(vec![
+            Ok(Bytes::from("foo")),
+            Err(interrupted()),
+            Ok(Bytes::from("bar")),
+        ]);
+        let mut reader = StreamReader::new(InterruptedRetryStream::new(stream, None));
+        let mut content = String::new();
+        reader.read_to_string(&mut content).await.unwrap();
+        assert_eq!(content, "foobar");
+
+        // The other errors are not retried.
+        let stream = futures::stream::iter(vec![
+            Ok(Bytes::from("foo")),
+            Err(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                "not found",
+            )),
+            Ok(Bytes::from("bar")),
+        ]);
+        let mut reader = StreamReader::new(InterruptedRetryStream::new(stream, None));
+        let mut content = String::new();
+        let err = reader.read_to_string(&mut content).await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+
+        // The consecutive retries are bounded.
+        let stream = futures::stream::iter(
+            (0..=InterruptedRetryStream::<()>::MAX_INTERRUPTED_RETRIES)
+                .map(|_| Err(interrupted()))
+                .chain([Ok(Bytes::from("foo"))]),
+        );
+        let mut reader = StreamReader::new(InterruptedRetryStream::new(stream, None));
+        let mut content = String::new();
+        let err = reader.read_to_string(&mut content).await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::Interrupted);
+
+        // The stream ending after the retried error returns the error, instead of the
+        // truncated content.
+        let stream = futures::stream::iter(vec![Ok(Bytes::from("foo")), Err(interrupted())]);
+        let mut reader = StreamReader::new(InterruptedRetryStream::new(stream, None));
+        let mut content = String::new();
+        let err = reader.read_to_string(&mut content).await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::Interrupted);
+
+        // The interrupted read is not retried once the retry budget is exhausted.
+        let stream = futures::stream::iter(vec![
+            Ok(Bytes::from("foo")),
+            Err(interrupted()),
+            Ok(Bytes::from("bar")),
+        ]);
+        let retry_budget = Arc::new(RetryBudget::new(0.0, 0, 0));
+        let mut reader = StreamReader::new(InterruptedRetryStream::new(stream, Some(retry_budget)));
+        let mut content = String::new();
+        let err = reader.read_to_string(&mut content).await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::Interrupted);
+    }
+
+    #[tokio::test]
+    async fn should_get_specific_version_of_object() {
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/test-bucket/file"))
+            .and(query_param("versionId", "v1"))
+            .respond_with(ResponseTemplate::new(200).insert_header("Content-Length", "10"))
+            // The version is stated by the stat, and by the get for the content length.
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/test-bucket/file"))
+            .and(query_param("versionId", "v1"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("version-v1"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let object_storage_info = mock_object_storage_info(&server);
+
+        let object_storage = ObjectStorage::new(Scheme::S3, Arc::new(Config::default())).unwrap();
+        let response = object_storage
+            .stat(
+                StatRequest::builder()
+                    .task_id("test")
+                    .url("s3://test-bucket/file")
+                    .object_storage(object_storage_info.clone())
+                    .version_id("v1")
+                    .build(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.content_length, Some(10));
+
+        let mut response = object_storage
+            .get(
+                GetRequest::builder()
+                    .task_id("test")
+                    .piece_id("test")
+                    .url("s3://test-bucket/file")
+                    .object_storage(object_storage_info)
+                    .version_id("v1")
+                    .build(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.text().await.unwrap(), "version-v1");
+        server.verify().await;
+    }
+
+    #[tokio::test]
+    async fn should_get_object_from_endpoint_override() {
+        let mut servers = Vec::new();
+        for content in ["primary", "mirror"] {
+            let server = MockServer::start().await;
+            Mock::given(method("HEAD"))
+                .and(path("/test-bucket/file"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .insert_header("Content-Length", content.len().to_string().as_str()),
+                )
+                .mount(&server)
+                .await;
+
+            Mock::given(method("GET"))
+                .and(path("/test-bucket/file"))
+                .respond_with(ResponseTemplate::new(200).set_body_string(content))
+                .expect(1)
+                .mount(&server)
+                .await;
+
+            servers.push((server, content));
+        }
+
+        // The configured endpoint is unreachable, so the gets succeed only by the overrides.
+        let object_storage_info = ObjectStorageInfo {
+            region: Some("test-region".into()),
+            endpoint: Some("http://127.0.0.1:1".into()),
+            access_key_id: Some("access-key-id".into()),
+            access_key_secret: Some("access-key-secret".into()),
+            ..Default::default()
+        };
+
+        let object_storage = ObjectStorage::new(Scheme::S3, Arc::new(Config::default())).unwrap();
+        for (server, content) in &servers {
+            let mut response = object_storage
+                .get(
+                    GetRequest::builder()
+                        .task_id("test")
+                        .piece_id("test")
+                        .url("s3://test-bucket/file")
+                        .object_storage(object_storage_info.clone())
+                        .endpoint_override(server.uri())
+                        .build(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.text().await.unwrap(), *content);
+        }
+
+        for (server, _) in &servers {
+            server.verify().await;
+        }
+
+        let result = object_storage
+            .get(
+                GetRequest::builder()
+                    .task_id("test")
+                    .piece_id("test")
+                    .url("s3://test-bucket/file")
+                    .object_storage(object_storage_info)
+                    .endpoint_override("ftp://127.0.0.1:21")
+                    .build(),
+            )
+            .await;
+        assert!(matches!(result, Err(ClientError::ValidationError(..))));
+    }
+
+    #[tokio::test]
+    async fn should_auto_decompress_object_without_content_encoding() {
+        let content = b"gzip object without content encoding".repeat(32);
+        let mut gzip = Vec::new();
+        async_compression::tokio::bufread::GzipEncoder::new(&content[..])
+            .read_to_end(&mut gzip)
+            .await
+            .unwrap();
+
+        // The gzip object is stored without the Content-Encoding header.
+        let server = MockServer::start().await;
+        for (key, body) in [("gzip", gzip), ("plain", content.clone())] {
+            Mock::given(method("HEAD"))
+                .and(path(format!("/test-bucket/{}", key)))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .insert_header("Content-Length", body.len().to_string().as_str()),
+                )
+                .mount(&server)
+                .await;
+
+            Mock::given(method("GET"))
+                .and(path(format!("/test-bucket/{}", key)))
+                .respond_with(ResponseTemplate::new(200).set_body_bytes(body))
+                .mount(&server)
+                .await;
+        }
+
+        let object_storage = ObjectStorage::new(Scheme::S3, Arc::new(Config::default())).unwrap();
+        for key in ["gzip", "plain"] {
+            let mut response = object_storage
+                .get(
+                    GetRequest::builder()
+                        .task_id("test")
+                        .piece_id("test")
+                        .url(format!("s3://test-bucket/{}", key))
+                        .object_storage(mock_object_storage_info(&server))
+                        .auto_decompress(true)
+                        .build(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.content_length, None);
+
+            let mut body = Vec::new();
+            response.reader.read_to_end(&mut body).await.unwrap();
+            assert_eq!(body, content, "{}", key);
+        }
+    }
+
+    #[tokio::test]
+    async fn should_return_unsupported_when_versioning_unsupported() {
+        let object_storage =
+            ObjectStorage::new(Scheme::S3Compatible, Arc::new(Config::default())).unwrap();
+        let result = object_storage
+            .get(
+                GetRequest::builder()
+                    .task_id("test")
+                    .piece_id("test")
+                    .url("s3c://test-bucket/file")
+                    .object_storage(ObjectStorageInfo {
+                        endpoint: Some("http://127.0.0.1:1".into()),
+                        access_key_id: Some("access-key-id".into()),
+                        access_key_secret: Some("access-key-secret".into()),
+                        ..Default::default()
+                    })
+                    .version_id("v1")
+                    .build(),
+            )
+            .await;
+
+        assert!(matches!(result, Err(ClientError::Unsupported(..))));
+    }
+
+    #[tokio::test]
+    async fn should_read_buffer_of_object() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/test-bucket/file"))
+            .and(header("Range", "bytes=2-5"))
+            .respond_with(ResponseTemplate::new(206).set_body_string("2345"))
+            .mount(&server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/test-bucket/large-file"))
+            .and(header("Range", "bytes=0-4"))
+            .respond_with(ResponseTemplate::new(206).set_body_string("01234"))
+            .mount(&server)
+            .await;
+
+        let object_storage = ObjectStorage::new(Scheme::S3, Arc::new(Config::default())).unwrap();
+        let request = |url: &str| {
+            GetRequest::builder()
+                .task_id("test")
+                .piece_id("test")
+                .url(url)
+                .object_storage(mock_object_storage_info(&server))
+        };
+
+        let buffer = object_storage
+            .read_buffer(
+                request("s3://test-bucket/file")
+                    .range(Range {
+                        start: 2,
+                        length: 4,
+                    })
+                    .build(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(buffer.to_bytes(), "2345");
+
+        // The max body size bounds the read without range.
+        let result = object_storage
+            .read_buffer(
+                request("s3://test-bucket/large-file")
+                    .max_body_size(4)
+                    .build(),
+            )
+            .await;
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("exceeds the max body size of 4 bytes"));
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn should_get_object_without_blocking_runtime() {
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/test-bucket/file"))
+            .respond_with(ResponseTemplate::new(200).insert_header("Content-Length", "7"))
+            .mount(&server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/test-bucket/file"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string("content")
+                    .set_delay(Duration::from_millis(200)),
+            )
+            .mount(&server)
+            .await;
+
+        // The timer ticks on the same thread as the get, so it is delayed if the get blocks
+        // the runtime. The S3 service of opendal backs both S3 and OCI.
+        for scheme in [Scheme::S3, Scheme::OCI] {
+            let ticks = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+            let timer = tokio::spawn({
+                let ticks = ticks.clone();
+                async move {
+                    let mut interval = tokio::time::interval(Duration::from_millis(10));
+                    loop {
+                        interval.tick().await;
+                        ticks.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                    }
+                }
+            });
+
+            let object_storage = ObjectStorage::new(scheme, Arc::new(Config::default())).unwrap();
+            let mut response = object_storage
+                .get(
+                    GetRequest::builder()
+                        .task_id("test")
+                        .piece_id("test")
+                        .url(format!("{}://test-bucket/file", scheme))
+                        .object_storage(mock_object_storage_info(&server))
+                        .build(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.text().await.unwrap(), "content");
+            timer.abort();
+
+            assert!(
+                ticks.load(std::sync::atomic::Ordering::SeqCst) >= 10,
+                "{}",
+                scheme
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn should_put_object_if_not_exists() {
+        let server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/test-bucket/new"))
+            .and(header("If-None-Match", "*"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        Mock::given(method("PUT"))
+            .and(path("/test-bucket/existing"))
+            .and(header("If-None-Match", "*"))
+            .respond_with(ResponseTemplate::new(412))
+            .mount(&server)
+            .await;
+
+        let dir = tempfile::tempdir().unwrap();
+        let file_path = dir.path().join("file");
+        tokio::fs::write(&file_path, b"dragonfly").await.unwrap();
+
+        let object_storage = ObjectStorage::new(Scheme::S3, Arc::new(Config::default())).unwrap();
+        let put_request = |key: &str| PutRequest {
+            task_id: "test".to_string(),
+            url: format!("s3://test-bucket/{}", key),
+            path: file_path.clone(),
+            http_header: None,
+            timeout: Duration::from_secs(5),
+            client_cert: None,
+            object_storage: Some(mock_object_storage_info(&server)),
+            hdfs: None,
+            hugging_face: None,
+            model_scope: None,
+            if_not_exists: true,
+            acl: None,
+        };
+
+        let response = object_storage.put(put_request("new")).await.unwrap();
+        assert!(response.success);
+        assert_eq!(response.content_length, Some(9));
+
+        let result = object_storage.put(put_request("existing")).await;
+        assert!(matches!(result, Err(ClientError::PreconditionFailed(_))));
+
+        server.verify().await;
+    }
+
+    #[tokio::test]
+    async fn should_put_object_with_acl() {
+        let server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/test-bucket/file"))
+            .and(header("x-amz-acl", "public-read"))
+            .and(signed_header("x-amz-acl"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let dir = tempfile::tempdir().unwrap();
+        let file_path = dir.path().join("file");
+        tokio::fs::write(&file_path, b"dragonfly").await.unwrap();
+
+        let object_storage = ObjectStorage::new(Scheme::S3, Arc::new(Config::default())).unwrap();
+        let put_request = |acl: &str| PutRequest {
+            task_id: "test".to_string(),
+            url: "s3://test-bucket/file".to_string(),
+            path: file_path.clone(),
+            http_header: None,
+            timeout: Duration::from_secs(5),
+            client_cert: None,
+            object_storage: Some(mock_object_storage_info(&server)),
+            hdfs: None,
+            hugging_face: None,
+            model_scope: None,
+            if_not_exists: false,
+            acl: Some(acl.to_string()),
+        };
+
+        let response = object_storage
+            .put(put_request("public-read"))
+            .await
+            .unwrap();
+        assert!(response.success);
+
+        // The ACL unknown to the object storage is rejected before the write.
+        let result = object_storage.put(put_request("publicRead")).await;
+        assert!(matches!(result, Err(ClientError::ValidationError(_))));
+
+        server.verify().await;
+    }
+
+    #[tokio::test]
+    async fn should_append_to_abs_append_blob() {
+        let server = MockServer::start().await;
+        Mock::given(method("HEAD"))
+            .and(path("/test-bucket/app.log"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+
+        // The append blob is created before the first append.
+        Mock::given(method("PUT"))
+            .and(path("/test-bucket/app.log"))
+            .and(query_param_is_missing("comp"))
+            .and(header("x-ms-blob-type", "AppendBlob"))
+            .respond_with(ResponseTemplate::new(201))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        // The block is appended at the current length of the blob.
+        Mock::given(method("PUT"))
+            .and(path("/test-bucket/app.log"))
+            .and(query_param("comp", "appendblock"))
+            .and(header("x-ms-blob-condition-appendpos", "0"))
+            .respond_with(ResponseTemplate::new(201))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let object_storage = ObjectStorage::new(Scheme::ABS, Arc::new(Config::default())).unwrap();
+        let append = |position: Option<u64>| {
+            object_storage.append(AppendRequest {
+                task_id: "test".to_string(),
+                url: "abs://test-bucket/app.log".to_string(),
+                content: Bytes::from_static(b"line\n"),
+                position,
+                timeout: Duration::from_secs(5),
+                object_storage: Some(ObjectStorageInfo {
+                    endpoint: Some(server.uri()),
+                    access_key_id: Some("access-key-id".into()),
+                    access_key_secret: Some("YWNjZXNzLWtleS1zZWNyZXQK".into()),
                     ..Default::default()
-                },
-                "backend error: abs need access_key_id",
-            ),
-        ];
+                }),
+            })
+        };
 
-        for (object_storage, error_message) in test_cases {
-            let url: Url = "abs://test-bucket/file".parse().unwrap();
-            let parsed_url: ParsedURL = url.try_into().unwrap();
+        append(Some(0)).await.unwrap();
+
+        // The mismatched position fails without appending.
+        let result = append(Some(5)).await;
+        assert!(matches!(result, Err(ClientError::PreconditionFailed(_))));
+        server.verify().await;
+    }
+
+    #[tokio::test]
+    async fn should_return_unsupported_when_append_to_s3() {
+        let server = MockServer::start().await;
+        let result = ObjectStorage::new(Scheme::S3, Arc::new(Config::default()))
+            .unwrap()
+            .append(AppendRequest {
+                task_id: "test".to_string(),
+                url: "s3://test-bucket/app.log".to_string(),
+                content: Bytes::from_static(b"line\n"),
+                position: None,
+                timeout: Duration::from_secs(5),
+                object_storage: Some(mock_object_storage_info(&server)),
+            })
+            .await;
+
+        assert!(matches!(result, Err(ClientError::Unsupported(_))));
+        assert!(server.received_requests().await.unwrap().is_empty());
+    }
 
-            let result = ObjectStorage::new(Scheme::ABS, Arc::new(Config::default()))
-                .unwrap()
-                .operator(&parsed_url, Some(object_storage), Duration::from_secs(3));
+    #[tokio::test]
+    async fn should_copy_object_within_bucket() {
+        let dir = tempfile::tempdir().unwrap();
+        let operator = Operator::new(
+            opendal::services::Fs::default().root(dir.path().to_string_lossy().as_ref()),
+        )
+        .unwrap()
+        .finish();
+        operator
+            .write("source", b"dragonfly".to_vec())
+            .await
+            .unwrap();
 
-            assert!(result.is_err());
-            assert_eq!(result.unwrap_err().to_string(), error_message);
-        }
+        let backend = ObjectStorage::new(Scheme::S3, Arc::new(Config::default())).unwrap();
+        backend
+            .copy_object(&operator, "source", None, "dest")
+            .await
+            .unwrap();
+
+        assert!(operator.exists("dest").await.unwrap());
+        assert_eq!(
+            operator.read("dest").await.unwrap().to_vec(),
+            b"dragonfly".to_vec()
+        );
     }
 
-    #[test]
-    fn should_return_error_when_oss_lacks_of_info() {
-        let test_cases = vec![
-            (
-                ObjectStorageInfo::default(),
-                "backend error: oss need access_key_id, access_key_secret, endpoint",
-            ),
-            (
-                ObjectStorageInfo {
-                    access_key_id: Some("access_key_id".into()),
-                    ..Default::default()
-                },
-                "backend error: oss need access_key_secret, endpoint",
-            ),
-            (
-                ObjectStorageInfo {
-                    access_key_secret: Some("access_key_secret".into()),
-                    ..Default::default()
-                },
-                "backend error: oss need access_key_id, endpoint",
-            ),
-            (
-                ObjectStorageInfo {
-                    endpoint: Some("test-endpoint.local".into()),
-                    ..Default::default()
-                },
-                "backend error: oss need access_key_id, access_key_secret",
-            ),
-            (
-                ObjectStorageInfo {
-                    access_key_id: Some("access_key_id".into()),
-                    access_key_secret: Some("access_key_secret".into()),
-                    ..Default::default()
-                },
-                "backend error: oss need endpoint",
-            ),
-            (
-                ObjectStorageInfo {
-                    access_key_id: Some("access_key_id".into()),
-                    endpoint: Some("test-endpoint.local".into()),
-                    ..Default::default()
-                },
-                "backend error: oss need access_key_secret",
-            ),
-            (
-                ObjectStorageInfo {
-                    access_key_secret: Some("access_key_secret".into()),
-                    endpoint: Some("test-endpoint.local".into()),
-                    ..Default::default()
-                },
-                "backend error: oss need access_key_id",
-            ),
-        ];
+    #[tokio::test]
+    async fn should_copy_object_across_buckets() {
+        let source_dir = tempfile::tempdir().unwrap();
+        let source_operator = Operator::new(
+            opendal::services::Fs::default().root(source_dir.path().to_string_lossy().as_ref()),
+        )
+        .unwrap()
+        .finish();
+        source_operator
+            .write("source", b"dragonfly".to_vec())
+            .await
+            .unwrap();
 
-        for (object_storage, error_message) in test_cases {
-            let url: Url = "oss://test-bucket/file".parse().unwrap();
-            let parsed_url: ParsedURL = url.try_into().unwrap();
+        let dest_dir = tempfile::tempdir().unwrap();
+        let dest_operator = Operator::new(
+            opendal::services::Fs::default().root(dest_dir.path().to_string_lossy().as_ref()),
+        )
+        .unwrap()
+        .finish();
 
-            let result = ObjectStorage::new(Scheme::OSS, Arc::new(Config::default()))
-                .unwrap()
-                .operator(&parsed_url, Some(object_storage), Duration::from_secs(3));
+        let backend = ObjectStorage::new(Scheme::S3, Arc::new(Config::default())).unwrap();
+        backend
+            .copy_object(&source_operator, "source", Some(&dest_operator), "dest")
+            .await
+            .unwrap();
 
-            assert!(result.is_err());
-            assert_eq!(result.unwrap_err().to_string(), error_message);
-        }
+        assert_eq!(
+            dest_operator.read("dest").await.unwrap().to_vec(),
+            b"dragonfly".to_vec()
+        );
     }
 
-    #[test]
-    fn should_return_error_when_obs_lacks_of_info() {
-        let test_cases = vec![
-            (
-                ObjectStorageInfo::default(),
-                "backend error: obs need access_key_id, access_key_secret, endpoint",
-            ),
-            (
-                ObjectStorageInfo {
-                    access_key_id: Some("access_key_id".into()),
-                    ..Default::default()
-                },
-                "backend error: obs need access_key_secret, endpoint",
-            ),
-            (
-                ObjectStorageInfo {
-                    access_key_secret: Some("access_key_secret".into()),
-                    ..Default::default()
-                },
-                "backend error: obs need access_key_id, endpoint",
-            ),
-            (
-                ObjectStorageInfo {
-                    endpoint: Some("test-endpoint.local".into()),
-                    ..Default::default()
-                },
-                "backend error: obs need access_key_id, access_key_secret",
-            ),
-            (
-                ObjectStorageInfo {
-                    access_key_id: Some("access_key_id".into()),
-                    access_key_secret: Some("access_key_secret".into()),
-                    ..Default::default()
-                },
-                "backend error: obs need endpoint",
-            ),
-            (
-                ObjectStorageInfo {
-                    access_key_id: Some("access_key_id".into()),
-                    endpoint: Some("test-endpoint.local".into()),
-                    ..Default::default()
-                },
-                "backend error: obs need access_key_secret",
-            ),
-            (
-                ObjectStorageInfo {
-                    access_key_secret: Some("access_key_secret".into()),
-                    endpoint: Some("test-endpoint.local".into()),
-                    ..Default::default()
-                },
-                "backend error: obs need access_key_id",
-            ),
-        ];
+    #[tokio::test]
+    async fn should_read_same_content_with_read_buffer_size() {
+        let dir = tempfile::tempdir().unwrap();
+        let operator = Operator::new(
+            opendal::services::Fs::default().root(dir.path().to_string_lossy().as_ref()),
+        )
+        .unwrap()
+        .finish();
 
-        for (object_storage, error_message) in test_cases {
-            let url: Url = "obs://test-bucket/file".parse().unwrap();
-            let parsed_url: ParsedURL = url.try_into().unwrap();
+        let content: Vec<u8> = (0..4 * 1024 * 1024).map(|i| (i % 251) as u8).collect();
+        operator.write("object", content.clone()).await.unwrap();
 
-            let result = ObjectStorage::new(Scheme::OBS, Arc::new(Config::default()))
+        let default_content = ObjectStorage::reader(&operator, "object", None, None, None)
+            .await
+            .unwrap()
+            .read(..)
+            .await
+            .unwrap()
+            .to_vec();
+
+        let buffered_content =
+            ObjectStorage::reader(&operator, "object", Some(16 * 1024 * 1024), None, None)
+                .await
                 .unwrap()
-                .operator(&parsed_url, Some(object_storage), Duration::from_secs(3));
+                .read(..)
+                .await
+                .unwrap()
+                .to_vec();
 
-            assert!(result.is_err());
-            assert_eq!(result.unwrap_err().to_string(), error_message);
-        }
+        assert_eq!(default_content, content);
+        assert_eq!(buffered_content, default_content);
     }
 
-    #[test]
-    fn should_return_error_when_cos_lacks_of_info() {
-        let test_cases = vec![
-            (
-                ObjectStorageInfo::default(),
-                "backend error: cos need access_key_id, access_key_secret, endpoint",
-            ),
-            (
-                ObjectStorageInfo {
-                    access_key_id: Some("access_key_id".into()),
-                    ..Default::default()
-                },
-                "backend error: cos need access_key_secret, endpoint",
-            ),
-            (
-                ObjectStorageInfo {
-                    access_key_secret: Some("access_key_secret".into()),
-                    ..Default::default()
-                },
-                "backend error: cos need access_key_id, endpoint",
-            ),
-            (
-                ObjectStorageInfo {
-                    endpoint: Some("test-endpoint.local".into()),
-                    ..Default::default()
-                },
-                "backend error: cos need access_key_id, access_key_secret",
-            ),
-            (
-                ObjectStorageInfo {
-                    access_key_id: Some("access_key_id".into()),
-                    access_key_secret: Some("access_key_secret".into()),
-                    ..Default::default()
-                },
-                "backend error: cos need endpoint",
-            ),
-            (
-                ObjectStorageInfo {
-                    access_key_id: Some("access_key_id".into()),
-                    endpoint: Some("test-endpoint.local".into()),
-                    ..Default::default()
-                },
-                "backend error: cos need access_key_secret",
-            ),
-            (
-                ObjectStorageInfo {
-                    access_key_secret: Some("access_key_secret".into()),
-                    endpoint: Some("test-endpoint.local".into()),
-                    ..Default::default()
-                },
-                "backend error: cos need access_key_id",
-            ),
-        ];
+    #[tokio::test]
+    async fn should_read_same_content_with_adaptive_chunking() {
+        let dir = tempfile::tempdir().unwrap();
+        let operator = Operator::new(
+            opendal::services::Fs::default().root(dir.path().to_string_lossy().as_ref()),
+        )
+        .unwrap()
+        .finish();
 
-        for (object_storage, error_message) in test_cases {
-            let url: Url = "cos://test-bucket/file".parse().unwrap();
-            let parsed_url: ParsedURL = url.try_into().unwrap();
+        let content: Vec<u8> = (0..5 * 1024 * 1024 + 7).map(|i| (i % 251) as u8).collect();
+        operator.write("object", content.clone()).await.unwrap();
 
-            let result = ObjectStorage::new(Scheme::COS, Arc::new(Config::default()))
-                .unwrap()
-                .operator(&parsed_url, Some(object_storage), Duration::from_secs(3));
+        let reader = ObjectStorage::reader(&operator, "object", None, None, None)
+            .await
+            .unwrap();
+        let fixed_content = reader.read(..).await.unwrap().to_vec();
 
-            assert!(result.is_err());
-            assert_eq!(result.unwrap_err().to_string(), error_message);
+        for range in [0..content.len() as u64, 3..4 * 1024 * 1024 + 5] {
+            let chunks: Vec<Bytes> = AdaptiveChunkStream::new(reader.clone(), range.clone())
+                .try_collect()
+                .await
+                .unwrap();
+
+            // The fast reads grow the chunk size from the initial chunk size.
+            assert!(chunks.len() > 1);
+            assert_eq!(
+                chunks[0].len() as u64,
+                AdaptiveChunkStream::INITIAL_CHUNK_SIZE
+            );
+            assert!(chunks[1].len() as u64 > AdaptiveChunkStream::INITIAL_CHUNK_SIZE);
+            assert_eq!(
+                chunks.concat(),
+                fixed_content[range.start as usize..range.end as usize]
+            );
         }
     }
 
-    #[test]
-    fn should_handle_insecure_skip_verify_parameter() {
-        let test_cases = vec![
-            ObjectStorageInfo {
-                endpoint: Some("https://oss-cn-beijing.aliyuncs.com".into()),
-                access_key_id: Some("test-access-key-id".into()),
-                access_key_secret: Some("test-access-key-secret".into()),
-                insecure_skip_verify: Some(true),
-                ..Default::default()
-            },
-            ObjectStorageInfo {
-                endpoint: Some("https://oss-cn-beijing.aliyuncs.com".into()),
-                access_key_id: Some("test-access-key-id".into()),
-                access_key_secret: Some("test-access-key-secret".into()),
-                insecure_skip_verify: Some(false),
-                ..Default::default()
-            },
-            ObjectStorageInfo {
-                endpoint: Some("https://oss-cn-beijing.aliyuncs.com".into()),
-                access_key_id: Some("test-access-key-id".into()),
-                access_key_secret: Some("test-access-key-secret".into()),
-                insecure_skip_verify: None,
-                ..Default::default()
-            },
-        ];
+    #[tokio::test]
+    async fn should_adapt_chunk_size_by_read_duration() {
+        let dir = tempfile::tempdir().unwrap();
+        let operator = Operator::new(
+            opendal::services::Fs::default().root(dir.path().to_string_lossy().as_ref()),
+        )
+        .unwrap()
+        .finish();
+        operator.write("object", vec![0u8]).await.unwrap();
 
-        for object_storage in test_cases {
-            let config = Arc::new(Config::default());
-            let backend = ObjectStorage::new(Scheme::OSS, config).unwrap();
-            let url: Url = "oss://test-bucket/file".parse().unwrap();
-            let parsed_url: ParsedURL = url.try_into().unwrap();
+        let mut stream = AdaptiveChunkStream::new(operator.reader("object").await.unwrap(), 0..1);
 
-            let result =
-                backend.operator(&parsed_url, Some(object_storage), Duration::from_secs(3));
-            assert!(result.is_ok());
+        for _ in 0..16 {
+            stream.adapt(Duration::from_millis(10));
+        }
+        assert_eq!(stream.chunk_size, AdaptiveChunkStream::MAX_CHUNK_SIZE);
+
+        stream.adapt(Duration::from_secs(1));
+        assert_eq!(stream.chunk_size, AdaptiveChunkStream::MAX_CHUNK_SIZE);
+
+        for _ in 0..16 {
+            stream.adapt(Duration::from_secs(10));
         }
+        assert_eq!(stream.chunk_size, AdaptiveChunkStream::MIN_CHUNK_SIZE);
+    }
+
+    #[tokio::test]
+    async fn should_return_error_when_copy_across_schemes() {
+        let backend = ObjectStorage::new(Scheme::S3, Arc::new(Config::default())).unwrap();
+        let result = backend
+            .copy(CopyRequest {
+                source_url: "s3://test-bucket/source".to_string(),
+                dest_url: "gs://test-bucket/dest".to_string(),
+                timeout: Duration::from_secs(3),
+                object_storage: None,
+            })
+            .await;
+
+        assert!(matches!(result, Err(ClientError::InvalidParameter)));
     }
 }
@@ -32,7 +32,7 @@ use rustls_pki_types::CertificateDer;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fmt;
-use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
 use std::path::PathBuf;
 use std::time::Duration;
 use tokio::fs;
@@ -188,11 +188,24 @@ fn default_backend_put_concurrent_chunk_count() -> u32 {
     16
 }
 
+/// default_backend_max_prefetch_chunks is the default maximum number of the chunks prefetched
+/// from the object storage.
+#[inline]
+fn default_backend_max_prefetch_chunks() -> u32 {
+    16
+}
+
 /// default_backend_put_chunk_size is the default chunk size for uploading, default is 8MiB.
 fn default_backend_put_chunk_size() -> ByteSize {
     ByteSize::mib(8)
 }
 
+/// default_backend_file_mmap_threshold is the default minimum size of the content read by the
+/// memory map in the file backend, default is 64MiB.
+fn default_backend_file_mmap_threshold() -> ByteSize {
+    ByteSize::mib(64)
+}
+
 /// default_backend_put_timeout is the default timeout for uploading a file to backend, default is
 /// 15 minutes.
 fn default_backend_put_timeout() -> Duration {
@@ -204,6 +217,61 @@ fn default_backend_enable_hickory_dns() -> bool {
     true
 }
 
+/// default_backend_object_storage_stat_cache_ttl is the default TTL for the cached stat of the
+/// object storage, default is 1 minute.
+#[inline]
+fn default_backend_object_storage_stat_cache_ttl() -> Duration {
+    Duration::from_secs(60)
+}
+
+/// default_backend_pool_idle_timeout is the default timeout for the idle connections in the pool
+/// of the backend, default is 30 seconds.
+#[inline]
+fn default_backend_pool_idle_timeout() -> Option<Duration> {
+    Some(Duration::from_secs(30))
+}
+
+/// default_backend_retry_after_max_retries is the default max retries for the throttled responses
+/// of the backend.
+#[inline]
+fn default_backend_retry_after_max_retries() -> u32 {
+    3
+}
+
+/// default_backend_min_tls_version is the default minimum TLS version of the backend clients,
+/// default is TLS 1.2.
+#[inline]
+fn default_backend_min_tls_version() -> Option<TLSVersion> {
+    Some(TLSVersion::TLS12)
+}
+
+/// default_backend_object_storage_credentials_file is the default shared credentials file of
+/// the object storage profiles, default is `~/.aws/credentials`.
+#[inline]
+fn default_backend_object_storage_credentials_file() -> PathBuf {
+    home::home_dir()
+        .unwrap_or_default()
+        .join(".aws")
+        .join("credentials")
+}
+
+/// default_backend_decode_object_storage_key is the default option for decoding the object
+/// storage key in the URL path.
+#[inline]
+fn default_backend_decode_object_storage_key() -> bool {
+    true
+}
+
+/// default_backend_default_regions is the default regions of the object storage schemes,
+/// default is `us-east-1` for S3 and the generic S3-compatible object storage.
+#[inline]
+fn default_backend_default_regions() -> HashMap<String, String> {
+    HashMap::from([
+        ("s3".to_string(), "us-east-1".to_string()),
+        ("s3c".to_string(), "us-east-1".to_string()),
+    ])
+}
+
 /// default_download_max_schedule_count is the default max count of schedule.
 #[inline]
 fn default_download_max_schedule_count() -> u32 {
@@ -834,6 +902,38 @@ impl Scheduler {
     }
 }
 
+/// TLSVersion is the version of the TLS protocol.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum TLSVersion {
+    /// TLS12 indicates the TLS 1.2.
+    #[serde(rename = "1.2")]
+    TLS12,
+
+    /// TLS13 indicates the TLS 1.3.
+    #[serde(rename = "1.3")]
+    TLS13,
+}
+
+/// TLSVersion implements Display.
+impl fmt::Display for TLSVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TLSVersion::TLS12 => write!(f, "1.2"),
+            TLSVersion::TLS13 => write!(f, "1.3"),
+        }
+    }
+}
+
+/// TLSVersion implements conversion to the TLS version of reqwest.
+impl From<TLSVersion> for reqwest::tls::Version {
+    fn from(version: TLSVersion) -> Self {
+        match version {
+            TLSVersion::TLS12 => reqwest::tls::Version::TLS_1_2,
+            TLSVersion::TLS13 => reqwest::tls::Version::TLS_1_3,
+        }
+    }
+}
+
 /// HostType is the type of the host.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
 pub enum HostType {
@@ -1559,6 +1659,150 @@ pub struct Backend {
         rename = "enableHickoryDNS"
     )]
     pub enable_hickory_dns: bool,
+
+    /// Retry after max retries specifies the maximum number of retries when the HTTP backend
+    /// responds with 429 Too Many Requests or 503 Service Unavailable. Each retry waits for the
+    /// duration of the Retry-After header, or uses exponential backoff if the header is absent.
+    /// The total wait is capped by the request timeout.
+    #[serde(default = "default_backend_retry_after_max_retries")]
+    pub retry_after_max_retries: u32,
+
+    /// Enable object storage stat cache controls whether to cache the stat of the object storage
+    /// for the ranged gets. Each piece of the same object is validated by the cached ETag instead
+    /// of a fresh stat request, and the cached stat is invalidated if the ETag changes.
+    pub enable_object_storage_stat_cache: bool,
+
+    /// Object storage stat cache TTL specifies the time-to-live for the cached stat of the object
+    /// storage. After this duration, the object will be stat again.
+    #[serde(
+        default = "default_backend_object_storage_stat_cache_ttl",
+        rename = "objectStorageStatCacheTTL",
+        with = "humantime_serde"
+    )]
+    pub object_storage_stat_cache_ttl: Duration,
+
+    /// Pool idle timeout specifies the timeout for the idle connections in the connection pool
+    /// of the backend clients. The idle connections may be closed by the server, so a shorter
+    /// timeout reduces the connection reset errors when reusing the stale connections.
+    /// If it is None, the idle connections are never closed by the client.
+    #[serde(
+        default = "default_backend_pool_idle_timeout",
+        with = "humantime_serde"
+    )]
+    pub pool_idle_timeout: Option<Duration>,
+
+    /// Pool max idle per host specifies the maximum number of idle connections per host in the
+    /// connection pool of the backend clients. If it is None, the default value is used.
+    pub pool_max_idle_per_host: Option<usize>,
+
+    /// File root dir restricts the file backend to the files under the directory, the requests
+    /// of the `file://` scheme escaping the directory are rejected. If it is None, the file
+    /// backend is not loaded and the `file://` scheme is unsupported.
+    pub file_root_dir: Option<PathBuf>,
+
+    /// Enable file mmap enables the memory map reads of the file backend, which takes effect
+    /// with the mmap feature of the backend. The file truncated by another process while it
+    /// is mapped crashes dfdaemon with SIGBUS, so it must be enabled only if the files are
+    /// never modified while they are read, and it is disabled by default.
+    pub enable_file_mmap: bool,
+
+    /// File mmap threshold is the minimum size of the content read by the memory map in the file
+    /// backend, the smaller content is read by the buffered reads. It takes effect if the file
+    /// mmap is enabled, and the buffered reads are used if the memory map fails.
+    #[serde(
+        default = "default_backend_file_mmap_threshold",
+        with = "bytesize_serde"
+    )]
+    pub file_mmap_threshold: ByteSize,
+
+    /// Object storage redirect hosts are the regexes of the S3 hosts, such as
+    /// `^.+\.s3\..+\.amazonaws\.com$`. When the HTTP backend is redirected to the matched host,
+    /// e.g. the CDN redirects to the presigned S3 URL, the request is handed off to the S3 backend
+    /// with the object storage credentials of the request instead of following the redirect.
+    /// If it is empty, the redirects are always followed.
+    #[serde(with = "serde_regex")]
+    pub object_storage_redirect_hosts: Vec<Regex>,
+
+    /// Min TLS version is the minimum TLS version accepted by the backend clients, including
+    /// the HTTP, object storage, hugging face and model scope backends. Supported values are
+    /// `1.2` and `1.3`. If it is None, the default TLS versions of the clients are used.
+    #[serde(default = "default_backend_min_tls_version", rename = "minTLSVersion")]
+    pub min_tls_version: Option<TLSVersion>,
+
+    /// Default regions are the regions of the object storage keyed by the scheme, such as
+    /// `s3: us-east-1`. The default region is used when the region is neither provided by the
+    /// request nor embedded in the URL host, which avoids the signature region mismatch of the
+    /// non-AWS endpoints.
+    #[serde(default = "default_backend_default_regions")]
+    pub default_regions: HashMap<String, String>,
+
+    /// DNS overrides are the static addresses of the object storage endpoint hosts, such as
+    /// `s3.internal: 10.0.0.1:443`. The host resolves to the configured address regardless of
+    /// the system DNS, and the port of the endpoint URL is used instead of the port of the
+    /// address.
+    pub dns_overrides: HashMap<String, SocketAddr>,
+
+    /// Object storage profile is the name of the profile in the shared credentials file, such
+    /// as `default`. If it is set, the S3 credentials of the profile are used when the request
+    /// has no inline credentials and no endpoint. The credentials file is loaded by the first
+    /// request using the profile and reloaded when it is modified, so the invalid file only fails
+    /// the requests using the profile. If it is None, only the inline credentials are used.
+    pub object_storage_profile: Option<String>,
+
+    /// Object storage credentials file is the shared credentials file of the object storage
+    /// profile, default is `~/.aws/credentials`.
+    #[serde(default = "default_backend_object_storage_credentials_file")]
+    pub object_storage_credentials_file: PathBuf,
+
+    /// Enable object storage application default credentials controls whether GCS uses the
+    /// Application Default Credentials, such as the `GOOGLE_APPLICATION_CREDENTIALS` environment
+    /// variable and the credentials file of `gcloud auth application-default login`, when the
+    /// request has no credential path. If it is false, only the credential path of the request
+    /// and the VM metadata are used.
+    pub enable_object_storage_application_default_credentials: bool,
+
+    /// Decode object storage key controls whether the percent-encoded object key in the URL
+    /// path is decoded to the raw key handed to the object storage, such as `a%20b` to `a b`.
+    /// The object storage encodes the raw key of the request internally. If it is false, the
+    /// key is used verbatim, which addresses the keys containing the literal `%`.
+    #[serde(default = "default_backend_decode_object_storage_key")]
+    pub decode_object_storage_key: bool,
+
+    /// Enable https to http fallback controls whether the `https://` URL built with the
+    /// fallback is retried as the equivalent `http://` URL if the HTTPS endpoint fails at the
+    /// connection or TLS layer. It is disabled by default, because downgrading exposes the
+    /// requests to the eavesdropping and tampering. The URL is never downgraded if the HTTPS
+    /// endpoint responds, such as 401 Unauthorized or 404 Not Found.
+    pub enable_https_to_http_fallback: bool,
+
+    /// Enable object storage checksum validation controls whether the get of the whole object
+    /// from S3 or GCS is verified against the checksum declared by the service, such as the
+    /// `x-amz-checksum-crc32c` or the `x-goog-hash` header of the get response. The whole
+    /// object is read by a presigned request instead of the operator, so it is disabled by
+    /// default. The supported algorithms are CRC32C and SHA256, the object without the full
+    /// object checksum is not verified.
+    pub enable_object_storage_checksum_validation: bool,
+
+    /// Host header overrides are the `Host` headers of the S3 requests keyed by the authority
+    /// of the endpoint, such as `proxy.internal:8443: s3.example.com`, which are used when the
+    /// object storage is behind a reverse proxy. The requests to the endpoint are signed with
+    /// the overridden host and sent to the endpoint, so the endpoint override and the failover
+    /// endpoints use their own hosts unless they are configured. If the authority of the
+    /// endpoint is not configured, the host of the endpoint is used.
+    pub host_header_overrides: HashMap<String, String>,
+
+    /// Endpoint env allowlist is the names of the environment variables that can be expanded
+    /// in the object storage endpoint, such as `REGION` in `https://${REGION}.oss.example.com`.
+    /// The endpoint is provided by the request, so the other variables are rejected instead of
+    /// leaking the environment of dfdaemon into the host. If it is empty, the endpoint is not
+    /// expanded.
+    pub endpoint_env_allowlist: Vec<String>,
+
+    /// Max prefetch chunks is the maximum number of the chunks fetched concurrently ahead of the
+    /// read cursor of the object storage. The prefetch chunks of the request are clamped to it,
+    /// which bounds the memory of the buffered chunks and the concurrent requests to the backend.
+    #[serde(default = "default_backend_max_prefetch_chunks")]
+    pub max_prefetch_chunks: u32,
 }
 
 /// Backend implements Default.
@@ -1572,6 +1816,27 @@ impl Default for Backend {
             put_chunk_size: default_backend_put_chunk_size(),
             put_timeout: default_backend_put_timeout(),
             enable_hickory_dns: default_backend_enable_hickory_dns(),
+            retry_after_max_retries: default_backend_retry_after_max_retries(),
+            enable_object_storage_stat_cache: false,
+            object_storage_stat_cache_ttl: default_backend_object_storage_stat_cache_ttl(),
+            pool_idle_timeout: default_backend_pool_idle_timeout(),
+            pool_max_idle_per_host: None,
+            file_root_dir: None,
+            enable_file_mmap: false,
+            file_mmap_threshold: default_backend_file_mmap_threshold(),
+            object_storage_redirect_hosts: Vec::new(),
+            min_tls_version: default_backend_min_tls_version(),
+            default_regions: default_backend_default_regions(),
+            dns_overrides: HashMap::new(),
+            object_storage_profile: None,
+            object_storage_credentials_file: default_backend_object_storage_credentials_file(),
+            enable_object_storage_application_default_credentials: false,
+            decode_object_storage_key: default_backend_decode_object_storage_key(),
+            enable_https_to_http_fallback: false,
+            enable_object_storage_checksum_validation: false,
+            host_header_overrides: HashMap::new(),
+            endpoint_env_allowlist: Vec::new(),
+            max_prefetch_chunks: default_backend_max_prefetch_chunks(),
         }
     }
 }
@@ -2292,7 +2557,34 @@ key: /etc/ssl/private/client.pem
             "putConcurrentChunkCount": 2,
             "putChunkSize": "2mib",
             "putTimeout": "1m",
-            "enableHickoryDNS": false
+            "enableHickoryDNS": false,
+            "retryAfterMaxRetries": 5,
+            "enableObjectStorageStatCache": true,
+            "objectStorageStatCacheTTL": "30s",
+            "poolIdleTimeout": "1m",
+            "poolMaxIdlePerHost": 64,
+            "fileRootDir": "/data/artifacts",
+            "enableFileMmap": true,
+            "fileMmapThreshold": "16mib",
+            "objectStorageRedirectHosts": ["^.+\\.s3\\..+\\.amazonaws\\.com$"],
+            "minTLSVersion": "1.3",
+            "defaultRegions": {
+                "s3": "eu-west-1"
+            },
+            "dnsOverrides": {
+                "s3.internal": "10.0.0.1:443"
+            },
+            "objectStorageProfile": "production",
+            "objectStorageCredentialsFile": "/etc/dragonfly/credentials",
+            "enableObjectStorageApplicationDefaultCredentials": true,
+            "decodeObjectStorageKey": false,
+            "enableHttpsToHttpFallback": true,
+            "enableObjectStorageChecksumValidation": true,
+            "hostHeaderOverrides": {
+                "proxy.internal:8443": "s3.example.com"
+            },
+            "endpointEnvAllowlist": ["REGION"],
+            "maxPrefetchChunks": 8
         }"#;
 
         let backend: Backend = serde_json::from_str(json_data).unwrap();
@@ -2314,5 +2606,53 @@ key: /etc/ssl/private/client.pem
         assert_eq!(backend.put_chunk_size, ByteSize::mib(2));
         assert_eq!(backend.put_timeout, Duration::from_secs(60));
         assert!(!backend.enable_hickory_dns);
+        assert_eq!(backend.retry_after_max_retries, 5);
+        assert!(backend.enable_object_storage_stat_cache);
+        assert_eq!(
+            backend.object_storage_stat_cache_ttl,
+            Duration::from_secs(30)
+        );
+        assert_eq!(backend.pool_idle_timeout, Some(Duration::from_secs(60)));
+        assert_eq!(backend.pool_max_idle_per_host, Some(64));
+        assert_eq!(
+            backend.file_root_dir,
+            Some(PathBuf::from("/data/artifacts"))
+        );
+        assert!(backend.enable_file_mmap);
+        assert_eq!(backend.file_mmap_threshold, ByteSize::mib(16));
+        assert_eq!(backend.object_storage_redirect_hosts.len(), 1);
+        assert!(
+            backend.object_storage_redirect_hosts[0].is_match("bucket.s3.us-east-1.amazonaws.com")
+        );
+        assert_eq!(backend.min_tls_version, Some(TLSVersion::TLS13));
+        assert_eq!(
+            backend.default_regions.get("s3"),
+            Some(&"eu-west-1".to_string())
+        );
+        assert_eq!(
+            backend.dns_overrides.get("s3.internal"),
+            Some(&"10.0.0.1:443".parse::<SocketAddr>().unwrap())
+        );
+        assert_eq!(
+            backend.object_storage_profile,
+            Some("production".to_string())
+        );
+        assert_eq!(
+            backend.object_storage_credentials_file,
+            PathBuf::from("/etc/dragonfly/credentials")
+        );
+        assert!(backend.enable_object_storage_application_default_credentials);
+        assert!(!backend.decode_object_storage_key);
+        assert!(backend.enable_https_to_http_fallback);
+        assert!(backend.enable_object_storage_checksum_validation);
+        assert_eq!(
+            backend
+                .host_header_overrides
+                .get("proxy.internal:8443")
+                .map(String::as_str),
+            Some("s3.example.com")
+        );
+        assert_eq!(backend.endpoint_env_allowlist, vec!["REGION".to_string()]);
+        assert_eq!(backend.max_prefetch_chunks, 8);
     }
 }
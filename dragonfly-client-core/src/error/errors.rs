@@ -171,6 +171,34 @@ pub struct BackendError {
     pub header: Option<reqwest::header::HeaderMap>,
 }
 
+/// BackendError implements the conversion from the other errors.
+impl BackendError {
+    /// From opendal converts the error of opendal to the BackendError. The status code is
+    /// mapped from the kind of the error, opendal does not expose the response of the error.
+    pub fn from_opendal(err: &opendal::Error) -> BackendError {
+        BackendError {
+            message: err.to_string(),
+            status_code: status_code_from_opendal_kind(err.kind()),
+            header: None,
+        }
+    }
+}
+
+/// Status code from opendal kind maps the kind of the opendal error to the status code of the
+/// response, and returns None if the kind has no corresponding status code.
+fn status_code_from_opendal_kind(kind: opendal::ErrorKind) -> Option<reqwest::StatusCode> {
+    match kind {
+        opendal::ErrorKind::NotFound => Some(reqwest::StatusCode::NOT_FOUND),
+        opendal::ErrorKind::PermissionDenied => Some(reqwest::StatusCode::FORBIDDEN),
+        opendal::ErrorKind::RateLimited => Some(reqwest::StatusCode::TOO_MANY_REQUESTS),
+        opendal::ErrorKind::AlreadyExists => Some(reqwest::StatusCode::CONFLICT),
+        opendal::ErrorKind::ConditionNotMatch => Some(reqwest::StatusCode::PRECONDITION_FAILED),
+        opendal::ErrorKind::RangeNotSatisfied => Some(reqwest::StatusCode::RANGE_NOT_SATISFIABLE),
+        opendal::ErrorKind::Unsupported => Some(reqwest::StatusCode::NOT_IMPLEMENTED),
+        _ => None,
+    }
+}
+
 /// DownloadFromParentFailed is the error when the download from parent is failed.
 #[derive(Debug, thiserror::Error)]
 #[error("download piece {piece_number} from parent {parent_id} failed")]
@@ -211,6 +239,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn should_convert_opendal_error_to_backend_error() {
+        let test_cases = vec![
+            (
+                opendal::Error::new(opendal::ErrorKind::NotFound, "not found"),
+                Some(reqwest::StatusCode::NOT_FOUND),
+            ),
+            (
+                opendal::Error::new(opendal::ErrorKind::PermissionDenied, "denied"),
+                Some(reqwest::StatusCode::FORBIDDEN),
+            ),
+            (
+                opendal::Error::new(opendal::ErrorKind::RateLimited, "slow down"),
+                Some(reqwest::StatusCode::TOO_MANY_REQUESTS),
+            ),
+            (
+                opendal::Error::new(opendal::ErrorKind::ConditionNotMatch, "etag mismatch"),
+                Some(reqwest::StatusCode::PRECONDITION_FAILED),
+            ),
+            (
+                opendal::Error::new(opendal::ErrorKind::RangeNotSatisfied, "range"),
+                Some(reqwest::StatusCode::RANGE_NOT_SATISFIABLE),
+            ),
+            (
+                opendal::Error::new(opendal::ErrorKind::Unexpected, "unexpected"),
+                None,
+            ),
+        ];
+
+        for (err, expected) in test_cases {
+            let backend_error = BackendError::from_opendal(&err);
+            assert_eq!(backend_error.status_code, expected, "{}", err);
+            assert_eq!(backend_error.message, err.to_string());
+        }
+    }
+
     #[test]
     fn should_extend_result_with_error() {
         let result: Result<(), std::io::Error> = Err(std::io::Error::new(
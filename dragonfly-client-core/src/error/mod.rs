@@ -23,6 +23,8 @@ pub use errors::ExternalError;
 pub use errors::OrErr;
 pub use errors::{BackendError, DownloadFromParentFailed};
 
+use std::time::Duration;
+
 /// DFError is the error for dragonfly.
 #[derive(thiserror::Error, Debug)]
 pub enum DFError {
@@ -166,6 +168,38 @@ pub enum DFError {
     #[error{"unauthorized"}]
     Unauthorized,
 
+    /// Cancelled is the error when the operation is cancelled by the cancel token.
+    #[error{"cancelled"}]
+    Cancelled,
+
+    /// PreconditionFailed is the error when the precondition of the request is not met, such as
+    /// the object already exists for the conditional write.
+    #[error("precondition failed: {0}")]
+    PreconditionFailed(String),
+
+    /// Throttled is the error when the request is throttled by the server, such as the
+    /// `503 SlowDown` of S3. The retry after is the backoff hinted by the server.
+    #[error("throttled, retry after {retry_after:?}")]
+    Throttled { retry_after: Option<Duration> },
+
+    /// ClockSkew is the error when the server rejects the request because the clock of the
+    /// host differs too much from the clock of the server, such as the `RequestTimeTooSkewed`
+    /// of S3. The server time is the time reported by the server if present.
+    #[error("request time too skewed from server time {server_time:?}, check the clock of the host is synchronized by NTP")]
+    ClockSkew {
+        server_time: Option<chrono::DateTime<chrono::Utc>>,
+    },
+
+    /// StalledTransfer is the error when the throughput of the transfer drops below the minimum
+    /// throughput over the window, such as the body trickling at a few bytes per second, which
+    /// the overall timeout misses because the data still flows.
+    #[error("transfer stalled at {throughput} bytes/s, below the minimum throughput of {min_throughput} bytes/s over {window:?}")]
+    StalledTransfer {
+        throughput: u64,
+        min_throughput: u64,
+        window: Duration,
+    },
+
     /// ArrayTryFromSliceError is the error for array try from slice.
     #[error(transparent)]
     ArrayTryFromSliceError(#[from] std::array::TryFromSliceError),
@@ -256,6 +290,38 @@ pub enum DFError {
     #[error("unsupported {0}")]
     Unsupported(String),
 
+    /// UnsupportedScheme is the error when the scheme of the url is not registered.
+    #[error("unsupported scheme {scheme}, available schemes: {}", available.join(", "))]
+    UnsupportedScheme {
+        /// scheme is the scheme of the url.
+        scheme: String,
+
+        /// available is the registered schemes.
+        available: Vec<String>,
+    },
+
+    /// TransferSourceFailed is the error when the source of the transfer fails, such as the get
+    /// of the source url.
+    #[error("transfer source {url} failed: {err}")]
+    TransferSourceFailed {
+        /// url is the source url of the transfer.
+        url: String,
+
+        /// err is the error of the source.
+        err: Box<DFError>,
+    },
+
+    /// TransferDestinationFailed is the error when the destination of the transfer fails, such
+    /// as the put of the destination url.
+    #[error("transfer destination {url} failed: {err}")]
+    TransferDestinationFailed {
+        /// url is the destination url of the transfer.
+        url: String,
+
+        /// err is the error of the destination.
+        err: Box<DFError>,
+    },
+
     /// TokioJoinError is the error for tokio join.
     #[error(transparent)]
     TokioJoinError(tokio::task::JoinError),
@@ -270,6 +336,96 @@ pub enum DFError {
     CgroupsFSError(#[from] cgroups_rs::fs::error::Error),
 }
 
+/// DFError implements the classification of the errors.
+impl DFError {
+    /// Is retryable returns whether the error is transient, such as the timeouts, the connection
+    /// resets, the 5xx responses and the throttling, so the request can be retried. The terminal
+    /// errors, such as the 404 and 403 responses and the malformed URLs, are not retryable.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            DFError::IO(err) => is_retryable_io_error(err),
+            DFError::SendTimeout
+            | DFError::DownloadPieceFinishedTimeout(_)
+            | DFError::WaitForPieceFinishedTimeout(_)
+            | DFError::TokioStreamElapsed(_)
+            | DFError::TokioTimeErrorElapsed(_)
+            | DFError::Throttled { .. }
+            | DFError::StalledTransfer { .. } => true,
+            DFError::ReqwestError(err) => is_retryable_reqwest_error(err),
+            DFError::ReqwestMiddlewareError(reqwest_middleware::Error::Reqwest(err)) => {
+                is_retryable_reqwest_error(err)
+            }
+            DFError::ReqwestMiddlewareError(reqwest_middleware::Error::Middleware(err)) => err
+                .chain()
+                .any(|err| match err.downcast_ref::<reqwest::Error>() {
+                    Some(err) => is_retryable_reqwest_error(err),
+                    None => err
+                        .downcast_ref::<std::io::Error>()
+                        .is_some_and(is_retryable_io_error),
+                }),
+            DFError::OpenDALError(err) => {
+                err.is_temporary() || err.kind() == opendal::ErrorKind::RateLimited
+            }
+            DFError::HyperError(err) => {
+                err.is_timeout() || err.is_incomplete_message() || err.is_closed()
+            }
+            DFError::HyperUtilClientLegacyError(err) => err.is_connect(),
+            DFError::BackendError(err) => err.status_code.is_some_and(is_retryable_status_code),
+            DFError::TransferSourceFailed { err, .. }
+            | DFError::TransferDestinationFailed { err, .. } => err.is_retryable(),
+            DFError::TonicStatus(status) => matches!(
+                status.code(),
+                tonic::Code::Unavailable
+                    | tonic::Code::DeadlineExceeded
+                    | tonic::Code::ResourceExhausted
+                    | tonic::Code::Aborted
+            ),
+            _ => false,
+        }
+    }
+}
+
+/// Is retryable status code returns whether the status code of the response is transient, such
+/// as the 5xx responses except the unsupported ones, the 408 and the 429.
+fn is_retryable_status_code(status_code: http::StatusCode) -> bool {
+    match status_code {
+        http::StatusCode::REQUEST_TIMEOUT | http::StatusCode::TOO_MANY_REQUESTS => true,
+        http::StatusCode::NOT_IMPLEMENTED | http::StatusCode::HTTP_VERSION_NOT_SUPPORTED => false,
+        _ => status_code.is_server_error(),
+    }
+}
+
+/// Is retryable reqwest error returns whether the reqwest error is transient. The error of the
+/// response body is transient, because the connection is interrupted while reading the body.
+fn is_retryable_reqwest_error(err: &reqwest::Error) -> bool {
+    if let Some(status_code) = err.status() {
+        return is_retryable_status_code(status_code);
+    }
+
+    err.is_timeout() || err.is_connect() || err.is_body()
+}
+
+/// Is retryable io error returns whether the io error is transient. The io error wrapping the
+/// reqwest error, such as the error of the response body stream, is classified by the inner error.
+fn is_retryable_io_error(err: &std::io::Error) -> bool {
+    if let Some(inner) = err.get_ref() {
+        if let Some(err) = inner.downcast_ref::<reqwest::Error>() {
+            return is_retryable_reqwest_error(err);
+        }
+    }
+
+    matches!(
+        err.kind(),
+        std::io::ErrorKind::TimedOut
+            | std::io::ErrorKind::ConnectionReset
+            | std::io::ErrorKind::ConnectionAborted
+            | std::io::ErrorKind::ConnectionRefused
+            | std::io::ErrorKind::BrokenPipe
+            | std::io::ErrorKind::UnexpectedEof
+            | std::io::ErrorKind::Interrupted
+    )
+}
+
 /// SendError is the error for send.
 impl<T> From<tokio::sync::mpsc::error::SendError<T>> for DFError {
     fn from(e: tokio::sync::mpsc::error::SendError<T>) -> Self {
@@ -308,4 +464,123 @@ mod tests {
         let err = do_sth_with_error().err().unwrap();
         assert_eq!(format!("{}", err), "StorageError cause: inner error");
     }
+
+    #[test]
+    fn should_classify_retryable_errors() {
+        let test_cases = vec![
+            (
+                DFError::IO(std::io::Error::from(std::io::ErrorKind::TimedOut)),
+                true,
+            ),
+            (
+                DFError::IO(std::io::Error::from(std::io::ErrorKind::ConnectionReset)),
+                true,
+            ),
+            (
+                DFError::IO(std::io::Error::from(std::io::ErrorKind::NotFound)),
+                false,
+            ),
+            (DFError::Throttled { retry_after: None }, true),
+            (DFError::ClockSkew { server_time: None }, false),
+            (
+                DFError::StalledTransfer {
+                    throughput: 10,
+                    min_throughput: 1024,
+                    window: Duration::from_secs(30),
+                },
+                true,
+            ),
+            (
+                DFError::BackendError(Box::new(BackendError {
+                    message: "internal server error".to_string(),
+                    status_code: Some(http::StatusCode::INTERNAL_SERVER_ERROR),
+                    header: None,
+                })),
+                true,
+            ),
+            (
+                DFError::BackendError(Box::new(BackendError {
+                    message: "service unavailable".to_string(),
+                    status_code: Some(http::StatusCode::SERVICE_UNAVAILABLE),
+                    header: None,
+                })),
+                true,
+            ),
+            (
+                DFError::BackendError(Box::new(BackendError {
+                    message: "not found".to_string(),
+                    status_code: Some(http::StatusCode::NOT_FOUND),
+                    header: None,
+                })),
+                false,
+            ),
+            (
+                DFError::BackendError(Box::new(BackendError {
+                    message: "forbidden".to_string(),
+                    status_code: Some(http::StatusCode::FORBIDDEN),
+                    header: None,
+                })),
+                false,
+            ),
+            (
+                DFError::TransferSourceFailed {
+                    url: "http://example.com/file".to_string(),
+                    err: Box::new(DFError::SendTimeout),
+                },
+                true,
+            ),
+            (
+                DFError::TransferDestinationFailed {
+                    url: "s3://bucket/file".to_string(),
+                    err: Box::new(DFError::Unauthorized),
+                },
+                false,
+            ),
+            (
+                DFError::OpenDALError(
+                    opendal::Error::new(opendal::ErrorKind::Unexpected, "reset").set_temporary(),
+                ),
+                true,
+            ),
+            (
+                DFError::OpenDALError(opendal::Error::new(
+                    opendal::ErrorKind::RateLimited,
+                    "slow down",
+                )),
+                true,
+            ),
+            (
+                DFError::OpenDALError(opendal::Error::new(
+                    opendal::ErrorKind::PermissionDenied,
+                    "denied",
+                )),
+                false,
+            ),
+            (
+                DFError::URLParseError(url::ParseError::RelativeUrlWithoutBase),
+                false,
+            ),
+            (DFError::InvalidURI("invalid".to_string()), false),
+            (DFError::TonicStatus(tonic::Status::unavailable("")), true),
+            (DFError::TonicStatus(tonic::Status::not_found("")), false),
+        ];
+
+        for (err, expected) in test_cases {
+            assert_eq!(err.is_retryable(), expected, "{:?}", err);
+        }
+    }
+
+    #[tokio::test]
+    async fn should_classify_retryable_reqwest_errors() {
+        // The port of the dropped listener refuses the connections.
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        drop(listener);
+
+        let err = reqwest::get(format!("http://{}", addr)).await.unwrap_err();
+        assert!(DFError::ReqwestError(err).is_retryable());
+
+        let err = reqwest::get("http://[invalid").await.unwrap_err();
+        assert!(!DFError::ReqwestError(err).is_retryable());
+    }
 }
@@ -530,5 +530,8 @@ async fn main() -> Result<(), anyhow::Error> {
     // Wait for the other server to exit.
     let _ = shutdown_complete_rx.recv().await;
 
+    // Shutdown the backends to flush the buffered data after the servers exit.
+    backend_factory.shutdown_all().await;
+
     Ok(())
 }
@@ -67,6 +67,9 @@ Examples:
   # Download a file from HDFS.
   $ dfget hdfs://<host>:<port>/<path> -O /tmp/file.txt --hdfs-delegation-token=<delegation_token>
 
+  # Download a file from the local filesystem of dfdaemon, e.g. a shared NFS mount.
+  $ dfget file:///<path> -O /tmp/file.txt
+
   # Download a file from Amazon Simple Storage Service(S3).
   $ dfget s3://<bucket>/<path> -O /tmp/file.txt --storage-access-key-id=<access_key_id> --storage-access-key-secret=<access_key_secret>
 
@@ -85,6 +88,9 @@ Examples:
   # Download a file from Tencent Cloud Object Storage Service(COS).
   $ dfget cos://<bucket>/<path> -O /tmp/file.txt --storage-access-key-id=<access_key_id> --storage-access-key-secret=<access_key_secret> --storage-endpoint=<endpoint>
 
+  # Download a file from Oracle Cloud Infrastructure Object Storage(OCI).
+  $ dfget oci://<bucket>@<namespace>/<path> -O /tmp/file.txt --storage-access-key-id=<access_key_id> --storage-access-key-secret=<access_key_secret> --storage-region=<region>
+
   # Download a single file from Hugging Face Hub.
   $ dfget hf://<owner>/<repo>/<path> -O /tmp/model.safetensors
 
@@ -872,6 +878,9 @@ async fn get_all_entries(
                 url: url.to_string(),
                 content_length: 0,
                 is_dir: false,
+                etag: None,
+                last_modified: None,
+                content_type: None,
             });
 
             let parent = url.join(".").or_err(ErrorType::ParseError)?;
@@ -880,6 +889,9 @@ async fn get_all_entries(
                     url: parent.to_string(),
                     content_length: 0,
                     is_dir: true,
+                    etag: None,
+                    last_modified: None,
+                    content_type: None,
                 });
             }
 
@@ -916,6 +928,9 @@ async fn get_all_entries(
                     url: parent.to_string(),
                     content_length: 0,
                     is_dir: true,
+                    etag: None,
+                    last_modified: None,
+                    content_type: None,
                 });
             }
         }
@@ -1256,6 +1271,9 @@ async fn get_entries(
             url: entry.url,
             content_length: entry.content_length as usize,
             is_dir: entry.is_dir,
+            etag: None,
+            last_modified: None,
+            content_type: None,
         })
         .collect())
 }
@@ -1579,6 +1597,9 @@ mod tests {
                 .to_string(),
             content_length: 100,
             is_dir: false,
+            etag: None,
+            last_modified: None,
+            content_type: None,
         };
 
         let result = make_output_by_entry(url, output_path, entry);
@@ -1604,6 +1625,9 @@ mod tests {
                 .to_string(),
             content_length: 100,
             is_dir: false,
+            etag: None,
+            last_modified: None,
+            content_type: None,
         };
 
         let result = make_output_by_entry(url, &output_path, entry);
@@ -1623,6 +1647,9 @@ mod tests {
             url: "invalid_url".to_string(),
             content_length: 100,
             is_dir: false,
+            etag: None,
+            last_modified: None,
+            content_type: None,
         };
 
         let result = make_output_by_entry(url, output, entry);
@@ -1732,31 +1759,49 @@ mod tests {
                     url: "http://example.com/root/dir1/file1.txt".to_string(),
                     content_length: 100,
                     is_dir: false,
+                    etag: None,
+                    last_modified: None,
+                    content_type: None,
                 },
                 DirEntry {
                     url: "http://example.com/root/dir1/file2.txt".to_string(),
                     content_length: 100,
                     is_dir: false,
+                    etag: None,
+                    last_modified: None,
+                    content_type: None,
                 },
                 DirEntry {
                     url: "http://example.com/root/dir1/".to_string(),
                     content_length: 0,
                     is_dir: true,
+                    etag: None,
+                    last_modified: None,
+                    content_type: None,
                 },
                 DirEntry {
                     url: "http://example.com/root/dir2/file1.txt".to_string(),
                     content_length: 200,
                     is_dir: false,
+                    etag: None,
+                    last_modified: None,
+                    content_type: None,
                 },
                 DirEntry {
                     url: "http://example.com/root/dir2/file2.txt".to_string(),
                     content_length: 200,
                     is_dir: false,
+                    etag: None,
+                    last_modified: None,
+                    content_type: None,
                 },
                 DirEntry {
                     url: "http://example.com/root/dir2/".to_string(),
                     content_length: 0,
                     is_dir: true,
+                    etag: None,
+                    last_modified: None,
+                    content_type: None,
                 },
             ]
             .into_iter()
@@ -1818,11 +1863,17 @@ mod tests {
                     url: "http://example.com/root/file1.txt".to_string(),
                     content_length: 100,
                     is_dir: false,
+                    etag: None,
+                    last_modified: None,
+                    content_type: None,
                 },
                 DirEntry {
                     url: "http://example.com/root/file2.txt".to_string(),
                     content_length: 200,
                     is_dir: false,
+                    etag: None,
+                    last_modified: None,
+                    content_type: None,
                 },
             ]
             .into_iter()
@@ -1904,41 +1955,65 @@ mod tests {
                     url: "http://example.com/root/file1.txt".to_string(),
                     content_length: 100,
                     is_dir: false,
+                    etag: None,
+                    last_modified: None,
+                    content_type: None,
                 },
                 DirEntry {
                     url: "http://example.com/root/file2.txt".to_string(),
                     content_length: 200,
                     is_dir: false,
+                    etag: None,
+                    last_modified: None,
+                    content_type: None,
                 },
                 DirEntry {
                     url: "http://example.com/root/dir1/file1.txt".to_string(),
                     content_length: 100,
                     is_dir: false,
+                    etag: None,
+                    last_modified: None,
+                    content_type: None,
                 },
                 DirEntry {
                     url: "http://example.com/root/dir1/file2.txt".to_string(),
                     content_length: 100,
                     is_dir: false,
+                    etag: None,
+                    last_modified: None,
+                    content_type: None,
                 },
                 DirEntry {
                     url: "http://example.com/root/dir1/".to_string(),
                     content_length: 0,
                     is_dir: true,
+                    etag: None,
+                    last_modified: None,
+                    content_type: None,
                 },
                 DirEntry {
                     url: "http://example.com/root/dir2/file1.txt".to_string(),
                     content_length: 200,
                     is_dir: false,
+                    etag: None,
+                    last_modified: None,
+                    content_type: None,
                 },
                 DirEntry {
                     url: "http://example.com/root/dir2/file2.txt".to_string(),
                     content_length: 200,
                     is_dir: false,
+                    etag: None,
+                    last_modified: None,
+                    content_type: None,
                 },
                 DirEntry {
                     url: "http://example.com/root/dir2/".to_string(),
                     content_length: 0,
                     is_dir: true,
+                    etag: None,
+                    last_modified: None,
+                    content_type: None,
                 },
             ]
             .into_iter()
@@ -848,6 +848,21 @@ impl DfdaemonUpload for DfdaemonUploadServerHandler {
                 hdfs: request.hdfs.clone(),
                 hugging_face: request.hugging_face.clone(),
                 model_scope: request.model_scope.clone(),
+                page_size: None,
+                continuation_token: None,
+                cancel_token: None,
+                sniff_content_type: false,
+                retry_budget: None,
+                version_id: None,
+                list_metakeys: Vec::new(),
+                filter: None,
+                max_depth: None,
+                endpoint_override: None,
+                expected_bucket_owner: None,
+                head_via_get_fallback: false,
+                start_after: None,
+                stat_timeout: None,
+                trace_context: None,
             })
             .await
             .map_err(|err| {
@@ -858,6 +873,24 @@ impl DfdaemonUpload for DfdaemonUploadServerHandler {
                 Status::internal(err.to_string())
             })?;
 
+        // The backend returns the failed response instead of the error, such as 404 Not Found
+        // of the HTTP backend, and the partial entries of the listing failed midway, which must
+        // not be returned as the complete entries.
+        if !response.success {
+            // Collect the list tasks failure metrics.
+            collect_list_task_entries_failure_metrics(TaskType::Standard as i32);
+
+            let error_message = response.error_message.unwrap_or_default();
+            error!("list task entries failed: {}", error_message);
+            return Err(match response.http_status_code {
+                Some(http::StatusCode::NOT_FOUND) => Status::not_found(error_message),
+                Some(http::StatusCode::UNAUTHORIZED | http::StatusCode::FORBIDDEN) => {
+                    Status::permission_denied(error_message)
+                }
+                _ => Status::internal(error_message),
+            });
+        }
+
         Ok(Response::new(ListTaskEntriesResponse {
             content_length: response.content_length.unwrap_or_default(),
             response_header: headermap_to_hashmap(&response.http_header.unwrap_or_default()),
@@ -586,6 +586,8 @@ impl PersistentTask {
                 hdfs: None,
                 hugging_face: None,
                 model_scope: None,
+                if_not_exists: false,
+                acl: None,
             })
             .await
             .inspect_err(|err| {
@@ -2915,6 +2917,21 @@ impl PersistentTask {
                 hdfs: None,
                 hugging_face: None,
                 model_scope: None,
+                page_size: None,
+                continuation_token: None,
+                cancel_token: None,
+                sniff_content_type: false,
+                retry_budget: None,
+                version_id: None,
+                list_metakeys: Vec::new(),
+                filter: None,
+                max_depth: None,
+                endpoint_override: None,
+                expected_bucket_owner: None,
+                head_via_get_fallback: false,
+                start_after: None,
+                stat_timeout: None,
+                trace_context: None,
             })
             .await
             .inspect_err(|err| {
@@ -548,6 +548,22 @@ impl Piece {
                 hdfs,
                 hugging_face,
                 model_scope,
+                read_buffer_size: None,
+                max_body_size: None,
+                piece_length: None,
+                ranges: Vec::new(),
+                cancel_token: None,
+                retry_budget: None,
+                suffix_length: None,
+                version_id: None,
+                adaptive_chunking: false,
+                endpoint_override: None,
+                expected_bucket_owner: None,
+                auto_decompress: false,
+                trace_context: None,
+                prefetch_chunks: 0,
+                min_throughput: None,
+                min_throughput_window: None,
             })
             .await
             .inspect_err(|err| {
@@ -893,6 +909,22 @@ impl Piece {
                 hdfs,
                 hugging_face,
                 model_scope,
+                read_buffer_size: None,
+                max_body_size: None,
+                piece_length: None,
+                ranges: Vec::new(),
+                cancel_token: None,
+                retry_budget: None,
+                suffix_length: None,
+                version_id: None,
+                adaptive_chunking: false,
+                endpoint_override: None,
+                expected_bucket_owner: None,
+                auto_decompress: false,
+                trace_context: None,
+                prefetch_chunks: 0,
+                min_throughput: None,
+                min_throughput_window: None,
             })
             .await
             .inspect_err(|err| {
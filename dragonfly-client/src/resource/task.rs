@@ -205,6 +205,21 @@ impl Task {
                 hdfs: request.hdfs,
                 hugging_face: request.hugging_face,
                 model_scope: request.model_scope,
+                page_size: None,
+                continuation_token: None,
+                cancel_token: None,
+                sniff_content_type: false,
+                retry_budget: None,
+                version_id: None,
+                list_metakeys: Vec::new(),
+                filter: None,
+                max_depth: None,
+                endpoint_override: None,
+                expected_bucket_owner: None,
+                head_via_get_fallback: false,
+                start_after: None,
+                stat_timeout: None,
+                trace_context: None,
             })
             .await
             .inspect_err(|_err| {